@@ -0,0 +1,395 @@
+//! # Achievement Module
+//!
+//! Ce module gère les succès du jeu. La liste des succès est déclarative (`ACHIEVEMENTS`) :
+//! chaque succès associe une condition, évaluée à partir des événements de la partie,
+//! à une récompense éventuelle (un skin débloqué dans le menu des skins).
+//! Les succès débloqués sont sauvegardés sur le disque.
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::save::{read_key_values, write_key_values};
+use crate::sound::Sound;
+use crate::stats::{LifetimeStats, SessionStats};
+use crate::utils::{background_asteroids, handle_enter_press};
+use macroquad::prelude::*;
+use std::path::Path;
+use std::time::Instant;
+
+/// Condition à remplir pour débloquer un succès.
+pub enum Condition {
+    /// Détruire un nombre d'astéroïds d'une forme donnée (toutes parties confondues).
+    Destroy(AsteroidShape, u32),
+    /// Survivre un certain temps (en secondes) dans un mode de jeu.
+    Survive(&'static str, f64),
+    /// Gagner une partie dans un mode de jeu sans perdre de bouclier.
+    WinWithoutShieldLoss(&'static str),
+    /// Atteindre un combo donné.
+    Combo(u32),
+    /// Former un nombre de trous noirs (toutes parties confondues).
+    FormBlackholes(u32),
+}
+
+/// Définition d'un succès.
+pub struct Achievement {
+    /// Identifiant unique (utilisé pour la sauvegarde).
+    pub id: &'static str,
+    /// Nom affiché.
+    pub name: &'static str,
+    /// Description affichée.
+    pub description: &'static str,
+    /// Condition de déblocage.
+    pub condition: Condition,
+    /// Skin débloqué par le succès.
+    pub reward: Option<&'static str>,
+}
+
+/// Liste des succès du jeu.
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_blood",
+        name: "First Blood",
+        description: "Destroy a small asteroid",
+        condition: Condition::Destroy(AsteroidShape::Small, 1),
+        reward: None,
+    },
+    Achievement {
+        id: "pebble_crusher",
+        name: "Pebble Crusher",
+        description: "Destroy 100 small asteroids",
+        condition: Condition::Destroy(AsteroidShape::Small, 100),
+        reward: Some("assets/pictures/ships/spaceship4.png"),
+    },
+    Achievement {
+        id: "boulder_breaker",
+        name: "Boulder Breaker",
+        description: "Destroy 50 large asteroids",
+        condition: Condition::Destroy(AsteroidShape::Large, 50),
+        reward: None,
+    },
+    Achievement {
+        id: "modern_survivor",
+        name: "Modern Survivor",
+        description: "Survive 60 s in modern mode",
+        condition: Condition::Survive("modern", 60.0),
+        reward: Some("assets/pictures/backgrounds/background7.png"),
+    },
+    Achievement {
+        id: "untouchable",
+        name: "Untouchable",
+        description: "Win classic mode without losing shield",
+        condition: Condition::WinWithoutShieldLoss("classic"),
+        reward: Some("assets/pictures/asteroids/asteroid3.png"),
+    },
+    Achievement {
+        id: "chain_reaction",
+        name: "Chain Reaction",
+        description: "Chain 10 kills",
+        condition: Condition::Combo(10),
+        reward: None,
+    },
+    Achievement {
+        id: "event_horizon",
+        name: "Event Horizon",
+        description: "Form 10 black holes",
+        condition: Condition::FormBlackholes(10),
+        reward: Some("assets/pictures/blackholes/blackhole3.png"),
+    },
+];
+
+/// État de la partie utilisé pour évaluer les succès.
+pub struct GameContext<'a> {
+    /// Mode de jeu ("classic" ou "modern").
+    pub mode: &'a str,
+    /// Temps de jeu.
+    pub time: f64,
+    /// Statistiques de la partie en cours.
+    pub session: &'a SessionStats,
+    /// Statistiques cumulées des parties précédentes.
+    pub lifetime: &'a LifetimeStats,
+    /// Meilleur combo de la partie.
+    pub max_combo: u32,
+    /// Vrai si la partie vient d'être gagnée.
+    pub won: bool,
+}
+
+impl Condition {
+    /// Vérifie si la condition est remplie.
+    ///
+    /// # Arguments
+    /// - `context` L'état de la partie.
+    ///
+    pub fn is_met(&self, context: &GameContext) -> bool {
+        match *self {
+            Condition::Destroy(shape, count) => {
+                context.lifetime.totals.get_destroyed(shape) + context.session.get_destroyed(shape)
+                    >= count
+            }
+            Condition::Survive(mode, duration) => context.mode == mode && context.time >= duration,
+            Condition::WinWithoutShieldLoss(mode) => {
+                context.mode == mode && context.won && context.session.shield_hits == 0
+            }
+            Condition::Combo(combo) => context.max_combo >= combo,
+            Condition::FormBlackholes(count) => {
+                context.lifetime.totals.blackholes_formed + context.session.blackholes_formed
+                    >= count
+            }
+        }
+    }
+}
+
+/// Notification affichée lors du déblocage d'un succès.
+pub struct Toast {
+    /// Texte affiché.
+    pub text: String,
+    /// Temps (réel) d'apparition de la notification.
+    pub birth_time: f64,
+}
+
+/// Succès débloqués par le joueur.
+pub struct Achievements {
+    /// Identifiants des succès débloqués.
+    unlocked: Vec<String>,
+    /// Notifications en cours d'affichage.
+    toasts: Vec<Toast>,
+}
+
+impl Achievements {
+    /// Nom du fichier de sauvegarde des succès.
+    const FILE_NAME: &'static str = "achievements.txt";
+    /// Durée d'affichage d'une notification, en secondes.
+    const TOAST_DURATION: f64 = 3.0;
+
+    /// Charge les succès débloqués depuis le disque.
+    pub fn load() -> Self {
+        let mut unlocked: Vec<String> = read_key_values(Self::FILE_NAME)
+            .into_iter()
+            .filter(|(_, value)| value == "unlocked")
+            .map(|(key, _)| key)
+            .collect();
+        unlocked.sort();
+        Self {
+            unlocked,
+            toasts: Vec::new(),
+        }
+    }
+
+    /// Sauvegarde les succès débloqués sur le disque.
+    pub fn save(&self) {
+        let entries: Vec<(String, String)> = self
+            .unlocked
+            .iter()
+            .map(|id| (id.clone(), "unlocked".to_string()))
+            .collect();
+        write_key_values(Self::FILE_NAME, &entries);
+    }
+
+    /// Vérifie si un succès est débloqué.
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == id)
+    }
+
+    /// Évalue les succès non débloqués et débloque ceux dont la condition est remplie.
+    /// Une notification est créée pour chaque nouveau succès.
+    ///
+    /// # Arguments
+    /// - `context` L'état de la partie.
+    /// - `now` Le temps réel (pour l'affichage des notifications).
+    ///
+    /// # Returns
+    /// - `usize` Le nombre de succès débloqués (à sauvegarder par l'appelant, voir `save`).
+    ///
+    pub fn evaluate(&mut self, context: &GameContext, now: f64) -> usize {
+        let mut count = 0;
+        for achievement in ACHIEVEMENTS {
+            if !self.is_unlocked(achievement.id) && achievement.condition.is_met(context) {
+                self.unlocked.push(achievement.id.to_string());
+                let text = match achievement.reward {
+                    Some(_) => format!("Achievement unlocked : {} (new skin !)", achievement.name),
+                    None => format!("Achievement unlocked : {}", achievement.name),
+                };
+                self.toasts.push(Toast {
+                    text,
+                    birth_time: now,
+                });
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Liste des skins encore verrouillés.
+    pub fn locked_rewards(&self) -> Vec<&'static str> {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| !self.is_unlocked(achievement.id))
+            .filter_map(|achievement| achievement.reward)
+            .collect()
+    }
+
+    /// Vérifie si un skin est verrouillé par un succès non débloqué.
+    ///
+    /// # Arguments
+    /// - `path` Le chemin du skin.
+    ///
+    pub fn is_skin_locked(&self, path: &Path) -> bool {
+        self.locked_rewards()
+            .iter()
+            .any(|reward| Path::new(reward) == path)
+    }
+
+    /// Affichage des notifications de succès (en haut au centre de l'écran).
+    ///
+    /// # Arguments
+    /// - `now` Le temps réel.
+    ///
+    pub fn draw_toasts(&mut self, now: f64) {
+        self.toasts
+            .retain(|toast| now - toast.birth_time < Self::TOAST_DURATION);
+        for (i, toast) in self.toasts.iter().enumerate() {
+            let alpha = (1.0 - (now - toast.birth_time) / Self::TOAST_DURATION) as f32;
+            let dimension = measure_text(&toast.text, None, 30, 1.0);
+            let x = (screen_width() - dimension.width) / 2.0;
+            let y = 60.0 + i as f32 * 45.0;
+            draw_rectangle(
+                x - 15.0,
+                y - 30.0,
+                dimension.width + 30.0,
+                42.0,
+                Color::new(0.0, 0.0, 0.0, 0.7 * alpha),
+            );
+            draw_text(&toast.text, x, y, 30.0, Color::new(1.0, 0.84, 0.0, alpha));
+        }
+    }
+}
+
+/// Menu des succès.
+/// Affiche la liste des succès, leur état et leur récompense.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids.
+/// - `sound` Les sons du jeu.
+///
+pub async fn achievements_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) {
+    let achievements = Achievements::load();
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Achievements", None, 50, 1.0);
+        draw_text(
+            "Achievements",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            let unlocked = achievements.is_unlocked(achievement.id);
+            let reward = match achievement.reward {
+                Some(path) => format!(
+                    " - unlocks {}",
+                    Path::new(path)
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .unwrap_or(path)
+                ),
+                None => String::new(),
+            };
+            let text = format!(
+                "[{}] {} : {}{}",
+                if unlocked { "x" } else { " " },
+                achievement.name,
+                achievement.description,
+                reward
+            );
+            let dimension = measure_text(&text, None, 30, 1.0);
+            draw_text(
+                &text,
+                (screen_width() - dimension.width) / 2.0,
+                300.0 + i as f32 * 45.0,
+                30.0,
+                if unlocked { GREEN } else { GRAY },
+            );
+        }
+
+        let dimension = measure_text("Back", None, 40, 1.0);
+        draw_text(
+            "Back",
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 200.0,
+            40.0,
+            YELLOW,
+        );
+
+        if is_key_pressed(KeyCode::Backspace)
+            || is_key_pressed(KeyCode::Escape)
+            || handle_enter_press(&mut last_enter_press)
+        {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(
+        mode: &'a str,
+        session: &'a SessionStats,
+        lifetime: &'a LifetimeStats,
+        won: bool,
+    ) -> GameContext<'a> {
+        GameContext {
+            mode,
+            time: 10.0,
+            session,
+            lifetime,
+            max_combo: 0,
+            won,
+        }
+    }
+
+    #[test]
+    fn test_destroy_condition_adds_lifetime_and_session() {
+        let mut lifetime = LifetimeStats::default();
+        lifetime.totals.destroyed = [99, 0, 0];
+        let mut session = SessionStats::new();
+        let condition = Condition::Destroy(AsteroidShape::Small, 100);
+        assert!(!condition.is_met(&context("classic", &session, &lifetime, false)));
+        session.destroyed = [1, 0, 0];
+        assert!(condition.is_met(&context("classic", &session, &lifetime, false)));
+    }
+
+    #[test]
+    fn test_win_without_shield_loss_and_rewards() {
+        let lifetime = LifetimeStats::default();
+        let session = SessionStats::new();
+        let mut achievements = Achievements {
+            unlocked: Vec::new(),
+            toasts: Vec::new(),
+        };
+        let reward = Path::new("assets/pictures/asteroids/asteroid3.png");
+        assert!(achievements.is_skin_locked(reward));
+
+        achievements.evaluate(&context("modern", &session, &lifetime, true), 0.0);
+        assert!(!achievements.is_unlocked("untouchable"));
+
+        assert_eq!(
+            achievements.evaluate(&context("classic", &session, &lifetime, true), 0.0),
+            1
+        );
+        assert!(achievements.is_unlocked("untouchable"));
+        assert!(!achievements.is_skin_locked(reward));
+        assert_eq!(achievements.toasts.len(), 1);
+    }
+}
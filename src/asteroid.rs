@@ -7,6 +7,7 @@
 //! ## Contenu
 //! - Structure `Asteroid`
 //! - Enumération `AsteroidShape`
+//! - Matériau des astéroïdes (voir le module `material`)
 //! - Génération aléatoire d'astéroïdes
 //! - Gestion des collisions et des déplacements
 //!
@@ -26,9 +27,13 @@
 //! ```
 use crate::blackhole::BlackHole;
 use crate::impl_stellar_object;
+use crate::material::Material;
+use crate::rng::game_rng;
+use crate::score::wave_number;
 use crate::stellarobject::StellarObject;
+use crate::tuning::tuning;
 use ::rand::distributions::{Distribution, Standard};
-use ::rand::{thread_rng, Rng};
+use ::rand::Rng;
 use macroquad::prelude::*;
 use std::f32::consts::PI;
 
@@ -46,6 +51,10 @@ pub struct Asteroid {
     pub rotation: f32,
     /// Temps de création de l'astéroïde.
     pub birth_time: f64,
+    /// Matériau de l'astéroïde.
+    pub material: Material,
+    /// Nombre de missiles restant à encaisser avant la destruction.
+    pub hit_points: u32,
 }
 
 /// Définit les formes possibles pour les astéroïdes.
@@ -71,13 +80,6 @@ impl Distribution<AsteroidShape> for Standard {
 }
 
 impl Asteroid {
-    /// Taille d'un petit astéroïde.
-    pub const ASTEROID_SMALL_SIZE: f32 = 30.0;
-    /// Taille d'un astéroïde de taille moyenne.
-    pub const ASTEROID_MEDIUM_SIZE: f32 = 50.0;
-    /// Taille d'un grand astéroïde.
-    pub const ASTEROID_LARGE_SIZE: f32 = 70.0;
-
     /// Crée un nouvel astéroïde.
     ///
     /// # Arguments
@@ -97,9 +99,10 @@ impl Asteroid {
     /// );
     /// ```
     pub fn new(position: Vec2, speed: Vec2, shape: AsteroidShape, size: f32) -> Self {
-        let mut rng = thread_rng();
+        let mut rng = game_rng();
+        let spin = tuning().asteroid_spin;
 
-        let rotation = if rng.gen_bool(0.5) { 0.01 } else { -0.01 };
+        let rotation = if rng.gen_bool(0.5) { spin } else { -spin };
 
         Self {
             position,
@@ -108,6 +111,8 @@ impl Asteroid {
             size,
             birth_time: if cfg!(test) { 0.0 } else { get_time() }, // Pour éviter les problèmes de 'get_time()' dans les tests. (macroquad n'est pas initialisé dans cette section)
             rotation,
+            material: Material::Rock,
+            hit_points: 1,
         }
     }
 
@@ -120,17 +125,46 @@ impl Asteroid {
     /// println!("Astéroïde aléatoire : {:?}", random_asteroid);
     /// ```
     pub fn new_random() -> Self {
-        let mut rng = thread_rng();
-        let shape: AsteroidShape = rng.gen();
-        let rotation = if rng.gen_bool(0.5) { 0.01 } else { -0.01 };
+        Self::new_random_biased(0.0)
+    }
+
+    /// Crée un astéroïde aléatoire dont la répartition des tailles est décalée.
+    ///
+    /// # Arguments
+    /// - `size_bias` Le décalage (entre -1 et 1) : positif, les grands astéroïdes sont plus fréquents ;
+    ///   négatif, les petits le sont ; 0, les trois formes sont équiprobables.
+    ///
+    pub fn new_random_biased(size_bias: f32) -> Self {
+        let mut rng = game_rng();
+        let shape: AsteroidShape = if size_bias == 0.0 {
+            rng.gen()
+        } else {
+            let bias = size_bias.clamp(-1.0, 1.0);
+            let roll = rng.gen_range(0.0..3.0);
+            if roll < 1.0 - bias {
+                AsteroidShape::Small
+            } else if roll < 2.0 - bias {
+                AsteroidShape::Medium
+            } else {
+                AsteroidShape::Large
+            }
+        };
+        let tuning = tuning();
+        let rotation = if rng.gen_bool(0.5) {
+            tuning.asteroid_spin
+        } else {
+            -tuning.asteroid_spin
+        };
         let size = match shape {
             AsteroidShape::Large => {
-                rng.gen_range(Self::ASTEROID_MEDIUM_SIZE..=Self::ASTEROID_LARGE_SIZE)
+                rng.gen_range(tuning.asteroid_medium_size..=tuning.asteroid_large_size)
             }
             AsteroidShape::Medium => {
-                rng.gen_range(Self::ASTEROID_SMALL_SIZE..=Self::ASTEROID_MEDIUM_SIZE)
+                rng.gen_range(tuning.asteroid_small_size..=tuning.asteroid_medium_size)
+            }
+            AsteroidShape::Small => {
+                rng.gen_range(tuning.asteroid_min_size..=tuning.asteroid_small_size)
             }
-            AsteroidShape::Small => rng.gen_range(10.0..=Self::ASTEROID_SMALL_SIZE),
         };
 
         Self {
@@ -140,9 +174,49 @@ impl Asteroid {
             size,
             birth_time: get_time(),
             rotation,
+            material: Material::Rock,
+            hit_points: 1,
         }
     }
 
+    /// Crée un astéroïde aléatoire dont le matériau dépend de la vague en cours.
+    ///
+    /// # Arguments
+    /// - `wave` Le numéro de la vague en cours.
+    /// - `size_bias` Le décalage de la répartition des tailles (voir `new_random_biased`).
+    ///
+    pub fn new_random_for_wave(wave: u32, size_bias: f32) -> Self {
+        let mut asteroid = Self::new_random_biased(size_bias);
+        asteroid.set_material(Material::random_for_wave(wave));
+        asteroid
+    }
+
+    /// Change le matériau de l'astéroïde (ses points de vie sont remis au maximum).
+    ///
+    /// # Arguments
+    /// - `material` Le nouveau matériau.
+    ///
+    pub fn set_material(&mut self, material: Material) {
+        self.material = material;
+        self.hit_points = material.hit_points(self.shape);
+    }
+
+    /// Getter du matériau de l'asteroid.
+    pub fn get_material(&self) -> Material {
+        self.material
+    }
+
+    /// Inflige un impact de missile à l'astéroïde.
+    ///
+    /// # Returns
+    /// - `true` si l'astéroïde est détruit.
+    /// - `false` s'il résiste (astéroïde métallique).
+    ///
+    pub fn hit(&mut self) -> bool {
+        self.hit_points = self.hit_points.saturating_sub(1);
+        self.hit_points == 0
+    }
+
     /// Getter du 'type' de l'asteroid.
     pub fn get_shape(&self) -> AsteroidShape {
         self.shape
@@ -162,6 +236,18 @@ impl Asteroid {
         self.rotation = rotation;
     }
 
+    /// Oriente la vitesse de l'astéroïde vers une cible (sa norme est conservée).
+    ///
+    /// # Arguments
+    /// - `target` La position visée.
+    ///
+    pub fn aim_at(&mut self, target: Vec2) {
+        let direction = (target - self.position).normalize_or_zero();
+        if direction != Vec2::ZERO {
+            self.speed = direction * self.speed.length();
+        }
+    }
+
     /// Casse l'astéroid en deux autres plus petits.
     ///
     /// # Returns
@@ -176,8 +262,9 @@ impl Asteroid {
     /// ```
     ///
     pub fn split(&self) -> Vec<Asteroid> {
-        let mut rng = thread_rng();
-        match self.get_shape() {
+        let mut rng = game_rng();
+        let tuning = tuning();
+        let mut fragments = match self.get_shape() {
             AsteroidShape::Large => {
                 let mut asteroids = Vec::new();
                 for _ in 0..2 {
@@ -185,7 +272,7 @@ impl Asteroid {
                         self.position,
                         Self::new_random_alea_speed(),
                         AsteroidShape::Medium,
-                        rng.gen_range(Self::ASTEROID_SMALL_SIZE..=Self::ASTEROID_MEDIUM_SIZE),
+                        rng.gen_range(tuning.asteroid_small_size..=tuning.asteroid_medium_size),
                     ));
                 }
                 asteroids
@@ -197,13 +284,73 @@ impl Asteroid {
                         self.position,
                         Self::new_random_alea_speed(),
                         AsteroidShape::Small,
-                        rng.gen_range(20.0..=Self::ASTEROID_SMALL_SIZE),
+                        rng.gen_range(
+                            tuning.asteroid_fragment_min_size..=tuning.asteroid_small_size,
+                        ),
                     ));
                 }
                 asteroids
             }
             AsteroidShape::Small => Vec::new(),
+        };
+        // La glace se brise en de nombreux petits fragments
+        if self.material == Material::Ice && !fragments.is_empty() {
+            let count = if self.shape == AsteroidShape::Large {
+                6
+            } else {
+                4
+            };
+            fragments = (0..count)
+                .map(|_| {
+                    Asteroid::new(
+                        self.position,
+                        Self::new_random_alea_speed() * tuning.ice_fragment_speed,
+                        AsteroidShape::Small,
+                        rng.gen_range(tuning.asteroid_min_size..=tuning.asteroid_fragment_min_size),
+                    )
+                })
+                .collect();
+        }
+        for fragment in &mut fragments {
+            fragment.set_material(self.material.fragment());
+        }
+        fragments
+    }
+
+    /// Détruit un astéroïde touché par un missile et le remplace par ses fragments.
+    /// Un astéroïde explosif endommage les astéroïdes dans le rayon de l'explosion
+    /// (qui peuvent à leur tour exploser).
+    ///
+    /// # Arguments
+    /// - `asteroids` Liste des astéroids.
+    /// - `index` L'indice de l'astéroid détruit.
+    ///
+    /// # Returns
+    /// - `Vec<Asteroid>` Les astéroids détruits (celui touché, puis ceux détruits par les explosions).
+    ///
+    pub fn destroy(asteroids: &mut Vec<Asteroid>, index: usize) -> Vec<Asteroid> {
+        let mut destroyed = vec![asteroids.swap_remove(index)];
+        let mut k = 0;
+        while k < destroyed.len() {
+            if destroyed[k].material == Material::Explosive {
+                let center = destroyed[k].position;
+                let mut j = 0;
+                while j < asteroids.len() {
+                    let in_blast = Vec2::distance(center, asteroids[j].position)
+                        < Material::BLAST_RADIUS + asteroids[j].size;
+                    if in_blast && asteroids[j].hit() {
+                        destroyed.push(asteroids.swap_remove(j));
+                    } else {
+                        j += 1;
+                    }
+                }
+            }
+            k += 1;
+        }
+        for asteroid in &destroyed {
+            asteroids.extend(asteroid.split());
         }
+        destroyed
     }
 
     /// Génère une position aléatoire près de l'un des bords.
@@ -215,7 +362,7 @@ impl Asteroid {
     /// - `Vec2` Position aléatoire.
     ///
     fn new_random_alea_pos(size: f32) -> Vec2 {
-        let mut rng = thread_rng();
+        let mut rng = game_rng();
 
         let nearpos: f32 = rng.gen_range(size / 2.0..=size);
         let nearside = rng.gen_range(1..=4); // 1 = top, 2 = right, 3 = down, 4 = left
@@ -238,7 +385,7 @@ impl Asteroid {
     /// - `Vec2` Vitesse aléatoire.
     ///
     fn new_random_alea_speed() -> Vec2 {
-        let mut rng = thread_rng();
+        let mut rng = game_rng();
 
         let angle: f32 = rng.gen_range(0.0..=(2.0 * PI));
         Vec2::from_angle(angle)
@@ -278,10 +425,11 @@ impl Asteroid {
         if !rotation {
             return self.position;
         }
+        let spin = tuning().asteroid_spin;
         if self.rotation > 0.0 {
-            self.set_rotation(self.rotation + 0.01);
+            self.set_rotation(self.rotation + spin);
         } else {
-            self.set_rotation(self.rotation - 0.01);
+            self.set_rotation(self.rotation - spin);
         }
         self.position * factor as f32
     }
@@ -296,13 +444,27 @@ impl Asteroid {
             texture,
             self.get_position().x - (self.get_size() / 2.0),
             self.get_position().y - (self.get_size() / 2.0),
-            WHITE,
+            self.material.tint(),
             DrawTextureParams {
                 dest_size: Some(Vec2::new(self.get_size() * 2.0, self.get_size() * 2.0)),
                 rotation: self.get_rotation(),
                 ..Default::default()
             },
         );
+        self.draw_cracks();
+    }
+
+    /// Dessine les fissures d'un astéroïde endommagé (une fissure par missile encaissé).
+    fn draw_cracks(&self) {
+        let damage = self.material.hit_points(self.shape) - self.hit_points.max(1);
+        let center = self.get_position() + Vec2::splat(self.get_size() / 2.0);
+        for i in 0..damage {
+            let angle = self.get_rotation() + i as f32 * 2.4;
+            let middle = center + Vec2::from_angle(angle) * self.get_size() * 0.4;
+            let end = middle + Vec2::from_angle(angle + 0.6) * self.get_size() * 0.4;
+            draw_line(center.x, center.y, middle.x, middle.y, 2.0, BLACK);
+            draw_line(middle.x, middle.y, end.x, end.y, 2.0, BLACK);
+        }
     }
     /// Décide si un nouvel astéroïde doit être ajouté à la liste en fonction du temps écoulé
     /// depuis le dernier ajout.
@@ -314,6 +476,7 @@ impl Asteroid {
     ///   où le dernier astéroïde a été ajouté.
     /// - `time`: Le timestamp actuel (en secondes).
     /// - `range_secs`: Le délai minimum (en secondes) entre deux ajouts successifs d'astéroïdes.
+    /// - `size_bias`: Le décalage de la répartition des tailles (voir `new_random_biased`).
     ///
     /// # Returns
     ///
@@ -330,7 +493,7 @@ impl Asteroid {
     /// let current_time = 10.0;
     /// let range_secs = 5.0;
     ///
-    /// let result = Asteroid::push_or_dont(&mut asteroids, &mut last_add, current_time, range_secs);
+    /// let result = Asteroid::push_or_dont(&mut asteroids, &mut last_add, current_time, range_secs, 0.0);
     ///
     /// assert!(result); // Un astéroïde a été ajouté car suffisamment de temps s'est écoulé.
     /// assert_eq!(asteroids.len(), 1);
@@ -345,9 +508,10 @@ impl Asteroid {
         last_add: &mut f64,
         time: f64,
         range_secs: f64,
+        size_bias: f32,
     ) -> bool {
         if time - range_secs > *last_add {
-            asteroids.push(Self::new_random());
+            asteroids.push(Self::new_random_for_wave(wave_number(time), size_bias));
             *last_add = time;
             return true;
         }
@@ -360,8 +524,13 @@ impl Asteroid {
     /// # Arguments
     /// - `astéroid` Liste des astéroids.
     /// - `blackholes` Liste des trous noirs.
+    /// - `blackhole_chance` Probabilité (une chance sur `n`) qu'une collision forme un trou noir.
     ///
-    pub fn what_collide_asteroids(asteroids: &mut Vec<Asteroid>, blackholes: &mut Vec<BlackHole>) {
+    pub fn what_collide_asteroids(
+        asteroids: &mut Vec<Asteroid>,
+        blackholes: &mut Vec<BlackHole>,
+        blackhole_chance: u32,
+    ) {
         let mut i = 0;
         let current_time = if cfg!(test) { 10.0 } else { get_time() }; // Pour éviter les problèmes de 'get_time()' dans les tests. (macroquad n'est pas initialisé dans cette section)
         let mut to_remove = Vec::new();
@@ -376,7 +545,7 @@ impl Asteroid {
                     && if cfg!(test) {
                         true
                     } else {
-                        thread_rng().gen_range(0..15) == 0
+                        game_rng().gen_range(0..blackhole_chance) == 0
                     }
                 // Pour assurer la condition de l'événement dans le test.
                 {
@@ -443,6 +612,61 @@ mod tests {
         assert_eq!(asteroids_test.len(), 2);
     }
 
+    #[test]
+    fn test_materials_hits_and_fragments() {
+        let mut metallic = Asteroid::new(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 0.0),
+            AsteroidShape::Medium,
+            40.0,
+        );
+        metallic.set_material(Material::Metallic);
+        assert!(!metallic.hit());
+        assert!(!metallic.hit());
+        assert!(metallic.hit());
+        assert!(metallic
+            .split()
+            .iter()
+            .all(|fragment| fragment.get_material() == Material::Metallic));
+
+        let mut ice = Asteroid::new(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 0.0),
+            AsteroidShape::Large,
+            70.0,
+        );
+        ice.set_material(Material::Ice);
+        let fragments = ice.split();
+        assert_eq!(fragments.len(), 6);
+        assert!(fragments
+            .iter()
+            .all(|fragment| fragment.get_shape() == AsteroidShape::Small));
+    }
+
+    #[test]
+    fn test_explosive_chain_reaction() {
+        let asteroid = |x: f32, material: Material| {
+            let mut asteroid = Asteroid::new(
+                Vec2::new(x, 100.0),
+                Vec2::new(0.0, 0.0),
+                AsteroidShape::Small,
+                15.0,
+            );
+            asteroid.set_material(material);
+            asteroid
+        };
+        let mut asteroids = vec![
+            asteroid(0.0, Material::Explosive),
+            asteroid(100.0, Material::Explosive),
+            asteroid(200.0, Material::Rock),
+            asteroid(800.0, Material::Rock),
+        ];
+        let destroyed = Asteroid::destroy(&mut asteroids, 0);
+        assert_eq!(destroyed.len(), 3);
+        assert_eq!(asteroids.len(), 1);
+        assert_eq!(asteroids[0].get_position().x, 800.0);
+    }
+
     #[test]
     fn test_asteroid_is_collide_round() {
         let asteroid_test_1 = Asteroid::new(
@@ -497,7 +721,7 @@ mod tests {
 
         let mut blackholes_test = Vec::new();
 
-        Asteroid::what_collide_asteroids(&mut asteroids_test, &mut blackholes_test);
+        Asteroid::what_collide_asteroids(&mut asteroids_test, &mut blackholes_test, 15);
         assert_eq!(blackholes_test.len(), 1);
     }
 }
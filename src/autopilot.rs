@@ -0,0 +1,709 @@
+//! # Autopilot Module
+//!
+//! Ce module gère le pilote automatique : à chaque frame, il produit les mêmes commandes
+//! qu'un joueur (`ShipInput` : poussée, rotation, tir et visée) à partir de l'état du jeu,
+//! quel que soit le schéma de contrôle.
+//!
+//! - Évaluation des menaces : trajectoire de chaque astéroïde (point de plus proche approche)
+//!   et proximité des trous noirs, d'où une direction d'esquive.
+//! - Choix de la cible : les astéroïdes menaçants et proches d'abord, avec une visée anticipée
+//!   (point d'interception des missiles).
+//! - Niveaux de compétence : temps de réaction, précision et anticipation de la visée, horizon des menaces.
+//!
+//! Le pilote remplace le clavier dans le mode "Watch AI" (partie affichée) et dans les
+//! simulations sans affichage (`simulate`), utiles pour l'équilibrage.
+use super::draw_background;
+use crate::asteroid::Asteroid;
+use crate::blackhole::BlackHole;
+use crate::classic::update_classic;
+use crate::control::ControlScheme;
+use crate::level::Spawner;
+use crate::modern::update_modern;
+use crate::rng;
+use crate::rules::ModeRules;
+use crate::score::{finalize_score, get_score};
+use crate::settings::Settings;
+use crate::sound::Sound;
+use crate::spaceship::{ShipInput, Spaceship};
+use crate::stellarobject::StellarObject;
+use crate::utils::{background_asteroids, handle_enter_press};
+use ::rand::rngs::StdRng;
+use ::rand::{Rng, SeedableRng};
+use macroquad::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use std::time::Instant;
+
+/// Niveau de compétence du pilote automatique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiSkill {
+    /// Réagit lentement, vise sans anticiper.
+    Rookie,
+    /// Réagit vite et anticipe en partie la trajectoire des cibles.
+    Pilot,
+    /// Réagit presque immédiatement et vise au point d'interception.
+    Ace,
+}
+
+impl AiSkill {
+    /// Nom affiché du niveau.
+    pub fn name(self) -> &'static str {
+        match self {
+            AiSkill::Rookie => "Rookie",
+            AiSkill::Pilot => "Pilot",
+            AiSkill::Ace => "Ace",
+        }
+    }
+
+    /// Niveau suivant (revient au premier après le dernier).
+    pub fn next(self) -> Self {
+        match self {
+            AiSkill::Rookie => AiSkill::Pilot,
+            AiSkill::Pilot => AiSkill::Ace,
+            AiSkill::Ace => AiSkill::Rookie,
+        }
+    }
+
+    /// Nombre de frames entre deux évaluations de la situation (temps de réaction).
+    fn reaction_frames(self) -> u64 {
+        match self {
+            AiSkill::Rookie => 12,
+            AiSkill::Pilot => 6,
+            AiSkill::Ace => 2,
+        }
+    }
+
+    /// Erreur de visée maximale, en radians.
+    fn aim_error(self) -> f32 {
+        match self {
+            AiSkill::Rookie => 0.15,
+            AiSkill::Pilot => 0.06,
+            AiSkill::Ace => 0.01,
+        }
+    }
+
+    /// Part du déplacement de la cible anticipée par la visée (0 : aucune, 1 : interception exacte).
+    fn lead(self) -> f32 {
+        match self {
+            AiSkill::Rookie => 0.0,
+            AiSkill::Pilot => 0.7,
+            AiSkill::Ace => 1.0,
+        }
+    }
+
+    /// Horizon (en frames) au-delà duquel un astéroïde n'est pas considéré comme une menace.
+    fn lookahead(self) -> f32 {
+        match self {
+            AiSkill::Rookie => 30.0,
+            AiSkill::Pilot => 60.0,
+            AiSkill::Ace => 90.0,
+        }
+    }
+
+    /// Écart maximal (en radians) entre l'orientation du vaisseau et la visée pour tirer.
+    fn fire_cone(self) -> f32 {
+        match self {
+            AiSkill::Rookie => 0.25,
+            AiSkill::Pilot => 0.12,
+            AiSkill::Ace => 0.06,
+        }
+    }
+}
+
+/// État du jeu observé par le pilote automatique.
+pub struct Situation<'a> {
+    /// Les astéroïdes.
+    pub asteroids: &'a [Asteroid],
+    /// Les trous noirs.
+    pub blackholes: &'a [BlackHole],
+    /// Facteur de vitesse des astéroïdes (voir `ModeRules::asteroid_speed`).
+    pub asteroid_speed: f32,
+    /// Vitesse des missiles (avant les améliorations du vaisseau).
+    pub missile_speed: f32,
+}
+
+/// Décision du pilote, conservée jusqu'à la prochaine évaluation de la situation.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Plan {
+    /// Point visé, s'il y a une cible.
+    aim: Option<Vec2>,
+    /// Direction d'esquive (sa norme mesure l'urgence ; nulle sans menace).
+    evade: Vec2,
+}
+
+/// Pilote automatique d'un vaisseau.
+pub struct Autopilot {
+    /// Niveau de compétence.
+    skill: AiSkill,
+    /// Nombre de frames jouées.
+    frame: u64,
+    /// Dernière décision.
+    plan: Plan,
+    /// Générateur de l'erreur de visée (indépendant de celui du jeu).
+    rng: StdRng,
+}
+
+impl Autopilot {
+    /// Marge (en pixels) ajoutée aux rayons pour juger qu'un astéroïde menace le vaisseau.
+    const DANGER_MARGIN: f32 = 30.0;
+    /// Distance (en pixels, entre les bords) à partir de laquelle un trou noir est évité.
+    const BLACKHOLE_MARGIN: f32 = 90.0;
+    /// Urgence minimale pour esquiver plutôt que viser.
+    const EVADE_THRESHOLD: f32 = 0.2;
+    /// Distance maximale (en pixels) du point visé pour tirer.
+    const MAX_RANGE: f32 = 800.0;
+    /// Distance (en pixels) à laquelle une cible est deux fois moins prioritaire.
+    const PRIORITY_DISTANCE: f32 = 300.0;
+
+    /// Créer un pilote automatique.
+    ///
+    /// # Arguments
+    /// - `skill` Le niveau de compétence.
+    ///
+    pub fn new(skill: AiSkill) -> Self {
+        Self {
+            skill,
+            frame: 0,
+            plan: Plan::default(),
+            rng: StdRng::seed_from_u64(skill as u64),
+        }
+    }
+
+    /// Getter du niveau de compétence.
+    pub fn get_skill(&self) -> AiSkill {
+        self.skill
+    }
+
+    /// Commandes du vaisseau pour la frame : la situation est réévaluée selon le temps de réaction,
+    /// puis la décision est traduite en commandes du schéma de contrôle.
+    ///
+    /// # Arguments
+    /// - `spaceship` Le vaisseau piloté.
+    /// - `situation` L'état du jeu.
+    /// - `scheme` Le schéma de contrôle du vaisseau.
+    ///
+    pub fn decide(
+        &mut self,
+        spaceship: &Spaceship,
+        situation: &Situation,
+        scheme: ControlScheme,
+    ) -> ShipInput {
+        if self.frame.is_multiple_of(self.skill.reaction_frames()) {
+            self.plan = self.assess(spaceship, situation);
+        }
+        self.frame += 1;
+        self.steer(spaceship, scheme)
+    }
+
+    /// Évalue les menaces et choisit la cible.
+    ///
+    /// # Arguments
+    /// - `spaceship` Le vaisseau piloté.
+    /// - `situation` L'état du jeu.
+    ///
+    fn assess(&mut self, spaceship: &Spaceship, situation: &Situation) -> Plan {
+        let ship = spaceship.get_position();
+        let ship_speed = spaceship.get_speed();
+        let lookahead = self.skill.lookahead();
+        let mut evade = Vec2::ZERO;
+        let mut best: Option<(f32, &Asteroid)> = None;
+
+        for asteroid in situation.asteroids {
+            // Point de plus proche approche, dans le repère du vaisseau
+            let offset = asteroid.get_position() - ship;
+            let velocity = asteroid.get_speed() * situation.asteroid_speed - ship_speed;
+            let time = if velocity.length_squared() > f32::EPSILON {
+                (-offset.dot(velocity) / velocity.length_squared()).clamp(0.0, lookahead)
+            } else {
+                0.0
+            };
+            let closest = offset + velocity * time;
+            let danger = asteroid.get_size() + spaceship.get_size() + Self::DANGER_MARGIN;
+            let threat = if closest.length() < danger {
+                1.0 - time / lookahead
+            } else {
+                0.0
+            };
+            if threat > 0.0 {
+                let away = if closest.length() > f32::EPSILON {
+                    -closest.normalize()
+                } else {
+                    velocity.perp().normalize_or_zero()
+                };
+                evade += away * threat;
+            }
+
+            let priority = (1.0 + 3.0 * threat) / (1.0 + offset.length() / Self::PRIORITY_DISTANCE);
+            if best.is_none_or(|(best_priority, _)| priority > best_priority) {
+                best = Some((priority, asteroid));
+            }
+        }
+
+        for blackhole in situation.blackholes {
+            let offset = ship - blackhole.get_position();
+            let gap = offset.length() - blackhole.get_size() - spaceship.get_size();
+            if gap < Self::BLACKHOLE_MARGIN {
+                let urgency = 2.0 * (1.0 - gap.max(0.0) / Self::BLACKHOLE_MARGIN);
+                evade += offset.normalize_or_zero() * urgency;
+            }
+        }
+
+        let missile_speed =
+            situation.missile_speed * spaceship.get_upgrades().missile_speed_factor();
+        let aim = best.map(|(_, target)| {
+            let velocity = target.get_speed() * situation.asteroid_speed;
+            let lead =
+                intercept_time(ship, target.get_position(), velocity, missile_speed).unwrap_or(0.0);
+            let point = target.get_position() + velocity * lead * self.skill.lead();
+            let error = self
+                .rng
+                .gen_range(-self.skill.aim_error()..=self.skill.aim_error());
+            ship + Vec2::from_angle(error).rotate(point - ship)
+        });
+        Plan { aim, evade }
+    }
+
+    /// Traduit la décision en commandes selon le schéma de contrôle.
+    ///
+    /// # Arguments
+    /// - `spaceship` Le vaisseau piloté.
+    /// - `scheme` Le schéma de contrôle du vaisseau.
+    ///
+    fn steer(&self, spaceship: &Spaceship, scheme: ControlScheme) -> ShipInput {
+        let ship = spaceship.get_position();
+        let rotation = spaceship.get_rotation();
+        let turn_rate = spaceship.get_definition().turn_rate;
+        let evading = self.plan.evade.length() > Self::EVADE_THRESHOLD;
+        let evade = self.plan.evade.normalize_or_zero();
+        let in_range = |aim: Vec2| aim.distance(ship) <= Self::MAX_RANGE;
+        // Écart entre l'orientation du vaisseau et la visée
+        let aim_gap = self
+            .plan
+            .aim
+            .map(|aim| angle_gap(rotation, heading(aim - ship)));
+        let aligned = self.plan.aim.is_some_and(in_range)
+            && aim_gap.is_some_and(|gap| gap.abs() <= self.skill.fire_cone());
+        let mut input = ShipInput::default();
+
+        match scheme {
+            ControlScheme::TwinStick => {
+                input.aim = self
+                    .plan
+                    .aim
+                    .unwrap_or(ship + Vec2::new(rotation.sin(), -rotation.cos()));
+                input.fire = self.plan.aim.is_some_and(in_range);
+                if evading {
+                    input.right = evade.x > 0.3;
+                    input.left = evade.x < -0.3;
+                    input.down = evade.y > 0.3;
+                    input.up = evade.y < -0.3;
+                }
+            }
+            ControlScheme::Tank => {
+                let gap = if evading {
+                    angle_gap(rotation, heading(evade))
+                } else {
+                    aim_gap.unwrap_or(0.0)
+                };
+                input.right = gap > turn_rate / 2.0;
+                input.left = gap < -turn_rate / 2.0;
+                input.up = evading && gap.abs() < 0.5;
+                input.fire = aligned;
+            }
+            ControlScheme::KeyboardAim => {
+                let gap = aim_gap.unwrap_or(0.0);
+                input.right = gap > turn_rate / 2.0;
+                input.left = gap < -turn_rate / 2.0;
+                if evading {
+                    let forward = Vec2::new(rotation.sin(), -rotation.cos());
+                    let side = Vec2::new(rotation.cos(), rotation.sin());
+                    input.up = evade.dot(forward) > 0.3;
+                    input.down = evade.dot(forward) < -0.3;
+                    input.strafe_right = evade.dot(side) > 0.3;
+                    input.strafe_left = evade.dot(side) < -0.3;
+                }
+                input.fire = aligned;
+            }
+        }
+        input
+    }
+}
+
+/// Orientation du vaisseau (en radians) pour faire face à une direction.
+fn heading(direction: Vec2) -> f32 {
+    direction.y.atan2(direction.x) + FRAC_PI_2
+}
+
+/// Écart (entre -π et π) à parcourir pour passer d'une orientation à une autre.
+fn angle_gap(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// Temps (en frames) au bout duquel un missile tiré maintenant atteint une cible en mouvement.
+///
+/// # Arguments
+/// - `shooter` La position de départ du missile.
+/// - `target` La position de la cible.
+/// - `velocity` La vitesse de la cible (pixels par frame).
+/// - `missile_speed` La vitesse du missile (pixels par frame).
+///
+/// # Returns
+/// - `Some(f32)` Le temps d'interception.
+/// - `None` si le missile ne peut pas rattraper la cible.
+///
+pub fn intercept_time(
+    shooter: Vec2,
+    target: Vec2,
+    velocity: Vec2,
+    missile_speed: f32,
+) -> Option<f32> {
+    let offset = target - shooter;
+    let a = velocity.dot(velocity) - missile_speed * missile_speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.dot(offset);
+    if a.abs() < f32::EPSILON {
+        return (b < 0.0).then(|| -c / b);
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+        .into_iter()
+        .filter(|&time| time >= 0.0)
+        .min_by(f32::total_cmp)
+}
+
+/// Résultat d'une partie simulée sans affichage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationReport {
+    /// Vrai si la partie est gagnée.
+    pub won: bool,
+    /// Le score final.
+    pub score: u32,
+    /// La durée de la partie, en secondes.
+    pub time: f64,
+    /// Le nombre d'astéroïdes détruits.
+    pub destroyed: u32,
+    /// La précision des tirs (entre 0 et 1).
+    pub accuracy: f32,
+}
+
+/// Simule une partie pilotée sans affichage, au pas d'une frame (1/60 s), aussi vite que possible.
+/// Le boss, la boutique et le directeur adaptatif ne sont pas simulés.
+///
+/// # Arguments
+/// - `skill` Le niveau du pilote.
+/// - `rules` Les règles de la partie.
+/// - `seed` La graine du générateur du jeu (même graine, même partie).
+/// - `max_time` La durée maximale de la partie, en secondes.
+///
+pub async fn simulate(
+    skill: AiSkill,
+    rules: &ModeRules,
+    seed: u64,
+    max_time: f64,
+) -> SimulationReport {
+    const FRAME: f64 = 1.0 / 60.0;
+    rng::set_seed(Some(seed));
+    let settings = Settings::load();
+    let scheme = settings.control_scheme_for(rules.flavor);
+    let mut spaceship = settings.new_spaceship(&settings.ship_definition());
+    let mut pilot = Autopilot::new(skill);
+    let mut sound = Sound::new();
+    sound.toggle_mute();
+    let mut asteroids: Vec<Asteroid> = Vec::new();
+    let mut blackholes: Vec<BlackHole> = Vec::new();
+    let mut missiles = Vec::new();
+    let mut ores = Vec::new();
+    let mut spawner = Spawner::Random { last_add: 0.0 };
+    let mut last_shoot = 0.0;
+    let mut time = 0.0;
+    let mut won = false;
+
+    while time < max_time {
+        time += FRAME;
+        let input = pilot.decide(
+            &spaceship,
+            &Situation {
+                asteroids: &asteroids,
+                blackholes: &blackholes,
+                asteroid_speed: rules.asteroid_speed(time) as f32,
+                missile_speed: rules.missile_speed,
+            },
+            scheme,
+        );
+        let mut lost = if rules.flavor == "classic" {
+            update_classic(
+                &mut asteroids,
+                &mut blackholes,
+                &mut spawner,
+                &mut spaceship,
+                time,
+                &mut last_shoot,
+                &mut missiles,
+                &mut ores,
+                &sound,
+                &input,
+                scheme,
+                rules,
+            )
+            .await
+        } else {
+            update_modern(
+                &mut asteroids,
+                &mut blackholes,
+                &mut spawner,
+                &mut spaceship,
+                time,
+                &mut last_shoot,
+                &mut missiles,
+                &mut ores,
+                &sound,
+                &input,
+                scheme,
+                rules,
+            )
+            .await
+        };
+        if lost && !rules.can_die {
+            spaceship.respawn(vec2(screen_width(), screen_height()) / 2.0, time);
+            lost = false;
+        }
+        won = !lost && rules.is_won(time, asteroids.is_empty(), get_score(&spaceship));
+        if lost || won || rules.is_time_up(time) {
+            break;
+        }
+    }
+    rng::set_seed(None);
+    finalize_score(&mut spaceship);
+    SimulationReport {
+        won,
+        score: get_score(&spaceship),
+        time,
+        destroyed: spaceship.stats.total_destroyed(),
+        accuracy: spaceship.stats.accuracy(),
+    }
+}
+
+/// Menu du pilote automatique : choix du niveau et du mode, partie regardée ("Watch AI")
+/// ou série de parties simulées sans affichage (résultats moyens affichés).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some((AiSkill, &str))` Le niveau et le mode de la partie à regarder.
+/// - `None` si le joueur revient en arrière.
+///
+pub async fn autopilot_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<(AiSkill, &'static str)> {
+    const SIMULATED_GAMES: u64 = 10;
+    const SIMULATED_TIME: f64 = 180.0;
+    let modes = ["classic", "modern", "survival", "time_attack"];
+    let options = ["Skill", "Mode", "Watch AI", "Run headless", "Back"];
+    let mut skill = AiSkill::Pilot;
+    let mut mode_index = 1;
+    let mut selected_index = 0;
+    let mut results: Vec<String> = Vec::new();
+    sound.play_sound_effect("select_menu");
+
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Autopilot", None, 50, 1.0);
+        draw_text(
+            "Autopilot",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        for (i, &option) in options.iter().enumerate() {
+            let label = match option {
+                "Skill" => format!("{} : {}", option, skill.name()),
+                "Mode" => format!("{} : {}", option, modes[mode_index]),
+                "Run headless" => format!("{} ({} games)", option, SIMULATED_GAMES),
+                _ => option.to_string(),
+            };
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            draw_text(
+                &label,
+                screen_width() / 2.0 - 150.0,
+                300.0 + i as f32 * 50.0,
+                40.0,
+                color,
+            );
+        }
+        for (i, line) in results.iter().enumerate() {
+            draw_text(
+                line,
+                screen_width() / 2.0 - 150.0,
+                600.0 + i as f32 * 30.0,
+                28.0,
+                GRAY,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % options.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                options.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            match options[selected_index] {
+                "Skill" => skill = skill.next(),
+                "Mode" => mode_index = (mode_index + 1) % modes.len(),
+                "Watch AI" => return Some((skill, modes[mode_index])),
+                "Run headless" => {
+                    let rules = ModeRules::for_mode(modes[mode_index])
+                        .unwrap_or(ModeRules::ALL[1])
+                        .with_difficulty(Settings::load().difficulty);
+                    let mut reports = Vec::new();
+                    for seed in 1..=SIMULATED_GAMES {
+                        draw_background(background_texture);
+                        draw_text(
+                            &format!("Simulating game {}/{}...", seed, SIMULATED_GAMES),
+                            screen_width() / 2.0 - 150.0,
+                            screen_height() / 2.0,
+                            40.0,
+                            WHITE,
+                        );
+                        next_frame().await;
+                        reports.push(simulate(skill, &rules, seed, SIMULATED_TIME).await);
+                    }
+                    results = summarize(skill, &rules, &reports);
+                }
+                _ => return None,
+            }
+        }
+
+        next_frame().await;
+    }
+}
+
+/// Résumé d'une série de parties simulées.
+///
+/// # Arguments
+/// - `skill` Le niveau du pilote.
+/// - `rules` Les règles des parties.
+/// - `reports` Les résultats des parties.
+///
+fn summarize(skill: AiSkill, rules: &ModeRules, reports: &[SimulationReport]) -> Vec<String> {
+    let count = reports.len().max(1) as f64;
+    let wins = reports.iter().filter(|report| report.won).count();
+    let mean = |value: fn(&SimulationReport) -> f64| reports.iter().map(value).sum::<f64>() / count;
+    vec![
+        format!(
+            "{} on {} ({}) : {} / {} games won",
+            skill.name(),
+            rules.title,
+            rules.difficulty.name(),
+            wins,
+            reports.len()
+        ),
+        format!(
+            "Average score : {:.0}   Average time : {:.1}s",
+            mean(|report| report.score as f64),
+            mean(|report| report.time)
+        ),
+        format!(
+            "Average destroyed : {:.1}   Average accuracy : {:.0}%",
+            mean(|report| report.destroyed as f64),
+            mean(|report| report.accuracy as f64) * 100.0
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::AsteroidShape;
+    use crate::shipclass::ShipDefinition;
+
+    #[test]
+    fn test_intercept_time() {
+        assert_eq!(
+            intercept_time(Vec2::ZERO, vec2(100.0, 0.0), Vec2::ZERO, 5.0),
+            Some(20.0)
+        );
+        assert_eq!(
+            intercept_time(Vec2::ZERO, vec2(100.0, 0.0), vec2(0.0, 3.0), 5.0),
+            Some(25.0)
+        );
+        assert_eq!(
+            intercept_time(Vec2::ZERO, vec2(100.0, 0.0), vec2(10.0, 0.0), 5.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_autopilot_leads_targets_and_evades_threats() {
+        let spaceship = Spaceship::new(&ShipDefinition::default());
+        let ship = spaceship.get_position();
+        let target = [Asteroid::new(
+            ship + vec2(300.0, 0.0),
+            vec2(0.0, 1.0),
+            AsteroidShape::Large,
+            40.0,
+        )];
+        let situation = Situation {
+            asteroids: &target,
+            blackholes: &[],
+            asteroid_speed: 1.0,
+            missile_speed: 5.0,
+        };
+        let mut ace = Autopilot::new(AiSkill::Ace);
+        let input = ace.decide(&spaceship, &situation, ControlScheme::TwinStick);
+        assert!(input.fire);
+        assert!(input.aim.y > ship.y + 40.0);
+        let mut rookie = Autopilot::new(AiSkill::Rookie);
+        let input = rookie.decide(&spaceship, &situation, ControlScheme::TwinStick);
+        assert!((input.aim.y - ship.y).abs() < 50.0);
+
+        let incoming = [Asteroid::new(
+            ship - vec2(100.0, 0.0),
+            vec2(3.0, 0.0),
+            AsteroidShape::Medium,
+            30.0,
+        )];
+        let blackholes = [BlackHole {
+            position: ship + vec2(0.0, 100.0),
+            size: 30.0,
+            rotation: 0.0,
+        }];
+        let situation = Situation {
+            asteroids: &incoming,
+            blackholes: &blackholes,
+            asteroid_speed: 1.0,
+            missile_speed: 5.0,
+        };
+        let input =
+            Autopilot::new(AiSkill::Ace).decide(&spaceship, &situation, ControlScheme::TwinStick);
+        assert!(input.up && !input.down);
+        let input =
+            Autopilot::new(AiSkill::Ace).decide(&spaceship, &situation, ControlScheme::Tank);
+        assert!(input.up && !input.left && !input.right);
+    }
+}
@@ -1,70 +1,245 @@
 use crate::asteroid::Asteroid;
 use crate::sound::Sound;
-use crate::utils::{background_asteroids, handle_enter_press, pressed_key};
+use crate::utils::{background_asteroids, handle_enter_press, pressed_input};
 use macroquad::prelude::*;
 use std::time::Instant;
 
-/// Structure des Binds de jeu.
+/// Actions du joueur, associées à des touches ou des boutons de la souris.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Thrust,
+    Reverse,
+    RotateLeft,
+    RotateRight,
+    StrafeLeft,
+    StrafeRight,
+    Fire,
+    Pause,
+    Quit,
+}
+
+impl Action {
+    /// Toutes les actions, dans l'ordre d'affichage.
+    pub const ALL: [Action; 9] = [
+        Action::Thrust,
+        Action::Reverse,
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::StrafeLeft,
+        Action::StrafeRight,
+        Action::Fire,
+        Action::Pause,
+        Action::Quit,
+    ];
+
+    /// Nom de l'action (pour l'affichage).
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Thrust => "Thrust",
+            Action::Reverse => "Reverse",
+            Action::RotateLeft => "Rotate left",
+            Action::RotateRight => "Rotate right",
+            Action::StrafeLeft => "Strafe left",
+            Action::StrafeRight => "Strafe right",
+            Action::Fire => "Fire",
+            Action::Pause => "Pause",
+            Action::Quit => "Quit",
+        }
+    }
+
+    /// Vrai pour les actions qui pilotent le vaisseau (propres à chaque joueur).
+    /// 'Pause' et 'Quit' concernent toute la partie.
+    pub fn is_ship_control(self) -> bool {
+        !matches!(self, Action::Pause | Action::Quit)
+    }
+}
+
+/// Entrée physique : une touche du clavier ou un bouton de la souris.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Input {
+    /// Vrai si l'entrée est maintenue.
+    pub fn is_down(self) -> bool {
+        match self {
+            Input::Key(key) => is_key_down(key),
+            Input::Mouse(button) => is_mouse_button_down(button),
+        }
+    }
+
+    /// Vrai si l'entrée vient d'être pressée (cette frame).
+    pub fn is_pressed(self) -> bool {
+        match self {
+            Input::Key(key) => is_key_pressed(key),
+            Input::Mouse(button) => is_mouse_button_pressed(button),
+        }
+    }
+
+    /// Nom de l'entrée (pour l'affichage).
+    pub fn label(self) -> String {
+        match self {
+            Input::Key(key) => format!("{:?}", key),
+            Input::Mouse(button) => format!("Mouse {:?}", button),
+        }
+    }
+}
+
+/// Conflit : une même entrée est associée à deux actions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conflict {
+    /// L'entrée en conflit.
+    pub input: Input,
+    /// L'action du Bind vérifié.
+    pub action: Action,
+    /// L'autre action utilisant la même entrée.
+    pub other: Action,
+    /// Vrai si l'autre action appartient au Bind de l'autre joueur.
+    pub other_player: bool,
+}
+
+/// Structure des Binds de jeu : les entrées associées à chaque action.
 pub struct Bind {
-    up: KeyCode,
-    down: KeyCode,
-    left: KeyCode,
-    right: KeyCode,
+    inputs: [Vec<Input>; Action::ALL.len()],
 }
 
 /// Implémentation du Bind.
 impl Bind {
     /// Créer un Bind initial.
-    /// Par défaut, les touches sont les flèches directionnelles.
+    /// Par défaut, les touches sont les flèches directionnelles, les pas de côté sur 'Z'/'X', le tir est sur 'Espace'
+    /// (les boutons de la souris tirent aussi avec le schéma 'Twin-stick'), la pause sur 'Échap'
+    /// et quitter sur 'Retour arrière'.
     ///
     /// # Returns
     /// - `Bind` Nouveau Bind.
     ///
     pub fn new() -> Bind {
         Bind {
-            up: KeyCode::Up,
-            down: KeyCode::Down,
-            left: KeyCode::Left,
-            right: KeyCode::Right,
+            inputs: [
+                vec![Input::Key(KeyCode::Up)],
+                vec![Input::Key(KeyCode::Down)],
+                vec![Input::Key(KeyCode::Left)],
+                vec![Input::Key(KeyCode::Right)],
+                vec![Input::Key(KeyCode::Z)],
+                vec![Input::Key(KeyCode::X)],
+                vec![Input::Key(KeyCode::Space)],
+                vec![Input::Key(KeyCode::Escape)],
+                vec![Input::Key(KeyCode::Backspace)],
+            ],
         }
     }
 
-    /// Setters des touches de déplacement.
-    pub fn set_up(&mut self, up: KeyCode) {
-        self.up = up;
+    /// Créer le Bind initial du second joueur (mode coopératif).
+    /// Par défaut, les touches sont 'ZQSD'/'WASD', les pas de côté sur 'Q'/'E' et le tir est sur 'Shift' gauche.
+    /// La pause et quitter restent gérés par le Bind du premier joueur.
+    ///
+    /// # Returns
+    /// - `Bind` Nouveau Bind.
+    ///
+    pub fn new_player_two() -> Bind {
+        Bind {
+            inputs: [
+                vec![Input::Key(KeyCode::W)],
+                vec![Input::Key(KeyCode::S)],
+                vec![Input::Key(KeyCode::A)],
+                vec![Input::Key(KeyCode::D)],
+                vec![Input::Key(KeyCode::Q)],
+                vec![Input::Key(KeyCode::E)],
+                vec![Input::Key(KeyCode::LeftShift)],
+                vec![],
+                vec![],
+            ],
+        }
     }
 
-    pub fn set_down(&mut self, down: KeyCode) {
-        self.down = down;
+    /// Réinitialise les touches selon le Bind initial donné.
+    ///
+    /// # Arguments
+    /// - `default` Le Bind par défaut.
+    ///
+    pub fn reset(&mut self, default: Bind) {
+        *self = default;
     }
 
-    pub fn set_left(&mut self, left: KeyCode) {
-        self.left = left;
+    /// Entrées associées à une action.
+    pub fn get_inputs(&self, action: Action) -> &[Input] {
+        &self.inputs[action as usize]
     }
 
-    pub fn set_right(&mut self, right: KeyCode) {
-        self.right = right;
+    /// Remplace les entrées d'une action par une seule entrée.
+    pub fn set_input(&mut self, action: Action, input: Input) {
+        self.inputs[action as usize] = vec![input];
     }
 
-    /// Getters des touches de déplacement.
-    pub fn get_up(&self) -> KeyCode {
-        self.up
+    /// Ajoute une entrée à une action (si elle n'y est pas déjà).
+    pub fn add_input(&mut self, action: Action, input: Input) {
+        if !self.get_inputs(action).contains(&input) {
+            self.inputs[action as usize].push(input);
+        }
     }
 
-    pub fn get_down(&self) -> KeyCode {
-        self.down
+    /// Retire toutes les entrées d'une action.
+    pub fn clear_inputs(&mut self, action: Action) {
+        self.inputs[action as usize].clear();
     }
-    pub fn get_left(&self) -> KeyCode {
-        self.left
+
+    /// Vrai si une des entrées de l'action est maintenue.
+    pub fn is_down(&self, action: Action) -> bool {
+        self.get_inputs(action).iter().any(|input| input.is_down())
+    }
+
+    /// Vrai si une des entrées de l'action vient d'être pressée.
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.get_inputs(action)
+            .iter()
+            .any(|input| input.is_pressed())
     }
 
-    pub fn get_right(&self) -> KeyCode {
-        self.right
+    /// Recherche les entrées associées à plusieurs actions.
+    /// Les actions de pilotage sont aussi comparées à celles de l'autre joueur.
+    ///
+    /// # Arguments
+    /// - `other` Le Bind de l'autre joueur.
+    ///
+    /// # Returns
+    /// - `Vec<Conflict>` Les conflits trouvés.
+    ///
+    pub fn find_conflicts(&self, other: &Bind) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (i, &action) in Action::ALL.iter().enumerate() {
+            for &input in self.get_inputs(action) {
+                for &other_action in &Action::ALL[i + 1..] {
+                    if self.get_inputs(other_action).contains(&input) {
+                        conflicts.push(Conflict {
+                            input,
+                            action,
+                            other: other_action,
+                            other_player: false,
+                        });
+                    }
+                }
+                for &other_action in Action::ALL.iter().filter(|a| a.is_ship_control()) {
+                    if action.is_ship_control() && other.get_inputs(other_action).contains(&input) {
+                        conflicts.push(Conflict {
+                            input,
+                            action,
+                            other: other_action,
+                            other_player: true,
+                        });
+                    }
+                }
+            }
+        }
+        conflicts
     }
 }
 
-/// Menu de Binding des touches de déplacement.
-/// Permet de changer les touches de déplacement du vaisseau spatial avec une interaction utilisateur.
+/// Menu de Binding des actions du joueur (déplacement, tir, pause, quitter).
+/// Permet de changer les entrées (touches ou boutons de la souris) de chaque action :
+/// 'Entrée' remplace les entrées de l'action, 'Tab' en ajoute une, 'Suppr' les retire.
+/// Les entrées utilisées par plusieurs actions sont signalées en rouge.
 /// Possibilité de réinitialiser les touches par défaut.
 ///
 /// # Arguments
@@ -73,85 +248,122 @@ impl Bind {
 /// - `asteroids` Les astéroids.
 /// - `sound` Les sons du jeu.
 /// - `actual_binding` Le Bind actuel.
+/// - `other_binding` Le Bind de l'autre joueur (pour détecter les conflits).
+/// - `title` Le titre du menu (pour distinguer les joueurs).
+/// - `default` Le Bind par défaut (utilisé par 'Reset').
 ///
+#[allow(clippy::too_many_arguments)]
 pub async fn binding(
     background_texture: &Texture2D,
     asteroid_texture: &Texture2D,
     asteroids: &mut Vec<Asteroid>,
     sound: &Sound,
     actual_binding: &mut Bind,
+    other_binding: &Bind,
+    title: &str,
+    default: fn() -> Bind,
 ) {
     let mut selected_index = 0;
-    let options = ["UP", "DOWN", "LEFT", "RIGHT", "RESET", "Back"];
+    let reset_index = Action::ALL.len();
+    let back_index = reset_index + 1;
     let mut last_press = Instant::now();
     loop {
         clear_background(BLACK);
         background_asteroids(background_texture, asteroid_texture, asteroids).await;
 
-        let dimension = measure_text("Binding", None, 50, 1.0);
+        let dimension = measure_text(title, None, 50, 1.0);
         draw_text(
-            "Binding",
+            title,
             (screen_width() - dimension.width) / 2.0,
-            200.0,
+            150.0,
             50.0,
             WHITE,
         );
 
-        for (i, &option) in options.iter().enumerate() {
-            let color = if i == selected_index { YELLOW } else { WHITE };
-            if option == "Back" {
-                if option == "Back" {
-                    let text = "Back";
-                    let dimension = measure_text(text, None, 40, 1.0);
-                    draw_text(
-                        option,
-                        (screen_width() - dimension.width) / 2.0,
-                        screen_height() - 200.0,
-                        40.0,
-                        color,
-                    );
-                    continue;
-                }
-            } else if option == "RESET" {
-                let text = "Reset";
-                let dimension = measure_text(text, None, 40, 1.0);
-                draw_text(
-                    text,
-                    (screen_width() - dimension.width) / 2.0,
-                    350.0 + i as f32 * 50.0,
-                    40.0,
-                    color,
-                );
-                continue;
-            }
+        let conflicts = actual_binding.find_conflicts(other_binding);
+        for (i, &action) in Action::ALL.iter().enumerate() {
+            let inputs = actual_binding.get_inputs(action);
+            let in_conflict = conflicts
+                .iter()
+                .any(|c| c.action == action || (!c.other_player && c.other == action));
+            let labels: Vec<String> = inputs.iter().map(|input| input.label()).collect();
             let text = format!(
-                "Bind for {}: {:?}",
-                option,
-                match i {
-                    0 => actual_binding.get_up(),
-                    1 => actual_binding.get_down(),
-                    2 => actual_binding.get_left(),
-                    3 => actual_binding.get_right(),
-                    _ => KeyCode::Unknown,
+                "{} : {}",
+                action.name(),
+                if labels.is_empty() {
+                    "-".to_string()
+                } else {
+                    labels.join(", ")
                 }
             );
-            let dimension = measure_text(&text, None, 40, 1.0);
+            let color = match (i == selected_index, in_conflict) {
+                (true, _) => YELLOW,
+                (false, true) => RED,
+                (false, false) => WHITE,
+            };
+            let dimension = measure_text(&text, None, 35, 1.0);
             draw_text(
                 &text,
                 (screen_width() - dimension.width) / 2.0,
-                350.0 + i as f32 * 50.0,
+                250.0 + i as f32 * 45.0,
+                35.0,
+                color,
+            );
+        }
+        for (i, text) in ["Reset", "Back"].iter().enumerate() {
+            let color = if reset_index + i == selected_index {
+                YELLOW
+            } else {
+                WHITE
+            };
+            let dimension = measure_text(text, None, 40, 1.0);
+            draw_text(
+                text,
+                (screen_width() - dimension.width) / 2.0,
+                250.0 + (reset_index + i) as f32 * 45.0 + 20.0,
                 40.0,
                 color,
             );
         }
 
+        let hint = "'Enter' : replace   'Tab' : add   'Delete' : clear";
+        let dimension = measure_text(hint, None, 25, 1.0);
+        draw_text(
+            hint,
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 230.0,
+            25.0,
+            GRAY,
+        );
+        for (i, conflict) in conflicts.iter().take(3).enumerate() {
+            let text = format!(
+                "Conflict : {} is used by {} and {}{}",
+                conflict.input.label(),
+                conflict.action.name(),
+                conflict.other.name(),
+                if conflict.other_player {
+                    " (other player)"
+                } else {
+                    ""
+                }
+            );
+            let dimension = measure_text(&text, None, 25, 1.0);
+            draw_text(
+                &text,
+                (screen_width() - dimension.width) / 2.0,
+                screen_height() - 190.0 + i as f32 * 30.0,
+                25.0,
+                RED,
+            );
+        }
+
         if is_key_pressed(KeyCode::Down) {
-            selected_index = (selected_index + 1) % options.len();
+            selected_index = (selected_index + 1) % (back_index + 1);
             sound.play_sound_effect("select_menu");
         }
         if is_key_pressed(KeyCode::Up) {
             selected_index = if selected_index == 0 {
-                options.len() - 1
+                back_index
             } else {
                 selected_index - 1
             };
@@ -162,40 +374,65 @@ pub async fn binding(
             return;
         }
 
-        if handle_enter_press(&mut last_press) {
-            match selected_index {
-                0 => {
-                    actual_binding
-                        .set_up(pressed_key(background_texture, asteroid_texture, asteroids).await);
-                }
-                1 => {
-                    actual_binding.set_down(
-                        pressed_key(background_texture, asteroid_texture, asteroids).await,
-                    );
-                }
-                2 => {
-                    actual_binding.set_left(
-                        pressed_key(background_texture, asteroid_texture, asteroids).await,
-                    );
-                }
-                3 => {
-                    actual_binding.set_right(
-                        pressed_key(background_texture, asteroid_texture, asteroids).await,
-                    );
-                }
-                4 => {
-                    actual_binding.set_up(KeyCode::Up);
-                    actual_binding.set_down(KeyCode::Down);
-                    actual_binding.set_left(KeyCode::Left);
-                    actual_binding.set_right(KeyCode::Right);
-                }
-                5 => {
-                    return;
-                }
+        if selected_index < reset_index {
+            let action = Action::ALL[selected_index];
+            if is_key_pressed(KeyCode::Delete) {
+                actual_binding.clear_inputs(action);
+            }
+            if is_key_pressed(KeyCode::Tab) {
+                let input = pressed_input(background_texture, asteroid_texture, asteroids).await;
+                actual_binding.add_input(action, input);
+                last_press = Instant::now();
+            }
+        }
 
-                _ => {}
+        if handle_enter_press(&mut last_press) {
+            if selected_index < reset_index {
+                let input = pressed_input(background_texture, asteroid_texture, asteroids).await;
+                actual_binding.set_input(Action::ALL[selected_index], input);
+                last_press = Instant::now();
+            } else if selected_index == reset_index {
+                actual_binding.reset(default());
+            } else {
+                return;
             }
         }
         next_frame().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_binds_have_no_conflicts() {
+        let one = Bind::new();
+        let two = Bind::new_player_two();
+        assert!(one.find_conflicts(&two).is_empty());
+        assert!(two.find_conflicts(&one).is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_are_detected() {
+        let mut one = Bind::new();
+        let two = Bind::new_player_two();
+        one.add_input(Action::Pause, Input::Key(KeyCode::Up));
+        one.set_input(Action::Fire, Input::Key(KeyCode::W));
+
+        let conflicts = one.find_conflicts(&two);
+        assert_eq!(conflicts.len(), 2);
+        assert!(conflicts.contains(&Conflict {
+            input: Input::Key(KeyCode::Up),
+            action: Action::Thrust,
+            other: Action::Pause,
+            other_player: false,
+        }));
+        assert!(conflicts.contains(&Conflict {
+            input: Input::Key(KeyCode::W),
+            action: Action::Fire,
+            other: Action::Thrust,
+            other_player: true,
+        }));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::tuning::tuning;
 use macroquad::prelude::*;
 
 /// Structure du trou noir.
@@ -8,8 +9,6 @@ pub struct BlackHole {
 }
 
 impl BlackHole {
-    const BLACKHOLE_SIZE: f32 = 50.0;
-
     /// Créer un nouveau trou noir.
     ///     
     /// # Arguments
@@ -28,7 +27,7 @@ impl BlackHole {
     pub fn new(position: Vec2) -> Self {
         BlackHole {
             position,
-            size: Self::BLACKHOLE_SIZE,
+            size: tuning().blackhole_size,
             rotation: 0.0,
         }
     }
@@ -53,7 +52,7 @@ impl BlackHole {
 
     /// Met à jour la rotation du trou noir (tourne sur lui-même).
     pub fn update_rotation(&mut self) {
-        self.rotation += 0.01;
+        self.rotation += tuning().blackhole_spin;
     }
 
     /// Dessine le trou noir à partir de sa texture (son skin)
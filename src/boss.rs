@@ -0,0 +1,478 @@
+//! # Boss Module
+//!
+//! Ce module gère les boss qui apparaissent à la fin de chaque série de vagues
+//! (modes dont les règles activent `ModeRules::bosses`) :
+//! - l'astéroïde géant, formé de blocs qui se scindent en astéroïdes lorsqu'ils sont détruits ;
+//! - le vaisseau mère, dont les modules se brisent en débris métalliques et qui tire des salves.
+//!
+//! Chaque boss est formé de segments destructibles et enchaîne des attaques :
+//! apparition d'astéroïdes, salves de projectiles et création de trous noirs.
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::blackhole::BlackHole;
+use crate::material::Material;
+use crate::missile::Missile;
+use crate::rng::game_rng;
+use crate::score::add_score;
+use crate::shield::EnergyShield;
+use crate::sound::Sound;
+use crate::spaceship::Spaceship;
+use crate::stellarobject::StellarObject;
+use crate::tuning::tuning;
+use ::rand::Rng;
+use macroquad::prelude::*;
+use std::f32::consts::PI;
+
+/// Type de boss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossKind {
+    /// Astéroïde géant.
+    GiantAsteroid,
+    /// Vaisseau mère.
+    Mothership,
+}
+
+/// Attaque d'un boss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Attack {
+    /// Lance des astéroïdes vers le joueur.
+    SpawnAsteroids,
+    /// Tire une salve de projectiles en éventail.
+    Volley,
+    /// Crée un trou noir près du joueur.
+    BlackHole,
+}
+
+impl BossKind {
+    /// Nom du boss (affiché au-dessus de la barre de vie).
+    pub fn name(self) -> &'static str {
+        match self {
+            BossKind::GiantAsteroid => "Giant asteroid",
+            BossKind::Mothership => "Mothership",
+        }
+    }
+
+    /// Cycle d'attaques du boss.
+    fn attacks(self) -> &'static [Attack] {
+        match self {
+            BossKind::GiantAsteroid => &[
+                Attack::SpawnAsteroids,
+                Attack::BlackHole,
+                Attack::SpawnAsteroids,
+            ],
+            BossKind::Mothership => &[
+                Attack::Volley,
+                Attack::SpawnAsteroids,
+                Attack::Volley,
+                Attack::BlackHole,
+            ],
+        }
+    }
+
+    /// Position (par rapport au centre du boss) et taille de chaque segment.
+    fn layout(self) -> Vec<(Vec2, f32)> {
+        match self {
+            BossKind::GiantAsteroid => {
+                let mut layout = vec![(Vec2::ZERO, 70.0)];
+                layout.extend((0..6).map(|i| (Vec2::from_angle(i as f32 * PI / 3.0) * 95.0, 45.0)));
+                layout
+            }
+            BossKind::Mothership => vec![
+                (vec2(-150.0, 0.0), 35.0),
+                (vec2(-75.0, 0.0), 40.0),
+                (vec2(0.0, 0.0), 50.0),
+                (vec2(75.0, 0.0), 40.0),
+                (vec2(150.0, 0.0), 35.0),
+                (vec2(0.0, -60.0), 25.0),
+            ],
+        }
+    }
+
+    /// Matériau des astéroïdes libérés par un segment détruit.
+    fn debris(self) -> Material {
+        match self {
+            BossKind::GiantAsteroid => Material::Rock,
+            BossKind::Mothership => Material::Metallic,
+        }
+    }
+}
+
+/// Segment destructible d'un boss.
+struct Segment {
+    /// Position par rapport au centre du boss (avant rotation).
+    offset: Vec2,
+    /// Rayon du segment.
+    size: f32,
+    /// Nombre de missiles restant à encaisser.
+    hit_points: u32,
+}
+
+/// Boss en cours de combat.
+pub struct Boss {
+    kind: BossKind,
+    position: Vec2,
+    rotation: f32,
+    segments: Vec<Segment>,
+    max_health: u32,
+    /// Projectiles tirés par le boss.
+    projectiles: Vec<Missile>,
+    spawn_time: f64,
+    next_attack_time: f64,
+    attack_index: usize,
+}
+
+impl Boss {
+    /// Un boss apparaît toutes les `EVERY_WAVES` vagues.
+    pub const EVERY_WAVES: u32 = 3;
+    /// Points gagnés en battant un boss.
+    pub const DEFEAT_SCORE: u32 = 2000;
+    /// Points gagnés en détruisant un segment.
+    const SEGMENT_SCORE: u32 = 150;
+    /// Délai (en secondes) entre deux attaques.
+    const ATTACK_DELAY: f64 = 3.0;
+    /// Durée (en secondes) de l'entrée du boss depuis le haut de l'écran.
+    const ENTRY_DURATION: f32 = 3.0;
+    /// Vitesse des projectiles du boss.
+    const PROJECTILE_SPEED: f32 = 4.0;
+
+    /// Vérifie si un boss doit apparaître : au début de la vague qui suit chaque série de `EVERY_WAVES` vagues.
+    ///
+    /// # Arguments
+    /// - `wave` Le numéro de la vague en cours.
+    /// - `last_boss_wave` La vague du dernier boss (0 si aucun boss n'est encore apparu).
+    ///
+    pub fn is_due(wave: u32, last_boss_wave: u32) -> bool {
+        wave > last_boss_wave && wave > 1 && (wave - 1).is_multiple_of(Self::EVERY_WAVES)
+    }
+
+    /// Créer le boss d'une vague. Les boss alternent entre l'astéroïde géant et le vaisseau mère,
+    /// et leurs segments sont de plus en plus résistants.
+    ///
+    /// # Arguments
+    /// - `wave` Le numéro de la vague en cours.
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn new(wave: u32, time: f64) -> Self {
+        let encounter = (wave.max(2) - 1) / Self::EVERY_WAVES;
+        let kind = if encounter % 2 == 1 {
+            BossKind::GiantAsteroid
+        } else {
+            BossKind::Mothership
+        };
+        let segments: Vec<Segment> = kind
+            .layout()
+            .into_iter()
+            .map(|(offset, size)| Segment {
+                offset,
+                size,
+                hit_points: 2 + encounter,
+            })
+            .collect();
+        Self {
+            kind,
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            max_health: segments.iter().map(|segment| segment.hit_points).sum(),
+            segments,
+            projectiles: Vec::new(),
+            spawn_time: time,
+            next_attack_time: time + Self::ATTACK_DELAY,
+            attack_index: 0,
+        }
+    }
+
+    /// Getter du type de boss.
+    pub fn get_kind(&self) -> BossKind {
+        self.kind
+    }
+
+    /// Points de vie restants (somme des segments).
+    pub fn health(&self) -> u32 {
+        self.segments.iter().map(|segment| segment.hit_points).sum()
+    }
+
+    /// Points de vie initiaux.
+    pub fn get_max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    /// Vérifie si tous les segments du boss sont détruits.
+    pub fn is_defeated(&self) -> bool {
+        self.health() == 0
+    }
+
+    /// Position d'un segment à l'écran.
+    fn segment_position(&self, segment: &Segment) -> Vec2 {
+        self.position + Vec2::from_angle(self.rotation).rotate(segment.offset)
+    }
+
+    /// Mise à jour du boss : déplacement, attaques, projectiles et collisions avec le vaisseau.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `arena` La taille de l'écran.
+    /// - `asteroids` Les astéroids de la partie.
+    /// - `blackholes` Les trous noirs de la partie.
+    /// - `spaceship` Le vaisseau du joueur.
+    /// - `sound` Les sons du jeu.
+    ///
+    /// # Returns
+    /// - `true` si le vaisseau est détruit.
+    ///
+    pub fn update(
+        &mut self,
+        time: f64,
+        arena: Vec2,
+        asteroids: &mut Vec<Asteroid>,
+        blackholes: &mut Vec<BlackHole>,
+        spaceship: &mut Spaceship,
+        sound: &Sound,
+    ) -> bool {
+        // Entrée par le haut de l'écran, puis va-et-vient
+        let elapsed = (time - self.spawn_time) as f32;
+        let entry = (elapsed / Self::ENTRY_DURATION).min(1.0);
+        self.position = vec2(
+            arena.x / 2.0 + (elapsed * 0.3).sin() * arena.x * 0.3,
+            -150.0 + entry * (arena.y * 0.25 + 150.0) + (elapsed * 0.5).sin() * arena.y * 0.05,
+        );
+        if self.kind == BossKind::GiantAsteroid {
+            self.rotation = elapsed * 0.2;
+        }
+
+        if time >= self.next_attack_time {
+            let attacks = self.kind.attacks();
+            let attack = attacks[self.attack_index % attacks.len()];
+            self.attack(
+                attack,
+                arena,
+                asteroids,
+                blackholes,
+                spaceship.get_position(),
+            );
+            self.attack_index += 1;
+            self.next_attack_time = time + Self::ATTACK_DELAY;
+        }
+
+        for projectile in &mut self.projectiles {
+            projectile.move_missile();
+        }
+        Missile::clear_missiles(&mut self.projectiles);
+
+        let mut destroyed = false;
+        let mut i = 0;
+        while i < self.projectiles.len() {
+            if self.projectiles[i].is_collide_circle(spaceship.get_position(), spaceship.get_size())
+            {
+                self.projectiles.swap_remove(i);
+                destroyed |= spaceship.check_shield(EnergyShield::MISSILE_DAMAGE, time, sound);
+            } else {
+                i += 1;
+            }
+        }
+        for segment in self
+            .segments
+            .iter()
+            .filter(|segment| segment.hit_points > 0)
+        {
+            let distance = Vec2::distance(self.segment_position(segment), spaceship.get_position());
+            if distance < segment.size + spaceship.get_size() {
+                let damage =
+                    EnergyShield::impact_damage(segment.size, spaceship.get_speed().length());
+                destroyed |= spaceship.check_shield(damage, time, sound);
+            }
+        }
+        destroyed
+    }
+
+    /// Déclenche une attaque.
+    ///
+    /// # Arguments
+    /// - `attack` L'attaque à déclencher.
+    /// - `arena` La taille de l'écran.
+    /// - `asteroids` Les astéroids de la partie.
+    /// - `blackholes` Les trous noirs de la partie.
+    /// - `target` La position du joueur.
+    ///
+    fn attack(
+        &mut self,
+        attack: Attack,
+        arena: Vec2,
+        asteroids: &mut Vec<Asteroid>,
+        blackholes: &mut Vec<BlackHole>,
+        target: Vec2,
+    ) {
+        let mut rng = game_rng();
+        match attack {
+            Attack::SpawnAsteroids => {
+                let launchers: Vec<Vec2> = self
+                    .segments
+                    .iter()
+                    .filter(|segment| segment.hit_points > 0)
+                    .map(|segment| self.segment_position(segment))
+                    .take(2)
+                    .collect();
+                for launcher in launchers {
+                    let direction = (target - launcher)
+                        .normalize_or_zero()
+                        .rotate(Vec2::from_angle(rng.gen_range(-0.4..0.4)));
+                    asteroids.push(Asteroid::new(
+                        launcher,
+                        direction * 1.5,
+                        AsteroidShape::Medium,
+                        tuning().asteroid_small_size,
+                    ));
+                }
+            }
+            Attack::Volley => {
+                let aim = (target - self.position).normalize_or_zero();
+                for angle in [-0.3, -0.15, 0.0, 0.15, 0.3] {
+                    let direction = Vec2::from_angle(angle).rotate(aim);
+                    self.projectiles.push(Missile::new(
+                        self.position,
+                        direction * Self::PROJECTILE_SPEED,
+                        5.0,
+                        3.0,
+                        0,
+                    ));
+                }
+            }
+            Attack::BlackHole => {
+                let offset = Vec2::from_angle(rng.gen_range(0.0..2.0 * PI)) * 250.0;
+                let position = (target + offset).clamp(Vec2::splat(50.0), arena - 50.0);
+                blackholes.push(BlackHole::new(position));
+            }
+        }
+    }
+
+    /// Gestion des collisions entre les missiles du joueur et les segments du boss.
+    /// Un segment détruit se scinde en astéroïds (voir `Asteroid::split`) et rapporte des points.
+    ///
+    /// # Arguments
+    /// - `missiles` Les missiles du joueur.
+    /// - `asteroids` Les astéroids de la partie.
+    /// - `spaceship` Le vaisseau du joueur.
+    /// - `sound` Les sons du jeu.
+    ///
+    pub fn hit_by_missiles(
+        &mut self,
+        missiles: &mut Vec<Missile>,
+        asteroids: &mut Vec<Asteroid>,
+        spaceship: &mut Spaceship,
+        sound: &Sound,
+    ) {
+        let mut i = 0;
+        while i < missiles.len() {
+            let hit = self.segments.iter().position(|segment| {
+                segment.hit_points > 0
+                    && missiles[i].is_collide_circle(self.segment_position(segment), segment.size)
+            });
+            let Some(index) = hit else {
+                i += 1;
+                continue;
+            };
+            missiles.swap_remove(i);
+            let position = self.segment_position(&self.segments[index]);
+            let segment = &mut self.segments[index];
+            segment.hit_points -= 1;
+            if segment.hit_points > 0 {
+                sound.play_sound_effect("collision");
+                continue;
+            }
+            sound.play_sound_effect("boom");
+            let mut block = Asteroid::new(
+                position,
+                Vec2::ZERO,
+                AsteroidShape::Large,
+                tuning().asteroid_large_size,
+            );
+            block.set_material(self.kind.debris());
+            asteroids.extend(block.split());
+            add_score(spaceship, Self::SEGMENT_SCORE);
+        }
+    }
+
+    /// Dessine le boss et ses projectiles.
+    ///
+    /// # Arguments
+    /// - `asteroid_texture` La texture des astéroids (pour l'astéroïde géant).
+    ///
+    pub fn draw(&self, asteroid_texture: &Texture2D) {
+        let max_hit_points = self.max_health as f32 / self.segments.len() as f32;
+        if self.kind == BossKind::Mothership {
+            let alive: Vec<Vec2> = self
+                .segments
+                .iter()
+                .filter(|segment| segment.hit_points > 0)
+                .map(|segment| self.segment_position(segment))
+                .collect();
+            for pair in alive.windows(2) {
+                draw_line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, 12.0, DARKGRAY);
+            }
+        }
+        for segment in self
+            .segments
+            .iter()
+            .filter(|segment| segment.hit_points > 0)
+        {
+            let position = self.segment_position(segment);
+            // Les segments rougissent en perdant des points de vie
+            let health = segment.hit_points as f32 / max_hit_points;
+            let tint = Color::new(1.0, health, health, 1.0);
+            match self.kind {
+                BossKind::GiantAsteroid => draw_texture_ex(
+                    asteroid_texture,
+                    position.x - segment.size,
+                    position.y - segment.size,
+                    tint,
+                    DrawTextureParams {
+                        dest_size: Some(Vec2::splat(segment.size * 2.0)),
+                        rotation: self.rotation,
+                        ..Default::default()
+                    },
+                ),
+                BossKind::Mothership => {
+                    draw_circle(position.x, position.y, segment.size, GRAY);
+                    draw_circle(position.x, position.y, segment.size * 0.6, tint);
+                    draw_circle_lines(position.x, position.y, segment.size, 2.0, WHITE);
+                }
+            }
+        }
+        for projectile in &self.projectiles {
+            projectile.draw_missile_with_color(RED);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shipclass::ShipDefinition;
+
+    #[test]
+    fn test_boss_is_due_after_each_series_of_waves() {
+        assert!(!Boss::is_due(1, 0));
+        assert!(!Boss::is_due(3, 0));
+        assert!(Boss::is_due(4, 0));
+        assert!(!Boss::is_due(4, 4));
+        assert!(!Boss::is_due(5, 4));
+        assert!(Boss::is_due(7, 4));
+        assert_eq!(Boss::new(4, 0.0).get_kind(), BossKind::GiantAsteroid);
+        assert_eq!(Boss::new(7, 0.0).get_kind(), BossKind::Mothership);
+    }
+
+    #[test]
+    fn test_destroyed_segment_splits_into_asteroids() {
+        let mut boss = Boss::new(4, 0.0);
+        let mut spaceship = Spaceship::new(&ShipDefinition::default());
+        let mut asteroids = Vec::new();
+        let health = boss.health();
+        for _ in 0..3 {
+            let mut missiles = vec![Missile::new(vec2(-5.0, -5.0), vec2(1.0, 1.0), 5.0, 2.0, 0)];
+            boss.hit_by_missiles(&mut missiles, &mut asteroids, &mut spaceship, &Sound::new());
+            assert!(missiles.is_empty());
+        }
+        assert_eq!(boss.health(), health - 3);
+        assert_eq!(asteroids.len(), 2);
+        assert_eq!(spaceship.score, Boss::SEGMENT_SCORE);
+        assert!(!boss.is_defeated());
+    }
+}
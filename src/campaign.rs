@@ -0,0 +1,497 @@
+//! # Campaign Module
+//!
+//! Ce module gère la campagne : une suite de missions rangées dans `assets/campaign`
+//! (fichiers `.level`, voir le module `level`), aux objectifs variés : survivre, détruire
+//! de gros astéroïdes, escorter une capsule de cargaison ou atteindre une balise
+//! au travers d'un champ de trous noirs.
+//!
+//! Chaque mission est précédée d'un briefing et suivie d'un débriefing qui attribue
+//! de une à trois étoiles. La progression est sauvegardée dans `saves/campaign.txt` :
+//! une mission réussie débloque la suivante.
+use super::play;
+use crate::asteroid::Asteroid;
+use crate::bind::Bind;
+use crate::level::{Level, LevelOutcome};
+use crate::save::{read_key_values, write_key_values};
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, handle_enter_press};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Mission de la campagne.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mission {
+    /// Identifiant de la mission (nom du fichier, sans extension).
+    pub id: String,
+    /// Le niveau de la mission.
+    pub level: Level,
+}
+
+impl Mission {
+    /// Dossier contenant les missions.
+    pub const FOLDER: &'static str = "assets/campaign";
+
+    /// Charge les missions, dans l'ordre des noms de fichier (les missions invalides sont ignorées).
+    pub fn load_all() -> Vec<Self> {
+        Level::load_folder(Self::FOLDER)
+            .into_iter()
+            .filter_map(|(path, level)| match level {
+                Ok(level) => Some(Self {
+                    id: path.file_stem()?.to_string_lossy().into_owned(),
+                    level,
+                }),
+                Err(errors) => {
+                    eprintln!(
+                        "Mission invalide {}: {}",
+                        path.display(),
+                        errors.join(" / ")
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Nombre d'étoiles obtenues (0 si la mission est perdue).
+    /// Une étoile pour la victoire, une si le bouclier n'a pas été touché,
+    /// une si le score de référence est atteint (ou si la mission n'en a pas).
+    ///
+    /// # Arguments
+    /// - `outcome` Le résultat de la partie.
+    ///
+    pub fn star_rating(&self, outcome: &LevelOutcome) -> u32 {
+        if !outcome.won {
+            return 0;
+        }
+        1 + (outcome.shield_hits == 0) as u32
+            + self.level.par.is_none_or(|par| outcome.score >= par) as u32
+    }
+}
+
+/// Progression de la campagne : meilleur nombre d'étoiles de chaque mission.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CampaignProgress {
+    /// Étoiles obtenues, par identifiant de mission.
+    stars: HashMap<String, u32>,
+}
+
+impl CampaignProgress {
+    /// Nom du fichier de sauvegarde de la progression.
+    const FILE_NAME: &'static str = "campaign.txt";
+    /// Nombre maximal d'étoiles par mission.
+    pub const MAX_STARS: u32 = 3;
+
+    /// Charge la progression depuis le disque.
+    pub fn load() -> Self {
+        Self {
+            stars: read_key_values(Self::FILE_NAME)
+                .into_iter()
+                .filter_map(|(id, stars)| Some((id, stars.parse().ok()?)))
+                .collect(),
+        }
+    }
+
+    /// Sauvegarde la progression sur le disque (triée par mission).
+    pub fn save(&self) {
+        let mut entries: Vec<(String, String)> = self
+            .stars
+            .iter()
+            .map(|(id, stars)| (id.clone(), stars.to_string()))
+            .collect();
+        entries.sort();
+        write_key_values(Self::FILE_NAME, &entries);
+    }
+
+    /// Meilleur nombre d'étoiles d'une mission.
+    ///
+    /// # Arguments
+    /// - `id` L'identifiant de la mission.
+    ///
+    pub fn stars(&self, id: &str) -> u32 {
+        self.stars.get(id).copied().unwrap_or(0)
+    }
+
+    /// Nombre total d'étoiles obtenues.
+    pub fn total_stars(&self) -> u32 {
+        self.stars.values().sum()
+    }
+
+    /// Enregistre le résultat d'une mission (seul le meilleur est conservé).
+    ///
+    /// # Arguments
+    /// - `id` L'identifiant de la mission.
+    /// - `stars` Le nombre d'étoiles obtenues.
+    ///
+    /// # Returns
+    /// - `bool` Vrai si le meilleur résultat de la mission est amélioré.
+    ///
+    pub fn record(&mut self, id: &str, stars: u32) -> bool {
+        if stars <= self.stars(id) {
+            return false;
+        }
+        self.stars.insert(id.to_string(), stars);
+        true
+    }
+
+    /// Vérifie si une mission est débloquée (la première l'est toujours,
+    /// les suivantes une fois la précédente réussie).
+    ///
+    /// # Arguments
+    /// - `missions` Les missions de la campagne.
+    /// - `index` L'indice de la mission.
+    ///
+    pub fn is_unlocked(&self, missions: &[Mission], index: usize) -> bool {
+        index == 0 || self.stars(&missions[index - 1].id) > 0
+    }
+}
+
+/// Texte des étoiles d'une mission (par exemple `[**-]`).
+///
+/// # Arguments
+/// - `stars` Le nombre d'étoiles.
+///
+fn stars_text(stars: u32) -> String {
+    format!(
+        "[{}{}]",
+        "*".repeat(stars as usize),
+        "-".repeat((CampaignProgress::MAX_STARS - stars) as usize)
+    )
+}
+
+/// Dessine un texte centré horizontalement.
+fn draw_centered(text: &str, y: f32, font_size: f32, color: Color) {
+    let dimension = measure_text(text, None, font_size as u16, 1.0);
+    draw_text(
+        text,
+        (screen_width() - dimension.width) / 2.0,
+        y,
+        font_size,
+        color,
+    );
+}
+
+/// Menu de la campagne : choix d'une mission débloquée, briefing, partie puis débriefing.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les commandes des joueurs.
+/// - `skins` Les skins (astéroïdes, trous noirs, arrière-plan, vaisseau).
+///
+pub async fn campaign_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &mut Sound,
+    bindings: &mut [Bind; 2],
+    skins: [&str; 4],
+) {
+    let missions = Mission::load_all();
+    let mut progress = CampaignProgress::load();
+    let mut selected_index = 0;
+    let mut last_enter_press = Instant::now();
+    sound.play_sound_effect("select_menu");
+
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        draw_centered("Campaign", 200.0, 50.0, WHITE);
+        draw_centered(
+            &format!(
+                "Stars : {} / {}",
+                progress.total_stars(),
+                missions.len() as u32 * CampaignProgress::MAX_STARS
+            ),
+            250.0,
+            30.0,
+            GRAY,
+        );
+        if missions.is_empty() {
+            draw_centered(
+                &format!("No mission found in {}", Mission::FOLDER),
+                350.0,
+                30.0,
+                GRAY,
+            );
+        }
+
+        for (i, mission) in missions.iter().enumerate() {
+            let unlocked = progress.is_unlocked(&missions, i);
+            let label = if unlocked {
+                format!(
+                    "{}. {}  {}",
+                    i + 1,
+                    mission.level.name,
+                    stars_text(progress.stars(&mission.id))
+                )
+            } else {
+                format!("{}. Locked", i + 1)
+            };
+            let color = match (i == selected_index, unlocked) {
+                (true, _) => YELLOW,
+                (false, true) => WHITE,
+                (false, false) => GRAY,
+            };
+            draw_text(
+                &label,
+                screen_width() / 2.0 - 200.0,
+                320.0 + i as f32 * 45.0,
+                40.0,
+                color,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) && !missions.is_empty() {
+            selected_index = (selected_index + 1) % missions.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) && !missions.is_empty() {
+            selected_index = if selected_index == 0 {
+                missions.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+        if !missions.is_empty()
+            && progress.is_unlocked(&missions, selected_index)
+            && handle_enter_press(&mut last_enter_press)
+        {
+            let mission = &missions[selected_index];
+            if briefing(
+                background_texture,
+                asteroid_texture,
+                asteroids,
+                sound,
+                mission,
+            )
+            .await
+            {
+                let outcome = play(
+                    "campaign",
+                    skins,
+                    get_time(),
+                    sound,
+                    bindings,
+                    Some(&mission.level),
+                    None,
+                )
+                .await;
+                if let Some(outcome) = outcome {
+                    let stars = mission.star_rating(&outcome);
+                    let improved = progress.record(&mission.id, stars);
+                    if improved {
+                        progress.save();
+                    }
+                    let unlocked = missions.get(selected_index + 1).filter(|_| stars > 0);
+                    debriefing(
+                        background_texture,
+                        asteroid_texture,
+                        asteroids,
+                        sound,
+                        &outcome,
+                        stars,
+                        improved,
+                        unlocked,
+                    )
+                    .await;
+                    if unlocked.is_some() {
+                        selected_index += 1;
+                    }
+                }
+            }
+        }
+
+        next_frame().await;
+    }
+}
+
+/// Écran de briefing d'une mission : objectif et consignes.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `mission` La mission.
+///
+/// # Returns
+/// - `bool` Vrai si le joueur lance la mission, faux s'il revient en arrière.
+///
+async fn briefing(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    mission: &Mission,
+) -> bool {
+    let mut last_enter_press = Instant::now();
+    sound.play_sound_effect("select_menu");
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        draw_centered(&mission.level.name, 200.0, 50.0, WHITE);
+        draw_centered(
+            &format!("Objective : {}", mission.level.goal.describe()),
+            270.0,
+            35.0,
+            YELLOW,
+        );
+        for (i, line) in mission.level.briefing.iter().enumerate() {
+            draw_centered(line, 340.0 + i as f32 * 35.0, 30.0, WHITE);
+        }
+        let y = 360.0 + mission.level.briefing.len() as f32 * 35.0;
+        if let Some(par) = mission.level.par {
+            draw_centered(&format!("Par score : {} points", par), y + 20.0, 30.0, GRAY);
+        }
+        draw_centered(
+            "Enter : launch the mission   Backspace : back",
+            screen_height() - 100.0,
+            30.0,
+            GRAY,
+        );
+
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            return true;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return false;
+        }
+
+        next_frame().await;
+    }
+}
+
+/// Écran de débriefing d'une mission : résultat, étoiles et mission débloquée.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `outcome` Le résultat de la partie.
+/// - `stars` Le nombre d'étoiles obtenues.
+/// - `improved` Vrai si c'est le meilleur résultat de la mission.
+/// - `unlocked` La mission suivante, si elle est débloquée.
+///
+#[allow(clippy::too_many_arguments)]
+async fn debriefing(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    outcome: &LevelOutcome,
+    stars: u32,
+    improved: bool,
+    unlocked: Option<&Mission>,
+) {
+    let mut last_enter_press = Instant::now();
+    sound.play_sound_effect(if outcome.won { "win" } else { "select_menu" });
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        if outcome.won {
+            draw_centered("Mission accomplished", 200.0, 50.0, GREEN);
+        } else {
+            draw_centered("Mission failed", 200.0, 50.0, RED);
+        }
+        draw_centered(&stars_text(stars), 270.0, 50.0, YELLOW);
+        draw_centered(
+            &format!(
+                "Score : {}   Shield hits : {}   Time : {:.0}s",
+                outcome.score, outcome.shield_hits, outcome.time
+            ),
+            340.0,
+            30.0,
+            WHITE,
+        );
+        if improved {
+            draw_centered("New best result!", 390.0, 30.0, YELLOW);
+        }
+        if let Some(mission) = unlocked {
+            draw_centered(
+                &format!("Next mission : {}", mission.level.name),
+                440.0,
+                30.0,
+                WHITE,
+            );
+        }
+        draw_centered(
+            "Enter : back to the campaign",
+            screen_height() - 100.0,
+            30.0,
+            GRAY,
+        );
+
+        if handle_enter_press(&mut last_enter_press) || is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_star_rating_and_unlocking() {
+        let missions: Vec<Mission> = ["01_a", "02_b", "03_c"]
+            .iter()
+            .map(|id| Mission {
+                id: id.to_string(),
+                level: Level {
+                    par: Some(1000),
+                    ..Level::default()
+                },
+            })
+            .collect();
+        let mut outcome = LevelOutcome {
+            won: true,
+            score: 1500,
+            shield_hits: 0,
+            time: 60.0,
+        };
+        assert_eq!(missions[0].star_rating(&outcome), 3);
+        outcome.shield_hits = 2;
+        assert_eq!(missions[0].star_rating(&outcome), 2);
+        outcome.score = 500;
+        assert_eq!(missions[0].star_rating(&outcome), 1);
+        outcome.won = false;
+        assert_eq!(missions[0].star_rating(&outcome), 0);
+
+        let mut progress = CampaignProgress::default();
+        assert!(progress.is_unlocked(&missions, 0));
+        assert!(!progress.is_unlocked(&missions, 1));
+        assert!(!progress.record("01_a", 0));
+        assert!(progress.record("01_a", 2));
+        assert!(!progress.record("01_a", 1));
+        assert_eq!(progress.stars("01_a"), 2);
+        assert!(progress.is_unlocked(&missions, 1));
+        assert!(!progress.is_unlocked(&missions, 2));
+        assert_eq!(stars_text(2), "[**-]");
+    }
+
+    #[test]
+    fn test_bundled_missions_are_valid() {
+        let missions = Level::load_folder(Mission::FOLDER);
+        assert!(missions.len() >= 4);
+        for (path, level) in missions {
+            assert!(level.is_ok(), "{}: {:?}", path.display(), level);
+        }
+    }
+}
@@ -1,14 +1,22 @@
 use super::draw_background;
 use super::draw_time;
-use super::utils::sigmoid_range_secs;
-use crate::bind::Bind;
 use crate::blackhole::BlackHole;
+use crate::control::ControlScheme;
 use crate::gui::draw_asteroid_number;
+use crate::gui::draw_combo;
 use crate::gui::draw_score;
+use crate::gui::draw_score_popups;
 use crate::gui::draw_shield;
+use crate::level::Spawner;
+use crate::ore::Ore;
+use crate::rules::ModeRules;
+use crate::score::add_score;
 use crate::sound::Sound;
-use crate::utils::sigmoid_speed;
-use crate::{asteroid::Asteroid, missile::Missile, spaceship::Spaceship};
+use crate::{
+    asteroid::Asteroid,
+    missile::Missile,
+    spaceship::{ShipInput, Spaceship},
+};
 use macroquad::prelude::*;
 
 /// Affichage du jeu pour le mode classique.
@@ -43,16 +51,18 @@ pub fn draw_classic(
     for blackhole in blackholes {
         blackhole.draw_blackhole(blackhole_texture);
     }
-    spaceship.draw_spaceship_classic(spaceship_texture, time);
+    spaceship.draw_spaceship(spaceship_texture, time);
     draw_time(&time_str);
-    draw_shield(spaceship.shield);
+    draw_shield(spaceship, time);
     draw_score(spaceship.score);
+    draw_combo(&spaceship.score_engine);
 
     draw_asteroid_number(asteroids);
 
     for missile in missiles {
         missile.draw_missile();
     }
+    draw_score_popups(&spaceship.score_engine, time);
 }
 
 /// Mise à jour du jeu pour le mode 'classic'.
@@ -61,44 +71,56 @@ pub fn draw_classic(
 /// # Arguments
 /// - `asteroids` Les astéroids.
 /// - `blackholes` Les trous noirs.
-/// - `last_add_asteroid` Le temps de la dernière apparition d'astéroid (pour contrôler le temps entre chaque apparition).
+/// - `spawner` La source des apparitions d'astéroids (aléatoires selon les règles, ou scriptées par un niveau).
 /// - `spaceship` Le vaisseau spatial.
 /// - `time` Le temps de jeu.
 /// - `last_shoot` Le temps du dernier tir du vaisseau spatial.
 /// - `missiles` Les missiles tirés par le vaisseau spatial.
+/// - `ores` Le minerai laissé par les astéroids détruits (modes avec boutique).
 /// - `sound` Les sons du jeu.
-/// - `keybinding` Les touches du clavier utilisées pour contrôler le vaisseau spatial (choisi par le joueur).
+/// - `input` Les commandes du vaisseau spatial pour la frame (clavier du joueur ou pilote automatique).
+/// - `scheme` Le schéma de contrôle du vaisseau spatial (choisi dans les options).
+/// - `rules` Les règles du mode de jeu (apparition et vitesse des astéroïds, trous noirs, missiles).
 ///
 #[allow(clippy::too_many_arguments)]
 pub async fn update_classic(
     asteroids: &mut Vec<Asteroid>,
     blackholes: &mut Vec<BlackHole>,
-    last_add_asteroid: &mut f64,
+    spawner: &mut Spawner,
     spaceship: &mut Spaceship,
     time: f64,
     last_shoot: &mut f64,
     missiles: &mut Vec<Missile>,
+    ores: &mut Vec<Ore>,
     sound: &Sound,
-    keybinding: &Bind,
+    input: &ShipInput,
+    scheme: ControlScheme,
+    rules: &ModeRules,
 ) -> bool {
-    if time < 45.0
-        && Asteroid::push_or_dont(
-            asteroids,
-            last_add_asteroid,
-            time,
-            sigmoid_range_secs(time, 22.5),
-        )
-    {
-        *last_add_asteroid = time;
-    }
+    spawner.update(asteroids, time, rules);
 
     for asteroid in &mut *asteroids {
-        asteroid.move_asteroid(true, sigmoid_speed(time, "classic"));
+        asteroid.move_asteroid(true, rules.asteroid_speed(time));
+    }
+    if rules.black_holes {
+        let blackholes_before = blackholes.len();
+        Asteroid::what_collide_asteroids(
+            &mut *asteroids,
+            &mut *blackholes,
+            rules.difficulty.blackhole_chance(),
+        );
+        spaceship.stats.blackholes_formed += (blackholes.len() - blackholes_before) as u32;
+        for blackhole in &mut *blackholes {
+            blackhole.update_rotation();
+        }
     }
 
-    spaceship.update_position_classic(keybinding);
+    spaceship.apply_input(scheme, input);
 
-    if spaceship.spaceship_collision(&mut *asteroids).await && spaceship.check_shield(time, sound) {
+    if spaceship
+        .asteroid_impact(&mut *asteroids, time, sound)
+        .await
+    {
         return true;
     }
 
@@ -109,15 +131,37 @@ pub async fn update_classic(
         return true;
     }
 
-    if Missile::do_i_shoot_classic(KeyCode::Space, *last_shoot, time) {
-        missiles.push(Missile::new_missile_classic(spaceship, 5.0));
+    if input.fire && Missile::is_ready(spaceship, *last_shoot, time) {
+        let volley = Missile::fire(spaceship, rules.missile_speed, 0);
+        spaceship.register_shots(volley.len());
+        missiles.extend(volley);
         *last_shoot = time;
         sound.play_sound_effect("shoot");
     }
     for missile in &mut *missiles {
         missile.move_missile();
     }
-    Missile::clear_missiles(&mut *missiles);
-    Missile::what_collide_missile(&mut *missiles, &mut *asteroids, spaceship, sound);
+    if rules.missile_wrap {
+        Missile::wrap_missiles(&mut *missiles);
+    } else {
+        Missile::clear_missiles(&mut *missiles);
+    }
+    let destroyed = Missile::what_collide_missile(
+        &mut *missiles,
+        &mut *asteroids,
+        std::slice::from_mut(spaceship),
+        sound,
+        time,
+    );
+    if rules.ore_shop {
+        ores.extend(
+            destroyed
+                .iter()
+                .filter_map(|asteroid| Ore::drop_from(asteroid, time)),
+        );
+    }
+
+    let bonus = spaceship.score_engine.update(time, asteroids.is_empty());
+    add_score(spaceship, bonus);
     false
 }
@@ -0,0 +1,153 @@
+//! # Control Module
+//!
+//! Ce module définit les schémas de contrôle du vaisseau, indépendants du mode de jeu :
+//! - `Tank` : rotation et poussée (historiquement le mode 'classic').
+//! - `TwinStick` : déplacement dans 8 directions au clavier, visée à la souris (historiquement le mode 'modern').
+//! - `KeyboardAim` : visée par rotation au clavier, déplacement relatif au vaisseau avec pas de côté.
+use crate::spaceship::ShipInput;
+use macroquad::prelude::*;
+
+/// Schéma de contrôle du vaisseau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    Tank,
+    TwinStick,
+    KeyboardAim,
+}
+
+impl ControlScheme {
+    /// Tous les schémas, dans l'ordre d'affichage.
+    pub const ALL: [ControlScheme; 3] = [
+        ControlScheme::Tank,
+        ControlScheme::TwinStick,
+        ControlScheme::KeyboardAim,
+    ];
+
+    /// Schéma utilisé par défaut dans un mode de jeu.
+    ///
+    /// # Arguments
+    /// - `mode` Le mode de jeu ('classic' ou 'modern').
+    ///
+    pub fn default_for(mode: &str) -> Self {
+        match mode {
+            "modern" => ControlScheme::TwinStick,
+            _ => ControlScheme::Tank,
+        }
+    }
+
+    /// Nom du schéma (pour l'affichage et la sauvegarde).
+    pub fn name(self) -> &'static str {
+        match self {
+            ControlScheme::Tank => "Tank",
+            ControlScheme::TwinStick => "Twin-stick",
+            ControlScheme::KeyboardAim => "Keyboard aim",
+        }
+    }
+
+    /// Vrai si le schéma vise à la souris : les boutons de la souris tirent alors en plus de l'action 'Fire'.
+    pub fn fires_with_mouse(self) -> bool {
+        self == ControlScheme::TwinStick
+    }
+
+    /// Schéma correspondant à un nom (voir `name`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scheme| scheme.name() == name)
+    }
+
+    /// Calcule l'effet des commandes sur le vaisseau pour une frame.
+    ///
+    /// # Arguments
+    /// - `input` Les commandes du joueur.
+    /// - `position` La position du vaisseau.
+    /// - `rotation` La rotation actuelle du vaisseau, en radians.
+    /// - `turn_rate` La vitesse de rotation du vaisseau (radians par frame).
+    ///
+    /// # Returns
+    /// - `(Vec2, f32)` La direction de l'accélération (norme 1 au plus) et la nouvelle rotation.
+    ///
+    pub fn steer(
+        self,
+        input: &ShipInput,
+        position: Vec2,
+        rotation: f32,
+        turn_rate: f32,
+    ) -> (Vec2, f32) {
+        let axis = |positive: bool, negative: bool| positive as i32 as f32 - negative as i32 as f32;
+        let forward = Vec2::new(rotation.sin(), -rotation.cos());
+        match self {
+            ControlScheme::Tank => (
+                forward * axis(input.up, input.down),
+                rotation + axis(input.right, input.left) * turn_rate,
+            ),
+            ControlScheme::TwinStick => {
+                let direction =
+                    Vec2::new(axis(input.right, input.left), axis(input.down, input.up));
+                let aim = input.aim - position;
+                let rotation = if aim == Vec2::ZERO {
+                    rotation
+                } else {
+                    aim.y.atan2(aim.x) + std::f32::consts::FRAC_PI_2
+                };
+                (direction.normalize_or_zero(), rotation)
+            }
+            ControlScheme::KeyboardAim => {
+                let rotation = rotation + axis(input.right, input.left) * turn_rate;
+                let forward = Vec2::new(rotation.sin(), -rotation.cos());
+                let side = Vec2::new(rotation.cos(), rotation.sin());
+                let direction = forward * axis(input.up, input.down)
+                    + side * axis(input.strafe_right, input.strafe_left);
+                (direction.normalize_or_zero(), rotation)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_twin_stick_fires_with_mouse() {
+        assert!(ControlScheme::default_for("modern").fires_with_mouse());
+        assert!(!ControlScheme::default_for("classic").fires_with_mouse());
+        assert!(!ControlScheme::KeyboardAim.fires_with_mouse());
+    }
+
+    #[test]
+    fn test_twin_stick_diagonal_is_normalized() {
+        let diagonal = ShipInput {
+            up: true,
+            right: true,
+            ..Default::default()
+        };
+        let (direction, _) = ControlScheme::TwinStick.steer(&diagonal, Vec2::ZERO, 0.0, 0.05);
+        assert!((direction.length() - 1.0).abs() < 1e-6);
+        assert!(direction.x > 0.0 && direction.y < 0.0);
+    }
+
+    #[test]
+    fn test_twin_stick_faces_aim_and_keyboard_aim_strafes() {
+        let input = ShipInput {
+            aim: Vec2::new(100.0, 0.0),
+            ..Default::default()
+        };
+        let (_, rotation) = ControlScheme::TwinStick.steer(&input, Vec2::ZERO, 0.0, 0.05);
+        assert!((rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+
+        let strafe = ShipInput {
+            strafe_right: true,
+            ..Default::default()
+        };
+        let (direction, rotation) =
+            ControlScheme::KeyboardAim.steer(&strafe, Vec2::ZERO, 0.0, 0.05);
+        assert_eq!(rotation, 0.0);
+        assert!((direction - Vec2::new(1.0, 0.0)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_scheme_names_round_trip() {
+        for scheme in ControlScheme::ALL {
+            assert_eq!(ControlScheme::from_name(scheme.name()), Some(scheme));
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use super::draw_background;
+use super::draw_time;
+use super::utils::sigmoid_range_secs;
+use crate::bind::{Action, Bind};
+use crate::control::ControlScheme;
+use crate::difficulty::Difficulty;
+use crate::gui::{
+    coop_game_over, draw_asteroid_number, draw_player_panel, draw_score_popups, draw_tuning_errors,
+    pause,
+};
+use crate::score::{add_score, finalize_score};
+use crate::settings::Settings;
+use crate::shipclass::ShipDefinition;
+use crate::sound::Sound;
+use crate::stellarobject::StellarObject;
+use crate::tuning;
+use crate::utils::sigmoid_speed;
+use crate::{asteroid::Asteroid, missile::Missile, spaceship::Spaceship};
+use macroquad::prelude::*;
+
+/// Nombre de joueurs du mode coopératif.
+pub const PLAYER_COUNT: usize = 2;
+
+/// Couleurs associées à chaque joueur (panneaux du HUD).
+pub const PLAYER_COLORS: [Color; PLAYER_COUNT] = [SKYBLUE, ORANGE];
+
+/// Vérifie si un joueur est encore en jeu (bouclier non épuisé).
+///
+/// # Arguments
+/// - `spaceship` Le vaisseau du joueur.
+///
+pub fn is_alive(spaceship: &Spaceship) -> bool {
+    spaceship.shield > 0
+}
+
+/// Vérifie si tous les joueurs sont éliminés (fin de partie commune).
+///
+/// # Arguments
+/// - `players` Les vaisseaux des joueurs.
+///
+pub fn all_out(players: &[Spaceship]) -> bool {
+    !players.iter().any(is_alive)
+}
+
+/// Affichage du jeu pour le mode coopératif.
+///
+/// # Arguments
+/// - `asteroid_texture` La texture des astéroids.
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `spaceship_textures` Les textures des vaisseaux (une par joueur).
+/// - `asteroids` Les astéroids.
+/// - `players` Les vaisseaux des joueurs.
+/// - `time` Le temps de jeu.
+/// - `missiles` Les missiles tirés par les joueurs.
+///
+pub fn draw_coop(
+    asteroid_texture: &Texture2D,
+    background_texture: &Texture2D,
+    spaceship_textures: [&Texture2D; PLAYER_COUNT],
+    asteroids: &Vec<Asteroid>,
+    players: &[Spaceship; PLAYER_COUNT],
+    time: f64,
+    missiles: &Vec<Missile>,
+) {
+    let time_str = format!("{:.2}", time);
+    draw_background(background_texture);
+
+    for asteroid in asteroids {
+        asteroid.draw_asteroid(asteroid_texture);
+    }
+    for (i, player) in players.iter().enumerate() {
+        if is_alive(player) {
+            player.draw_spaceship(spaceship_textures[i], time);
+        }
+    }
+    for missile in missiles {
+        missile.draw_missile();
+    }
+
+    draw_time(&time_str);
+    draw_asteroid_number(asteroids);
+    for (i, player) in players.iter().enumerate() {
+        draw_player_panel(
+            &format!("P{}", i + 1),
+            player,
+            10.0 + i as f32 * 260.0,
+            PLAYER_COLORS[i],
+            time,
+        );
+        draw_score_popups(&player.score_engine, time);
+    }
+}
+
+/// Mise à jour du jeu pour le mode coopératif.
+/// Les règles sont celles du mode 'classic', chaque vaisseau étant contrôlé avec son propre Bind.
+/// Un joueur dont le bouclier est épuisé est éliminé jusqu'à la fin de la partie.
+///
+/// # Arguments
+/// - `asteroids` Les astéroids.
+/// - `last_add_asteroid` Le temps de la dernière apparition d'astéroid.
+/// - `players` Les vaisseaux des joueurs.
+/// - `time` Le temps de jeu.
+/// - `last_shoot` Le temps du dernier tir de chaque joueur.
+/// - `missiles` Les missiles tirés par les joueurs.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les touches de chaque joueur.
+/// - `difficulty` Le niveau de difficulté (apparition et vitesse des astéroids).
+///
+/// # Returns
+/// - `true` si les deux joueurs sont éliminés.
+///
+#[allow(clippy::too_many_arguments)]
+pub async fn update_coop(
+    asteroids: &mut Vec<Asteroid>,
+    last_add_asteroid: &mut f64,
+    players: &mut [Spaceship; PLAYER_COUNT],
+    time: f64,
+    last_shoot: &mut [f64; PLAYER_COUNT],
+    missiles: &mut Vec<Missile>,
+    sound: &Sound,
+    bindings: &[Bind; PLAYER_COUNT],
+    difficulty: Difficulty,
+) -> bool {
+    if time < 45.0 * difficulty.spawn_time_factor()
+        && Asteroid::push_or_dont(
+            asteroids,
+            last_add_asteroid,
+            time,
+            sigmoid_range_secs(time, 22.5, difficulty),
+            0.0,
+        )
+    {
+        *last_add_asteroid = time;
+    }
+
+    for asteroid in &mut *asteroids {
+        asteroid.move_asteroid(true, sigmoid_speed(time, "classic", difficulty));
+    }
+
+    for (i, player) in players.iter_mut().enumerate() {
+        if is_alive(player) {
+            player.update_position(ControlScheme::Tank, &bindings[i]);
+
+            player.asteroid_impact(&mut *asteroids, time, sound).await;
+
+            if is_alive(player) && Missile::do_i_shoot(&bindings[i], player, last_shoot[i], time) {
+                let volley = Missile::fire(player, 5.0, i);
+                player.register_shots(volley.len());
+                missiles.extend(volley);
+                last_shoot[i] = time;
+                sound.play_sound_effect("shoot");
+            }
+        }
+    }
+
+    for missile in &mut *missiles {
+        missile.move_missile();
+    }
+    Missile::clear_missiles(&mut *missiles);
+    Missile::what_collide_missile(&mut *missiles, &mut *asteroids, players, sound, time);
+
+    for player in players.iter_mut() {
+        let bonus = player.score_engine.update(time, asteroids.is_empty());
+        add_score(player, bonus);
+    }
+
+    all_out(players)
+}
+
+/// Boucle principale du mode coopératif (deux joueurs sur un même clavier).
+/// La partie se termine lorsque les deux joueurs sont éliminés, ou lorsque le terrain est nettoyé.
+///
+/// # Arguments
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseau du premier joueur).
+/// - `player_two_skin` Chemin du skin du vaisseau du second joueur.
+/// - `start_time` Temps de démarrage du jeu.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les Binds des deux joueurs.
+///
+pub async fn play_coop(
+    skins: [&str; 4],
+    player_two_skin: &str,
+    start_time: f64,
+    sound: &mut Sound,
+    bindings: &mut [Bind; PLAYER_COUNT],
+) {
+    let asteroid_texture = load_texture(skins[0]).await.unwrap();
+    asteroid_texture.set_filter(FilterMode::Linear);
+    let background_texture = load_texture(skins[2]).await.unwrap();
+    background_texture.set_filter(FilterMode::Nearest);
+    let player_one_texture = load_texture(skins[3]).await.unwrap();
+    player_one_texture.set_filter(FilterMode::Linear);
+    let player_two_texture = load_texture(player_two_skin).await.unwrap();
+    player_two_texture.set_filter(FilterMode::Linear);
+
+    let mut asteroids: Vec<Asteroid> = Vec::new();
+    let mut last_add_asteroid: f64 = 0.0;
+    let mut total_paused_time: f64 = 0.0;
+    let mut missiles: Vec<Missile> = Vec::new();
+    let mut last_shoot = [0.0; PLAYER_COUNT];
+    let settings = Settings::load();
+    let mut players = [
+        settings.new_spaceship(&settings.ship_definition()),
+        settings.new_spaceship(&ShipDefinition::default()),
+    ];
+    for (i, player) in players.iter_mut().enumerate() {
+        let offset = if i == 0 { -100.0 } else { 100.0 };
+        player.set_position(player.get_position() + vec2(offset, 0.0));
+    }
+
+    loop {
+        let time = get_time() - start_time - total_paused_time;
+        // Prise en compte des modifications du fichier d'équilibrage
+        tuning::reload_if_changed();
+
+        total_paused_time += pause(&background_texture, &asteroid_texture, sound, bindings).await;
+
+        if bindings[0].is_down(Action::Quit) {
+            break;
+        }
+
+        let lost = update_coop(
+            &mut asteroids,
+            &mut last_add_asteroid,
+            &mut players,
+            time,
+            &mut last_shoot,
+            &mut missiles,
+            sound,
+            bindings,
+            settings.difficulty,
+        )
+        .await;
+        let won = !lost && asteroids.is_empty() && time > 10.0;
+
+        if lost || won {
+            for player in players.iter_mut() {
+                finalize_score(player);
+            }
+            if coop_game_over(&players, sound, won).await {
+                break;
+            } else {
+                std::process::exit(0);
+            }
+        }
+
+        draw_coop(
+            &asteroid_texture,
+            &background_texture,
+            [&player_one_texture, &player_two_texture],
+            &asteroids,
+            &players,
+            time,
+            &missiles,
+        );
+        draw_tuning_errors();
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Épuise le bouclier d'un joueur, un impact à la fois (hors période de grâce).
+    ///
+    /// # Returns
+    /// - `u32` Le nombre d'impacts encaissés.
+    ///
+    fn knock_out(player: &mut Spaceship, sound: &Sound) -> u32 {
+        let mut hits = 0;
+        loop {
+            hits += 1;
+            if player.check_shield(1.0, hits as f64 * 100.0, sound) {
+                return hits;
+            }
+        }
+    }
+
+    #[test]
+    fn test_each_player_counts_down_their_own_shield() {
+        let sound = Sound::new();
+        let mut players = [
+            Spaceship::new(&ShipDefinition::default()),
+            Spaceship::new(&ShipDefinition::default()),
+        ];
+        let shield = players[1].shield;
+
+        assert_eq!(knock_out(&mut players[0], &sound), shield);
+        assert!(!is_alive(&players[0]));
+        assert_eq!(players[1].shield, shield);
+        assert!(is_alive(&players[1]));
+    }
+
+    #[test]
+    fn test_game_over_only_when_both_players_are_down() {
+        let sound = Sound::new();
+        let mut players = [
+            Spaceship::new(&ShipDefinition::default()),
+            Spaceship::new(&ShipDefinition::default()),
+        ];
+        assert!(!all_out(&players));
+
+        knock_out(&mut players[1], &sound);
+        assert!(!all_out(&players));
+
+        knock_out(&mut players[0], &sound);
+        assert!(all_out(&players));
+    }
+}
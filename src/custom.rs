@@ -0,0 +1,497 @@
+//! # Custom Module
+//!
+//! Ce module gère les parties personnalisées : avant de lancer la partie, le joueur règle des
+//! mutateurs (bouclier de départ, vitesse et fréquence d'apparition des astéroïdes, trous noirs,
+//! missiles qui traversent les bords, vitesse des missiles, durée et condition de victoire).
+//!
+//! La dernière configuration utilisée est sauvegardée dans `saves/custom.txt` ; les configurations
+//! nommées (préréglages) sont sauvegardées dans `saves/presets.txt`, au format `nom.clé=valeur`.
+use crate::asteroid::Asteroid;
+use crate::difficulty::Difficulty;
+use crate::rules::ModeRules;
+use crate::save::{get_or_default, read_key_values, write_key_values};
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, enter_name};
+use macroquad::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Condition de victoire d'une partie personnalisée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinCondition {
+    /// Nettoyer le terrain une fois les apparitions terminées.
+    ClearField,
+    /// Survivre jusqu'à la fin du temps imparti.
+    Survive,
+    /// Atteindre un score (avant la fin du temps imparti, s'il y en a un).
+    ReachScore,
+}
+
+impl WinCondition {
+    /// Toutes les conditions de victoire.
+    pub const ALL: [WinCondition; 3] = [
+        WinCondition::ClearField,
+        WinCondition::Survive,
+        WinCondition::ReachScore,
+    ];
+
+    /// Nom de la condition (pour l'affichage et la sauvegarde).
+    pub fn name(self) -> &'static str {
+        match self {
+            WinCondition::ClearField => "Clear field",
+            WinCondition::Survive => "Survive",
+            WinCondition::ReachScore => "Reach score",
+        }
+    }
+
+    /// Condition correspondant à un nom (voir `name`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|condition| condition.name() == name)
+    }
+}
+
+/// Configuration d'une partie personnalisée.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomConfig {
+    /// Variante de jeu ('classic' ou 'modern').
+    pub flavor: &'static str,
+    /// Bouclier du vaisseau au départ.
+    pub starting_shield: u32,
+    /// Facteur appliqué à la vitesse des astéroïdes.
+    pub speed_factor: f64,
+    /// Facteur appliqué au délai entre deux apparitions d'astéroïdes.
+    pub spawn_factor: f64,
+    /// Les collisions entre astéroïdes forment des trous noirs.
+    pub black_holes: bool,
+    /// Les missiles traversent les bords de l'écran.
+    pub friendly_wrap: bool,
+    /// Vitesse des missiles.
+    pub missile_speed: f32,
+    /// Durée de la partie, en minutes (0 : illimitée).
+    pub duration: u32,
+    /// Condition de victoire.
+    pub win_condition: WinCondition,
+    /// Score à atteindre (condition `ReachScore`).
+    pub score_target: u32,
+}
+
+impl Default for CustomConfig {
+    /// Configuration équivalente au mode moderne.
+    fn default() -> Self {
+        Self {
+            flavor: "modern",
+            starting_shield: 3,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: true,
+            friendly_wrap: false,
+            missile_speed: 5.0,
+            duration: 0,
+            win_condition: WinCondition::ClearField,
+            score_target: 5000,
+        }
+    }
+}
+
+impl CustomConfig {
+    /// Nom du fichier de la dernière configuration utilisée.
+    const FILE_NAME: &'static str = "custom.txt";
+    /// Nom du fichier des préréglages.
+    const PRESETS_FILE_NAME: &'static str = "presets.txt";
+    /// Nombre de réglages modifiables (voir `adjust`).
+    pub const SETTING_COUNT: usize = 10;
+    /// Durée des apparitions (en secondes) pour une partie sans limite de temps à nettoyer.
+    const DEFAULT_SPAWN_TIME: f64 = 45.0;
+
+    /// Charge la dernière configuration utilisée.
+    pub fn load() -> Self {
+        Self::from_key_values(&read_key_values(Self::FILE_NAME))
+    }
+
+    /// Sauvegarde la configuration comme dernière configuration utilisée.
+    pub fn save(&self) {
+        write_key_values(Self::FILE_NAME, &self.to_key_values());
+    }
+
+    /// Charge les préréglages, triés par nom.
+    pub fn load_presets() -> Vec<(String, Self)> {
+        let mut presets: BTreeMap<String, HashMap<String, String>> = BTreeMap::new();
+        for (key, value) in read_key_values(Self::PRESETS_FILE_NAME) {
+            if let Some((name, setting)) = key.split_once('.') {
+                presets
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(setting.to_string(), value);
+            }
+        }
+        presets
+            .into_iter()
+            .map(|(name, values)| (name, Self::from_key_values(&values)))
+            .collect()
+    }
+
+    /// Sauvegarde la configuration sous un nom (remplace le préréglage existant du même nom).
+    ///
+    /// # Arguments
+    /// - `name` Le nom du préréglage.
+    ///
+    pub fn save_preset(&self, name: &str) {
+        let mut presets = Self::load_presets();
+        presets.retain(|(preset, _)| preset != name);
+        presets.push((name.to_string(), self.clone()));
+        presets.sort_by(|a, b| a.0.cmp(&b.0));
+        let entries: Vec<(String, String)> = presets
+            .iter()
+            .flat_map(|(preset, config)| {
+                config
+                    .to_key_values()
+                    .into_iter()
+                    .map(move |(key, value)| (format!("{}.{}", preset, key), value))
+            })
+            .collect();
+        write_key_values(Self::PRESETS_FILE_NAME, &entries);
+    }
+
+    /// Lit une configuration depuis des couples `clé=valeur` (valeurs par défaut si absentes).
+    ///
+    /// # Arguments
+    /// - `values` Les couples clé/valeur lus.
+    ///
+    fn from_key_values(values: &HashMap<String, String>) -> Self {
+        let default = Self::default();
+        let or_default = |key: &str, fallback: f64| {
+            values
+                .get(key)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(fallback)
+        };
+        Self {
+            flavor: if values
+                .get("flavor")
+                .is_some_and(|flavor| flavor == "classic")
+            {
+                "classic"
+            } else {
+                "modern"
+            },
+            starting_shield: values
+                .get("starting_shield")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.starting_shield)
+                .clamp(1, 9),
+            speed_factor: or_default("speed_factor", default.speed_factor).clamp(0.25, 3.0),
+            spawn_factor: or_default("spawn_factor", default.spawn_factor).clamp(0.25, 3.0),
+            black_holes: values
+                .get("black_holes")
+                .map_or(default.black_holes, |value| value == "true"),
+            friendly_wrap: values
+                .get("friendly_wrap")
+                .is_some_and(|value| value == "true"),
+            missile_speed: or_default("missile_speed", default.missile_speed as f64)
+                .clamp(2.0, 15.0) as f32,
+            duration: get_or_default::<u32>(values, "duration").min(10),
+            win_condition: values
+                .get("win_condition")
+                .and_then(|name| WinCondition::from_name(name))
+                .unwrap_or(default.win_condition),
+            score_target: values
+                .get("score_target")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.score_target),
+        }
+    }
+
+    /// Convertit la configuration en couples `clé=valeur`.
+    fn to_key_values(&self) -> Vec<(String, String)> {
+        [
+            ("flavor", self.flavor.to_string()),
+            ("starting_shield", self.starting_shield.to_string()),
+            ("speed_factor", self.speed_factor.to_string()),
+            ("spawn_factor", self.spawn_factor.to_string()),
+            ("black_holes", self.black_holes.to_string()),
+            ("friendly_wrap", self.friendly_wrap.to_string()),
+            ("missile_speed", self.missile_speed.to_string()),
+            ("duration", self.duration.to_string()),
+            ("win_condition", self.win_condition.name().to_string()),
+            ("score_target", self.score_target.to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+
+    /// Modifie un réglage (flèches gauche et droite de l'écran de configuration).
+    ///
+    /// # Arguments
+    /// - `setting` L'indice du réglage (dans l'ordre de `describe`).
+    /// - `step` Le sens de la modification (`-1` ou `1`).
+    ///
+    pub fn adjust(&mut self, setting: usize, step: i32) {
+        match setting {
+            0 => {
+                self.flavor = if self.flavor == "modern" {
+                    "classic"
+                } else {
+                    "modern"
+                }
+            }
+            1 => self.starting_shield = (self.starting_shield as i32 + step).clamp(1, 9) as u32,
+            2 => self.speed_factor = (self.speed_factor + 0.25 * step as f64).clamp(0.25, 3.0),
+            3 => self.spawn_factor = (self.spawn_factor + 0.25 * step as f64).clamp(0.25, 3.0),
+            4 => self.black_holes = !self.black_holes,
+            5 => self.friendly_wrap = !self.friendly_wrap,
+            6 => self.missile_speed = (self.missile_speed + step as f32).clamp(2.0, 15.0),
+            7 => self.duration = (self.duration as i32 + step).clamp(0, 10) as u32,
+            8 => {
+                let count = WinCondition::ALL.len() as i32;
+                let index = WinCondition::ALL
+                    .iter()
+                    .position(|condition| *condition == self.win_condition)
+                    .unwrap_or(0) as i32;
+                self.win_condition = WinCondition::ALL[(index + step).rem_euclid(count) as usize];
+            }
+            9 => {
+                self.score_target =
+                    (self.score_target as i32 + 1000 * step).clamp(1000, 100_000) as u32
+            }
+            _ => {}
+        }
+    }
+
+    /// Lignes décrivant les réglages (dans l'ordre des indices de `adjust`).
+    pub fn describe(&self) -> Vec<String> {
+        let on_off = |value: bool| if value { "On" } else { "Off" };
+        vec![
+            format!("Base mode : {}", self.flavor),
+            format!("Starting shield : {}", self.starting_shield),
+            format!("Asteroid speed : x{:.2}", self.speed_factor),
+            format!("Spawn interval : x{:.2}", self.spawn_factor),
+            format!("Black holes : {}", on_off(self.black_holes)),
+            format!("Friendly wrap : {}", on_off(self.friendly_wrap)),
+            format!("Missile speed : {:.0}", self.missile_speed),
+            match self.duration {
+                0 => "Duration : Unlimited".to_string(),
+                minutes => format!("Duration : {} min", minutes),
+            },
+            format!("Win condition : {}", self.win_condition.name()),
+            format!("Score target : {}", self.score_target),
+        ]
+    }
+
+    /// Règles de la partie correspondant à la configuration.
+    pub fn rules(&self) -> ModeRules {
+        let duration = (self.duration > 0).then_some(self.duration as f64 * 60.0);
+        let (spawn_until, clear_after, score_target) = match self.win_condition {
+            WinCondition::ClearField => {
+                let spawn_time = duration.unwrap_or(Self::DEFAULT_SPAWN_TIME);
+                (Some(spawn_time), Some(spawn_time), None)
+            }
+            WinCondition::Survive => (None, None, None),
+            WinCondition::ReachScore => (None, None, Some(self.score_target)),
+        };
+        ModeRules {
+            name: "custom",
+            title: "Custom",
+            flavor: self.flavor,
+            spawn_until,
+            clear_after,
+            time_limit: match self.win_condition {
+                WinCondition::ClearField => None,
+                _ => duration,
+            },
+            endless_ramp: false,
+            can_die: true,
+            speed_factor: self.speed_factor,
+            spawn_factor: self.spawn_factor,
+            black_holes: self.black_holes,
+            missile_wrap: self.friendly_wrap,
+            missile_speed: self.missile_speed,
+            score_target,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: false,
+            bosses: false,
+        }
+    }
+}
+
+/// Écran de configuration d'une partie personnalisée.
+/// Flèches haut/bas : choisir un réglage ; flèches gauche/droite : le modifier.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `true` si le joueur lance la partie (la configuration est sauvegardée).
+/// - `false` si le joueur revient en arrière.
+///
+pub async fn custom_game_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> bool {
+    let mut config = CustomConfig::load();
+    let mut presets = CustomConfig::load_presets();
+    let mut preset_index = 0;
+    let mut selected_index = 0;
+    let load_index = CustomConfig::SETTING_COUNT;
+    let save_index = load_index + 1;
+    let start_index = load_index + 2;
+    let back_index = load_index + 3;
+    sound.play_sound_effect("select_menu");
+
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Custom Game", None, 50, 1.0);
+        draw_text(
+            "Custom Game",
+            (screen_width() - dimension.width) / 2.0,
+            150.0,
+            50.0,
+            WHITE,
+        );
+
+        let mut lines = config.describe();
+        lines.push(match presets.get(preset_index) {
+            Some((name, _)) => format!("Load preset : < {} >", name),
+            None => "Load preset : (none)".to_string(),
+        });
+        lines.push("Save preset".to_string());
+        lines.push("Start".to_string());
+        lines.push("Back".to_string());
+        for (i, line) in lines.iter().enumerate() {
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            draw_text(
+                line,
+                screen_width() / 2.0 - 250.0,
+                230.0 + i as f32 * 45.0,
+                36.0,
+                color,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % lines.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                lines.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        for (key, step) in [(KeyCode::Left, -1), (KeyCode::Right, 1)] {
+            if !is_key_pressed(key) {
+                continue;
+            }
+            if selected_index < CustomConfig::SETTING_COUNT {
+                config.adjust(selected_index, step);
+                sound.play_sound_effect("select_menu");
+            } else if selected_index == load_index && !presets.is_empty() {
+                preset_index =
+                    (preset_index as i32 + step).rem_euclid(presets.len() as i32) as usize;
+                sound.play_sound_effect("select_menu");
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return false;
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            if selected_index == load_index {
+                if let Some((_, preset)) = presets.get(preset_index) {
+                    config = preset.clone();
+                    sound.play_sound_effect("select_menu");
+                }
+            } else if selected_index == save_index {
+                if let Some(name) = enter_name(
+                    "Preset name",
+                    background_texture,
+                    asteroid_texture,
+                    asteroids,
+                    sound,
+                )
+                .await
+                {
+                    config.save_preset(&name);
+                    presets = CustomConfig::load_presets();
+                    preset_index = presets
+                        .iter()
+                        .position(|(preset, _)| *preset == name)
+                        .unwrap_or(0);
+                }
+            } else if selected_index == start_index {
+                config.save();
+                return true;
+            } else if selected_index == back_index {
+                sound.play_sound_effect("select_menu");
+                return false;
+            }
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::parse_key_values;
+
+    #[test]
+    fn test_config_round_trip_and_adjust() {
+        let mut config = CustomConfig::default();
+        config.adjust(0, 1);
+        config.adjust(2, 1);
+        config.adjust(5, 1);
+        config.adjust(8, -1);
+        for _ in 0..20 {
+            config.adjust(1, 1);
+        }
+        assert_eq!(config.flavor, "classic");
+        assert_eq!(config.speed_factor, 1.25);
+        assert!(config.friendly_wrap);
+        assert_eq!(config.win_condition, WinCondition::ReachScore);
+        assert_eq!(config.starting_shield, 9);
+
+        let content: String = config
+            .to_key_values()
+            .iter()
+            .map(|(key, value)| format!("{}={}\n", key, value))
+            .collect();
+        assert_eq!(
+            CustomConfig::from_key_values(&parse_key_values(&content)),
+            config
+        );
+    }
+
+    #[test]
+    fn test_config_rules() {
+        let mut config = CustomConfig {
+            duration: 2,
+            win_condition: WinCondition::Survive,
+            black_holes: false,
+            ..CustomConfig::default()
+        };
+        let rules = config.rules();
+        assert!(!rules.black_holes);
+        assert!(rules.is_won(120.0, false, 0));
+        assert!(rules.spawns(500.0));
+
+        config.win_condition = WinCondition::ReachScore;
+        let rules = config.rules();
+        assert!(!rules.is_won(120.0, true, 4000));
+        assert!(rules.is_time_up(120.0));
+        assert!(rules.is_won(30.0, false, 5000));
+    }
+}
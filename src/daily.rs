@@ -0,0 +1,379 @@
+//! # Daily Module
+//!
+//! Ce module gère le défi quotidien : la graine du générateur aléatoire et les modificateurs
+//! de la partie sont dérivés de la date du jour (UTC), tous les joueurs jouent donc la même partie.
+//!
+//! Seule la première tentative du jour compte : son score est enregistré dans `saves/daily.txt`
+//! (au format `AAAA-MM-JJ=score`), ce qui permet de calculer la série de jours consécutifs joués.
+use crate::asteroid::Asteroid;
+use crate::rules::ModeRules;
+use crate::save::{read_key_values, write_key_values};
+use crate::shipclass::{ShipDefinition, Weapon};
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, handle_enter_press};
+use ::rand::rngs::StdRng;
+use ::rand::seq::SliceRandom;
+use ::rand::SeedableRng;
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Modificateur de partie du défi quotidien.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// Les astéroïdes sont plus rapides.
+    FastAsteroids,
+    /// Les astéroïdes apparaissent plus souvent.
+    Swarm,
+    /// Le vaisseau n'a qu'un seul point de bouclier.
+    GlassCannon,
+    /// Le vaisseau tire deux fois plus vite.
+    RapidFire,
+    /// Le vaisseau tire en éventail.
+    SpreadShot,
+    /// Le vaisseau glisse (frottement réduit).
+    Slippery,
+}
+
+impl Modifier {
+    /// Tous les modificateurs.
+    pub const ALL: [Modifier; 6] = [
+        Modifier::FastAsteroids,
+        Modifier::Swarm,
+        Modifier::GlassCannon,
+        Modifier::RapidFire,
+        Modifier::SpreadShot,
+        Modifier::Slippery,
+    ];
+
+    /// Nom affiché du modificateur.
+    pub fn name(self) -> &'static str {
+        match self {
+            Modifier::FastAsteroids => "Fast asteroids",
+            Modifier::Swarm => "Swarm",
+            Modifier::GlassCannon => "Glass cannon",
+            Modifier::RapidFire => "Rapid fire",
+            Modifier::SpreadShot => "Spread shot",
+            Modifier::Slippery => "Slippery",
+        }
+    }
+
+    /// Applique le modificateur aux règles de la partie et au vaisseau.
+    ///
+    /// # Arguments
+    /// - `rules` Les règles de la partie.
+    /// - `definition` Les caractéristiques du vaisseau.
+    ///
+    pub fn apply(self, rules: &mut ModeRules, definition: &mut ShipDefinition) {
+        match self {
+            Modifier::FastAsteroids => rules.speed_factor *= 1.4,
+            Modifier::Swarm => rules.spawn_factor *= 0.6,
+            Modifier::GlassCannon => definition.shield = 1,
+            Modifier::RapidFire => definition.fire_delay *= 0.5,
+            Modifier::SpreadShot => definition.weapon = Weapon::Spread,
+            Modifier::Slippery => definition.drag *= 0.3,
+        }
+    }
+}
+
+/// Défi d'un jour donné.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyChallenge {
+    /// Numéro du jour (jours écoulés depuis le 1er janvier 1970, UTC).
+    pub day: i64,
+    /// Graine du générateur aléatoire de la partie.
+    pub seed: u64,
+    /// Modificateurs de la partie.
+    pub modifiers: Vec<Modifier>,
+}
+
+impl DailyChallenge {
+    /// Nombre de modificateurs tirés chaque jour.
+    const MODIFIER_COUNT: usize = 2;
+
+    /// Défi d'un jour donné.
+    ///
+    /// # Arguments
+    /// - `day` Le numéro du jour (jours écoulés depuis le 1er janvier 1970).
+    ///
+    pub fn for_day(day: i64) -> Self {
+        let seed = mix_seed(day as u64);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let modifiers = Modifier::ALL
+            .choose_multiple(&mut rng, Self::MODIFIER_COUNT)
+            .copied()
+            .collect();
+        Self {
+            day,
+            seed,
+            modifiers,
+        }
+    }
+
+    /// Défi du jour (date UTC).
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Self::for_day((seconds / 86_400) as i64)
+    }
+
+    /// Date du défi, au format `AAAA-MM-JJ`.
+    pub fn date(&self) -> String {
+        format_date(self.day)
+    }
+
+    /// Applique les modificateurs du jour aux règles de la partie et au vaisseau.
+    ///
+    /// # Arguments
+    /// - `rules` Les règles de la partie.
+    /// - `definition` Les caractéristiques du vaisseau.
+    ///
+    pub fn apply(&self, rules: &mut ModeRules, definition: &mut ShipDefinition) {
+        for modifier in &self.modifiers {
+            modifier.apply(rules, definition);
+        }
+    }
+
+    /// Vrai si le défi a déjà été joué : les tentatives suivantes ne sont que de l'entraînement.
+    pub fn is_played(&self) -> bool {
+        DailyLog::load().result(self.day).is_some()
+    }
+
+    /// Enregistre le score d'une tentative dans le journal (seule la première tentative compte).
+    ///
+    /// # Arguments
+    /// - `score` Le score de la partie.
+    ///
+    pub fn record_attempt(&self, score: u32) {
+        let mut log = DailyLog::load();
+        if log.record(self.day, score) {
+            log.save();
+        }
+    }
+}
+
+/// Mélange les bits d'un nombre (SplitMix64), pour obtenir des graines bien distinctes d'un jour à l'autre.
+///
+/// # Arguments
+/// - `value` Le nombre à mélanger.
+///
+fn mix_seed(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Convertit un numéro de jour en date `AAAA-MM-JJ` (calendrier grégorien).
+///
+/// # Arguments
+/// - `day` Le numéro du jour (jours écoulés depuis le 1er janvier 1970).
+///
+fn format_date(day: i64) -> String {
+    let z = day + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+/// Journal des défis quotidiens joués (score de la première tentative de chaque jour).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DailyLog {
+    /// Score de chaque jour joué (date `AAAA-MM-JJ` → score).
+    results: HashMap<String, u32>,
+}
+
+impl DailyLog {
+    /// Nom du fichier de sauvegarde du journal.
+    const FILE_NAME: &'static str = "daily.txt";
+
+    /// Charge le journal depuis le disque.
+    pub fn load() -> Self {
+        Self {
+            results: read_key_values(Self::FILE_NAME)
+                .into_iter()
+                .filter_map(|(date, score)| Some((date, score.parse().ok()?)))
+                .collect(),
+        }
+    }
+
+    /// Sauvegarde le journal sur le disque (trié par date).
+    pub fn save(&self) {
+        let mut entries: Vec<(String, String)> = self
+            .results
+            .iter()
+            .map(|(date, score)| (date.clone(), score.to_string()))
+            .collect();
+        entries.sort();
+        write_key_values(Self::FILE_NAME, &entries);
+    }
+
+    /// Score de la première tentative d'un jour, s'il a été joué.
+    ///
+    /// # Arguments
+    /// - `day` Le numéro du jour.
+    ///
+    pub fn result(&self, day: i64) -> Option<u32> {
+        self.results.get(&format_date(day)).copied()
+    }
+
+    /// Enregistre le score d'un jour, s'il s'agit de la première tentative.
+    ///
+    /// # Arguments
+    /// - `day` Le numéro du jour.
+    /// - `score` Le score de la partie.
+    ///
+    /// # Returns
+    /// - `true` si le score a été enregistré.
+    /// - `false` si le jour avait déjà été joué.
+    ///
+    pub fn record(&mut self, day: i64, score: u32) -> bool {
+        if self.result(day).is_some() {
+            return false;
+        }
+        self.results.insert(format_date(day), score);
+        true
+    }
+
+    /// Nombre de jours consécutifs joués jusqu'à aujourd'hui
+    /// (la série n'est pas interrompue si le défi du jour n'a pas encore été joué).
+    ///
+    /// # Arguments
+    /// - `today` Le numéro du jour actuel.
+    ///
+    pub fn streak(&self, today: i64) -> u32 {
+        let mut day = if self.result(today).is_some() {
+            today
+        } else {
+            today - 1
+        };
+        let mut streak = 0;
+        while self.result(day).is_some() {
+            streak += 1;
+            day -= 1;
+        }
+        streak
+    }
+}
+
+/// Écran du défi quotidien : date, modificateurs, résultat du jour et série en cours.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `true` si le joueur lance le défi.
+/// - `false` si le joueur revient en arrière.
+///
+pub async fn daily_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> bool {
+    let challenge = DailyChallenge::today();
+    let log = DailyLog::load();
+    let mut lines = vec![format!("Date : {}", challenge.date())];
+    lines.extend(
+        challenge
+            .modifiers
+            .iter()
+            .map(|modifier| format!("Modifier : {}", modifier.name())),
+    );
+    lines.push(match log.result(challenge.day) {
+        Some(score) => format!("Today's score : {} (practice only)", score),
+        None => "Today's score : not played yet".to_string(),
+    });
+    lines.push(format!("Streak : {} day(s)", log.streak(challenge.day)));
+    sound.play_sound_effect("select_menu");
+
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Daily Challenge", None, 50, 1.0);
+        draw_text(
+            "Daily Challenge",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                line,
+                screen_width() / 2.0 - 250.0,
+                350.0 + i as f32 * 50.0,
+                40.0,
+                WHITE,
+            );
+        }
+        let hint = "Press 'Enter' to play or 'Backspace' to go back";
+        let dimension = measure_text(hint, None, 30, 1.0);
+        draw_text(
+            hint,
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 200.0,
+            30.0,
+            YELLOW,
+        );
+
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return false;
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            return true;
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_challenge_is_derived_from_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(20_744), "2026-10-18");
+        assert_eq!(format_date(11_016), "2000-02-29");
+
+        let challenge = DailyChallenge::for_day(20_744);
+        assert_eq!(challenge, DailyChallenge::for_day(20_744));
+        assert_ne!(challenge.seed, DailyChallenge::for_day(20_745).seed);
+        assert_eq!(challenge.modifiers.len(), 2);
+        assert_ne!(challenge.modifiers[0], challenge.modifiers[1]);
+    }
+
+    #[test]
+    fn test_log_keeps_first_attempt_and_streak() {
+        let mut log = DailyLog::default();
+        assert!(log.record(100, 500));
+        assert!(!log.record(100, 900));
+        assert_eq!(log.result(100), Some(500));
+        assert!(log.record(99, 300));
+        assert!(log.record(97, 300));
+        assert_eq!(log.streak(100), 2);
+        assert_eq!(log.streak(101), 2);
+        assert_eq!(log.streak(102), 0);
+    }
+}
@@ -0,0 +1,132 @@
+//! # Difficulty Module
+//!
+//! Ce module définit les niveaux de difficulté (`Easy`, `Normal`, `Hard`, `Insane`).
+//! Chaque niveau paramètre les courbes du jeu : délai minimal entre deux apparitions d'astéroïdes
+//! et pente des courbes (`sigmoid_range_secs`), vitesse maximale des astéroïdes (`sigmoid_speed`),
+//! fin des apparitions et probabilité de formation d'un trou noir.
+//!
+//! `Normal` reprend les valeurs historiques du jeu.
+
+/// Niveau de difficulté.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Facile.
+    Easy,
+    /// Normal (valeurs historiques).
+    #[default]
+    Normal,
+    /// Difficile.
+    Hard,
+    /// Démentiel.
+    Insane,
+}
+
+impl Difficulty {
+    /// Tous les niveaux de difficulté, du plus facile au plus difficile.
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    /// Nom du niveau (pour l'affichage et la sauvegarde).
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Insane => "Insane",
+        }
+    }
+
+    /// Niveau correspondant à un nom (voir `name`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name() == name)
+    }
+
+    /// Niveau suivant (revient au premier après le dernier).
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Délai minimal (en secondes) entre deux apparitions d'astéroïdes.
+    pub fn min_spawn_interval(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 0.4,
+            Difficulty::Hard => 0.3,
+            Difficulty::Insane => 0.2,
+        }
+    }
+
+    /// Pente des courbes d'apparition et de vitesse des astéroïdes.
+    pub fn ramp_rate(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.04,
+            Difficulty::Normal => 0.05,
+            Difficulty::Hard => 0.06,
+            Difficulty::Insane => 0.08,
+        }
+    }
+
+    /// Gain de vitesse maximal des astéroïdes.
+    ///
+    /// # Arguments
+    /// - `mode` Le mode de jeu ('classic' ou 'modern').
+    ///
+    pub fn speed_gain(self, mode: &str) -> f64 {
+        let (classic, modern) = match self {
+            Difficulty::Easy => (0.6, 1.5),
+            Difficulty::Normal => (1.0, 2.5),
+            Difficulty::Hard => (1.4, 3.2),
+            Difficulty::Insane => (2.0, 4.0),
+        };
+        if mode == "classic" {
+            classic
+        } else {
+            modern
+        }
+    }
+
+    /// Facteur appliqué à la durée des apparitions d'astéroïdes (45 secondes en `Normal`).
+    pub fn spawn_time_factor(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.8,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 4.0 / 3.0,
+            Difficulty::Insane => 5.0 / 3.0,
+        }
+    }
+
+    /// Probabilité (une chance sur `n`) que deux astéroïdes qui se percutent forment un trou noir.
+    pub fn blackhole_chance(self) -> u32 {
+        match self {
+            Difficulty::Easy => 25,
+            Difficulty::Normal => 15,
+            Difficulty::Hard => 10,
+            Difficulty::Insane => 6,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_ordering() {
+        for pair in Difficulty::ALL.windows(2) {
+            let (easier, harder) = (pair[0], pair[1]);
+            assert!(easier.min_spawn_interval() > harder.min_spawn_interval());
+            assert!(easier.speed_gain("modern") < harder.speed_gain("modern"));
+            assert!(easier.spawn_time_factor() < harder.spawn_time_factor());
+            assert!(easier.blackhole_chance() > harder.blackhole_chance());
+        }
+        assert_eq!(Difficulty::Insane.next(), Difficulty::Easy);
+        assert_eq!(Difficulty::from_name("Hard"), Some(Difficulty::Hard));
+    }
+}
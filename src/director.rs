@@ -0,0 +1,170 @@
+//! # Director Module
+//!
+//! Ce module gère le directeur adaptatif (option des réglages) : pendant la partie, il observe
+//! la précision du joueur, les coups reçus par le bouclier et la vitesse à laquelle le terrain est nettoyé.
+//!
+//! À intervalles réguliers, il ajuste un niveau d'intensité (borné) qui modifie les règles de la partie :
+//! fréquence d'apparition, vitesse des astéroïdes et répartition de leurs tailles.
+//! Chaque décision est enregistrée dans `saves/director.log` pour le réglage du jeu.
+use crate::rules::ModeRules;
+use crate::save::{save_path, SAVE_DIR};
+use crate::stats::SessionStats;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+/// Directeur adaptatif d'une partie.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Director {
+    /// Niveau d'intensité, entre -1 (plus facile) et 1 (plus difficile).
+    level: f64,
+    /// Temps de la dernière évaluation, en secondes.
+    last_review: f64,
+    /// Statistiques de la partie lors de la dernière évaluation.
+    last_stats: SessionStats,
+    /// Décisions prises depuis le début de la partie.
+    decisions: Vec<String>,
+}
+
+impl Director {
+    /// Nom du fichier de journal des décisions.
+    const LOG_FILE_NAME: &'static str = "director.log";
+    /// Délai (en secondes) entre deux évaluations.
+    const REVIEW_PERIOD: f64 = 10.0;
+    /// Variation du niveau d'intensité à chaque évaluation.
+    const STEP: f64 = 0.25;
+    /// Variation maximale de la vitesse et du délai d'apparition des astéroïdes (au niveau ±1).
+    const MAX_ADJUSTMENT: f64 = 0.35;
+    /// Décalage maximal de la répartition des tailles des astéroïdes (au niveau ±1).
+    const MAX_SIZE_BIAS: f32 = 0.6;
+    /// Précision au-delà de laquelle le joueur est jugé à l'aise.
+    const HIGH_ACCURACY: f32 = 0.6;
+    /// Précision en deçà de laquelle le joueur est jugé en difficulté.
+    const LOW_ACCURACY: f32 = 0.3;
+    /// Nombre d'astéroïdes sur le terrain en deçà duquel le terrain est jugé nettoyé rapidement.
+    const CLEAR_FIELD_SIZE: usize = 4;
+
+    /// Créer un directeur neutre.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Évalue la performance du joueur depuis la dernière évaluation et ajuste le niveau d'intensité.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `stats` Les statistiques de la partie en cours.
+    /// - `field_size` Le nombre d'astéroïdes sur le terrain.
+    ///
+    pub fn review(&mut self, time: f64, stats: &SessionStats, field_size: usize) {
+        if time - self.last_review < Self::REVIEW_PERIOD {
+            return;
+        }
+        let shots = stats.shots_fired - self.last_stats.shots_fired;
+        let hits = stats.hits - self.last_stats.hits;
+        let shield_hits = stats.shield_hits - self.last_stats.shield_hits;
+        let destroyed = stats.total_destroyed() - self.last_stats.total_destroyed();
+        let accuracy = if shots == 0 {
+            0.0
+        } else {
+            hits as f32 / shots as f32
+        };
+
+        let mut verdict = 0;
+        if shots >= 5 && accuracy >= Self::HIGH_ACCURACY {
+            verdict += 1;
+        }
+        if shots >= 5 && accuracy < Self::LOW_ACCURACY {
+            verdict -= 1;
+        }
+        if destroyed > 0 && field_size <= Self::CLEAR_FIELD_SIZE {
+            verdict += 1;
+        }
+        verdict -= shield_hits.min(2) as i32;
+
+        let previous = self.level;
+        self.level = (self.level + Self::STEP * verdict.signum() as f64).clamp(-1.0, 1.0);
+        self.decisions.push(format!(
+            "t={:.0}s accuracy={:.2} shots={} shield_hits={} destroyed={} field={} level={:+.2}->{:+.2}",
+            time, accuracy, shots, shield_hits, destroyed, field_size, previous, self.level
+        ));
+        self.last_review = time;
+        self.last_stats = stats.clone();
+    }
+
+    /// Règles de la partie ajustées selon le niveau d'intensité.
+    ///
+    /// # Arguments
+    /// - `rules` Les règles de base de la partie.
+    ///
+    pub fn adjust(&self, rules: &ModeRules) -> ModeRules {
+        ModeRules {
+            speed_factor: rules.speed_factor * (1.0 + Self::MAX_ADJUSTMENT * self.level),
+            spawn_factor: rules.spawn_factor * (1.0 - Self::MAX_ADJUSTMENT * self.level),
+            size_bias: (rules.size_bias + Self::MAX_SIZE_BIAS * self.level as f32).clamp(-1.0, 1.0),
+            ..*rules
+        }
+    }
+
+    /// Ajoute les décisions de la partie au journal du directeur.
+    ///
+    /// # Arguments
+    /// - `mode` L'identifiant du mode de jeu.
+    ///
+    pub fn save_log(&self, mode: &str) {
+        if self.decisions.is_empty() {
+            return;
+        }
+        let content: String = self
+            .decisions
+            .iter()
+            .map(|decision| format!("[{}] {}\n", mode, decision))
+            .collect();
+        let result = fs::create_dir_all(SAVE_DIR).and_then(|_| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(save_path(Self::LOG_FILE_NAME))?
+                .write_all(content.as_bytes())
+        });
+        if let Err(e) = result {
+            eprintln!("Erreur lors de l'écriture du journal du directeur: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_director_reacts_to_performance() {
+        let mut director = Director::new();
+        let mut stats = SessionStats {
+            shots_fired: 10,
+            hits: 8,
+            destroyed: [3, 3, 2],
+            ..SessionStats::default()
+        };
+        director.review(5.0, &stats, 2);
+        assert_eq!(director.level, 0.0);
+        director.review(10.0, &stats, 2);
+        assert_eq!(director.level, 0.25);
+
+        stats.shots_fired += 10;
+        stats.hits += 1;
+        stats.shield_hits += 2;
+        director.review(20.0, &stats, 12);
+        assert_eq!(director.level, 0.0);
+        assert_eq!(director.decisions.len(), 2);
+
+        for i in 0..10 {
+            stats.shield_hits += 1;
+            director.review(30.0 + i as f64 * 10.0, &stats, 12);
+        }
+        assert_eq!(director.level, -1.0);
+        let rules = director.adjust(&ModeRules::for_mode("modern").unwrap());
+        assert!(rules.speed_factor < 1.0);
+        assert!(rules.spawn_factor > 1.0);
+        assert!(rules.size_bias < 0.0);
+    }
+}
@@ -0,0 +1,684 @@
+//! # Editor Module
+//!
+//! Ce module gère l'éditeur de niveaux (voir le module `level`).
+//! À la souris, il permet de placer, déplacer, redimensionner et orienter les astéroïdes
+//! et les trous noirs, de tracer la vitesse initiale des astéroïdes (flèches) et de choisir
+//! la position de départ du vaisseau. Les niveaux sont sauvegardés dans `assets/levels`
+//! et peuvent être testés directement depuis l'éditeur.
+//!
+//! Les apparitions sur les bords (`edge`) d'un niveau chargé ne sont pas affichées
+//! mais sont conservées lors de la sauvegarde.
+use super::{draw_background, play};
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::bind::Bind;
+use crate::blackhole::BlackHole;
+use crate::level::{level_menu, Level, LevelGoal, PlacedBlackHole, SpawnEvent, SpawnOrigin};
+use crate::sound::Sound;
+use crate::tuning::tuning;
+use crate::utils::enter_name;
+use macroquad::prelude::*;
+use std::path::PathBuf;
+
+/// Objet placé au clic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    /// Astéroïde.
+    Asteroid,
+    /// Trou noir.
+    BlackHole,
+    /// Position de départ du vaisseau.
+    Start,
+}
+
+impl Tool {
+    /// Nom de l'outil (pour l'affichage).
+    fn name(self) -> &'static str {
+        match self {
+            Tool::Asteroid => "Asteroid",
+            Tool::BlackHole => "Black hole",
+            Tool::Start => "Start",
+        }
+    }
+
+    /// Outil suivant.
+    fn next(self) -> Self {
+        match self {
+            Tool::Asteroid => Tool::BlackHole,
+            Tool::BlackHole => Tool::Start,
+            Tool::Start => Tool::Asteroid,
+        }
+    }
+}
+
+/// Objet sélectionné dans l'éditeur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Apparition d'astéroïde (indice dans `Level::events`).
+    Asteroid(usize),
+    /// Trou noir (indice dans `Level::blackholes`).
+    BlackHole(usize),
+    /// Position de départ du vaisseau.
+    Start,
+}
+
+/// État de l'éditeur de niveaux.
+pub struct LevelEditor {
+    /// Le niveau édité.
+    pub level: Level,
+    /// Le fichier du niveau (`None` tant que le niveau n'a pas été sauvegardé).
+    pub path: Option<PathBuf>,
+    /// L'objet placé au clic.
+    pub tool: Tool,
+    /// L'objet sélectionné.
+    pub selected: Option<Selection>,
+}
+
+impl LevelEditor {
+    /// Longueur (en pixels) de la flèche d'une vitesse de 1 pixel par frame.
+    const VELOCITY_SCALE: f32 = 40.0;
+    /// Variation de taille par cran de molette.
+    const SIZE_STEP: f32 = 5.0;
+    /// Taille minimale et maximale des objets.
+    const SIZE_RANGE: (f32, f32) = (10.0, 150.0);
+    /// Variation d'orientation (en radians) par cran de molette.
+    const ROTATION_STEP: f32 = 0.2;
+    /// Variation de l'instant d'apparition (en secondes) par pression de touche.
+    const TIME_STEP: f64 = 0.5;
+    /// Rayon de sélection de la position de départ du vaisseau.
+    const START_RADIUS: f32 = 25.0;
+    /// Hauteur de la barre de boutons.
+    const BAR_HEIGHT: f32 = 60.0;
+    /// Boutons de la barre (les trois premiers affichent leur valeur).
+    const BUTTONS: [&'static str; 7] = [
+        "Tool",
+        "Flavor",
+        "Goal",
+        "Save",
+        "Load",
+        "Test play",
+        "Back",
+    ];
+
+    /// Créer un éditeur.
+    ///
+    /// # Arguments
+    /// - `level` Le niveau à éditer.
+    /// - `path` Le fichier du niveau, s'il existe.
+    ///
+    pub fn new(level: Level, path: Option<PathBuf>) -> Self {
+        Self {
+            level,
+            path,
+            tool: Tool::Asteroid,
+            selected: None,
+        }
+    }
+
+    /// Forme d'un astéroïde selon sa taille.
+    ///
+    /// # Arguments
+    /// - `size` La taille de l'astéroïde.
+    ///
+    fn shape_for_size(size: f32) -> AsteroidShape {
+        let tuning = tuning();
+        if size <= tuning.asteroid_small_size {
+            AsteroidShape::Small
+        } else if size <= tuning.asteroid_medium_size {
+            AsteroidShape::Medium
+        } else {
+            AsteroidShape::Large
+        }
+    }
+
+    /// Objet sous un point de l'écran (le dernier placé en priorité).
+    ///
+    /// # Arguments
+    /// - `point` Le point (en pixels).
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn pick(&self, point: Vec2, screen: Vec2) -> Option<Selection> {
+        if let Some(start) = self.level.start {
+            if point.distance(start * screen) < Self::START_RADIUS {
+                return Some(Selection::Start);
+            }
+        }
+        let asteroid = self
+            .level
+            .events
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, event)| match event.origin {
+                SpawnOrigin::At(position) => point.distance(position * screen) < event.size,
+                SpawnOrigin::Edge(_) => false,
+            })
+            .map(|(index, _)| Selection::Asteroid(index));
+        asteroid.or_else(|| {
+            self.level
+                .blackholes
+                .iter()
+                .rposition(|blackhole| point.distance(blackhole.position * screen) < blackhole.size)
+                .map(Selection::BlackHole)
+        })
+    }
+
+    /// Place un nouvel objet (selon l'outil) et le sélectionne.
+    ///
+    /// # Arguments
+    /// - `point` Le point (en pixels).
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn place(&mut self, point: Vec2, screen: Vec2) {
+        let position = (point / screen).clamp(Vec2::ZERO, Vec2::ONE);
+        self.selected = Some(match self.tool {
+            Tool::Asteroid => {
+                let size = tuning().asteroid_medium_size;
+                self.level.events.push(SpawnEvent {
+                    time: 0.0,
+                    shape: Self::shape_for_size(size),
+                    size,
+                    origin: SpawnOrigin::At(position),
+                    velocity: Vec2::ZERO,
+                    rotation: 0.0,
+                });
+                Selection::Asteroid(self.level.events.len() - 1)
+            }
+            Tool::BlackHole => {
+                self.level.blackholes.push(PlacedBlackHole {
+                    position,
+                    size: tuning().blackhole_size,
+                    rotation: 0.0,
+                });
+                Selection::BlackHole(self.level.blackholes.len() - 1)
+            }
+            Tool::Start => {
+                self.level.start = Some(position);
+                Selection::Start
+            }
+        });
+    }
+
+    /// Déplace l'objet sélectionné.
+    ///
+    /// # Arguments
+    /// - `point` La nouvelle position (en pixels).
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn move_selected(&mut self, point: Vec2, screen: Vec2) {
+        let position = (point / screen).clamp(Vec2::ZERO, Vec2::ONE);
+        match self.selected {
+            Some(Selection::Asteroid(index)) => {
+                self.level.events[index].origin = SpawnOrigin::At(position);
+            }
+            Some(Selection::BlackHole(index)) => self.level.blackholes[index].position = position,
+            Some(Selection::Start) => self.level.start = Some(position),
+            None => {}
+        }
+    }
+
+    /// Redimensionne l'objet sélectionné (la forme d'un astéroïde suit sa taille).
+    ///
+    /// # Arguments
+    /// - `steps` Le nombre de crans (négatif pour réduire).
+    ///
+    pub fn resize_selected(&mut self, steps: f32) {
+        let (min, max) = Self::SIZE_RANGE;
+        match self.selected {
+            Some(Selection::Asteroid(index)) => {
+                let event = &mut self.level.events[index];
+                event.size = (event.size + steps * Self::SIZE_STEP).clamp(min, max);
+                event.shape = Self::shape_for_size(event.size);
+            }
+            Some(Selection::BlackHole(index)) => {
+                let blackhole = &mut self.level.blackholes[index];
+                blackhole.size = (blackhole.size + steps * Self::SIZE_STEP).clamp(min, max);
+            }
+            _ => {}
+        }
+    }
+
+    /// Oriente l'objet sélectionné.
+    ///
+    /// # Arguments
+    /// - `steps` Le nombre de crans (négatif pour tourner dans l'autre sens).
+    ///
+    pub fn rotate_selected(&mut self, steps: f32) {
+        match self.selected {
+            Some(Selection::Asteroid(index)) => {
+                self.level.events[index].rotation += steps * Self::ROTATION_STEP;
+            }
+            Some(Selection::BlackHole(index)) => {
+                self.level.blackholes[index].rotation += steps * Self::ROTATION_STEP;
+            }
+            _ => {}
+        }
+    }
+
+    /// Fixe la vitesse initiale de l'astéroïde sélectionné à partir de l'extrémité de sa flèche.
+    ///
+    /// # Arguments
+    /// - `target` L'extrémité de la flèche (en pixels).
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn set_velocity(&mut self, target: Vec2, screen: Vec2) {
+        if let Some(Selection::Asteroid(index)) = self.selected {
+            let event = &mut self.level.events[index];
+            if let SpawnOrigin::At(position) = event.origin {
+                event.velocity = (target - position * screen) / Self::VELOCITY_SCALE;
+            }
+        }
+    }
+
+    /// Décale l'instant d'apparition de l'astéroïde sélectionné.
+    ///
+    /// # Arguments
+    /// - `steps` Le nombre de pas (négatif pour avancer l'apparition).
+    ///
+    pub fn shift_time(&mut self, steps: f64) {
+        if let Some(Selection::Asteroid(index)) = self.selected {
+            let event = &mut self.level.events[index];
+            event.time = (event.time + steps * Self::TIME_STEP).max(0.0);
+        }
+    }
+
+    /// Supprime l'objet sélectionné.
+    pub fn delete_selected(&mut self) {
+        match self.selected.take() {
+            Some(Selection::Asteroid(index)) => {
+                self.level.events.remove(index);
+            }
+            Some(Selection::BlackHole(index)) => {
+                self.level.blackholes.remove(index);
+            }
+            Some(Selection::Start) => self.level.start = None,
+            None => {}
+        }
+    }
+
+    /// Passe à la condition de victoire suivante
+    /// (l'escorte et la balise se règlent dans le fichier du niveau).
+    fn next_goal(&mut self) {
+        self.level.goal = match self.level.goal {
+            LevelGoal::ClearField => LevelGoal::Survive(60.0),
+            LevelGoal::Survive(_) => LevelGoal::ReachScore(2000),
+            LevelGoal::ReachScore(_) => LevelGoal::DestroyLarge(5),
+            _ => LevelGoal::ClearField,
+        };
+    }
+
+    /// Texte d'un bouton de la barre.
+    ///
+    /// # Arguments
+    /// - `index` L'indice du bouton.
+    ///
+    fn button_label(&self, index: usize) -> String {
+        match index {
+            0 => format!("Tool : {}", self.tool.name()),
+            1 => format!("Flavor : {}", self.level.flavor),
+            2 => match self.level.goal {
+                LevelGoal::ClearField => "Goal : clear".to_string(),
+                LevelGoal::Survive(duration) => format!("Goal : survive {}s", duration),
+                LevelGoal::ReachScore(score) => format!("Goal : {} pts", score),
+                LevelGoal::DestroyLarge(count) => format!("Goal : {} large", count),
+                LevelGoal::Escort(duration) => format!("Goal : escort {}s", duration),
+                LevelGoal::Beacon(_) => "Goal : beacon".to_string(),
+            },
+            _ => Self::BUTTONS[index].to_string(),
+        }
+    }
+
+    /// Rectangle d'un bouton de la barre.
+    ///
+    /// # Arguments
+    /// - `index` L'indice du bouton.
+    ///
+    fn button_rect(index: usize) -> Rect {
+        let width = (screen_width() - 20.0) / Self::BUTTONS.len() as f32;
+        Rect::new(
+            10.0 + index as f32 * width,
+            screen_height() - Self::BAR_HEIGHT + 10.0,
+            width - 10.0,
+            Self::BAR_HEIGHT - 20.0,
+        )
+    }
+
+    /// Dessine le niveau édité.
+    ///
+    /// # Arguments
+    /// - `textures` Les textures (astéroïdes, trous noirs, vaisseau).
+    /// - `screen` La taille de l'écran.
+    ///
+    fn draw(&self, textures: [&Texture2D; 3], screen: Vec2) {
+        for (index, blackhole) in self.level.blackholes.iter().enumerate() {
+            let position = blackhole.position * screen;
+            BlackHole {
+                position,
+                size: blackhole.size,
+                rotation: blackhole.rotation,
+            }
+            .draw_blackhole(textures[1]);
+            if self.selected == Some(Selection::BlackHole(index)) {
+                draw_circle_lines(position.x, position.y, blackhole.size, 2.0, YELLOW);
+            }
+        }
+        for (index, event) in self.level.events.iter().enumerate() {
+            let SpawnOrigin::At(position) = event.origin else {
+                continue;
+            };
+            let position = position * screen;
+            let mut asteroid = Asteroid::new(position, event.velocity, event.shape, event.size);
+            asteroid.set_rotation(event.rotation);
+            asteroid.draw_asteroid(textures[0]);
+
+            let end = position + event.velocity * Self::VELOCITY_SCALE;
+            if event.velocity != Vec2::ZERO {
+                let direction = event.velocity.normalize();
+                let side = direction.perp() * 6.0;
+                draw_line(position.x, position.y, end.x, end.y, 2.0, SKYBLUE);
+                draw_triangle(end + direction * 10.0, end + side, end - side, SKYBLUE);
+            }
+            let color = if self.selected == Some(Selection::Asteroid(index)) {
+                draw_circle_lines(position.x, position.y, event.size, 2.0, YELLOW);
+                YELLOW
+            } else {
+                WHITE
+            };
+            draw_text(
+                &format!("t={:.1}", event.time),
+                position.x - 20.0,
+                position.y - event.size - 5.0,
+                20.0,
+                color,
+            );
+        }
+        let start = self.level.start.unwrap_or(vec2(0.5, 0.5)) * screen;
+        draw_texture_ex(
+            textures[2],
+            start.x - Self::START_RADIUS,
+            start.y - Self::START_RADIUS,
+            if self.level.start.is_some() {
+                WHITE
+            } else {
+                Color::new(1.0, 1.0, 1.0, 0.4)
+            },
+            DrawTextureParams {
+                dest_size: Some(Vec2::splat(Self::START_RADIUS * 2.0)),
+                ..Default::default()
+            },
+        );
+        if self.selected == Some(Selection::Start) {
+            draw_circle_lines(start.x, start.y, Self::START_RADIUS, 2.0, YELLOW);
+        }
+    }
+}
+
+/// Éditeur de niveaux.
+///
+/// Clic gauche : placer un objet (selon l'outil), ou sélectionner et déplacer un objet.
+/// Clic droit (glisser) : vitesse initiale de l'astéroïde. Molette : taille ; 'Maj' + molette : orientation.
+/// Flèches haut/bas : instant d'apparition. 'Suppr' : supprimer. 'Échap' : revenir au menu.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les commandes des joueurs (pour tester le niveau).
+/// - `skins` Les chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseau).
+///
+pub async fn level_editor(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &mut Sound,
+    bindings: &mut [Bind; 2],
+    skins: [&str; 4],
+) {
+    let blackhole_texture = load_texture(skins[1]).await.unwrap();
+    let spaceship_texture = load_texture(skins[3]).await.unwrap();
+    let mut editor = LevelEditor::new(Level::default(), None);
+    let mut status = String::from("New level");
+    let mut dragging = false;
+    sound.play_sound_effect("select_menu");
+
+    loop {
+        let screen = vec2(screen_width(), screen_height());
+        let mouse = Vec2::from(mouse_position());
+        let on_bar = mouse.y > screen.y - LevelEditor::BAR_HEIGHT;
+
+        clear_background(BLACK);
+        draw_background(background_texture);
+        editor.draw(
+            [asteroid_texture, &blackhole_texture, &spaceship_texture],
+            screen,
+        );
+
+        // Souris : sélection, placement, déplacement et vitesse
+        if is_mouse_button_pressed(MouseButton::Left) && !on_bar {
+            editor.selected = editor.pick(mouse, screen);
+            if editor.selected.is_none() {
+                editor.place(mouse, screen);
+            }
+            dragging = true;
+        }
+        if dragging && is_mouse_button_down(MouseButton::Left) {
+            editor.move_selected(mouse, screen);
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            dragging = false;
+        }
+        if is_mouse_button_pressed(MouseButton::Right) {
+            if let Some(Selection::Asteroid(index)) = editor.pick(mouse, screen) {
+                editor.selected = Some(Selection::Asteroid(index));
+            }
+        }
+        if is_mouse_button_down(MouseButton::Right) && !on_bar {
+            editor.set_velocity(mouse, screen);
+        }
+        let wheel = mouse_wheel().1;
+        if wheel != 0.0 {
+            let steps = wheel.signum();
+            if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                editor.rotate_selected(steps);
+            } else {
+                editor.resize_selected(steps);
+            }
+        }
+
+        // Clavier
+        if is_key_pressed(KeyCode::Up) {
+            editor.shift_time(1.0);
+        }
+        if is_key_pressed(KeyCode::Down) {
+            editor.shift_time(-1.0);
+        }
+        if is_key_pressed(KeyCode::Delete) {
+            editor.delete_selected();
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+
+        // Barre de boutons
+        for index in 0..LevelEditor::BUTTONS.len() {
+            let rect = LevelEditor::button_rect(index);
+            let hovered = rect.contains(mouse);
+            draw_rectangle(
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h,
+                if hovered { DARKGRAY } else { BLACK },
+            );
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, WHITE);
+            draw_text(
+                &editor.button_label(index),
+                rect.x + 8.0,
+                rect.y + rect.h * 0.65,
+                22.0,
+                if hovered { YELLOW } else { WHITE },
+            );
+        }
+        let clicked = is_mouse_button_pressed(MouseButton::Left)
+            .then(|| {
+                (0..LevelEditor::BUTTONS.len())
+                    .find(|&i| LevelEditor::button_rect(i).contains(mouse))
+            })
+            .flatten();
+        if let Some(index) = clicked {
+            sound.play_sound_effect("select_menu");
+            match LevelEditor::BUTTONS[index] {
+                "Tool" => editor.tool = editor.tool.next(),
+                "Flavor" => {
+                    editor.level.flavor = if editor.level.flavor == "classic" {
+                        "modern"
+                    } else {
+                        "classic"
+                    };
+                }
+                "Goal" => editor.next_goal(),
+                "Save" => {
+                    if editor.path.is_none() {
+                        if let Some(name) = enter_name(
+                            "Level name",
+                            background_texture,
+                            asteroid_texture,
+                            asteroids,
+                            sound,
+                        )
+                        .await
+                        {
+                            if editor.level.name == Level::default().name {
+                                editor.level.name = name.clone();
+                            }
+                            editor.path =
+                                Some(PathBuf::from(Level::FOLDER).join(format!("{}.level", name)));
+                        }
+                    }
+                    if let Some(path) = &editor.path {
+                        status = match editor.level.save(path) {
+                            Ok(()) => format!("Saved to {}", path.display()),
+                            Err(e) => format!("Could not save {}: {}", path.display(), e),
+                        };
+                    }
+                }
+                "Load" => {
+                    if let Some((path, level)) =
+                        level_menu(background_texture, asteroid_texture, asteroids, sound).await
+                    {
+                        status = format!("Loaded {}", path.display());
+                        editor = LevelEditor::new(level, Some(path));
+                    }
+                }
+                "Test play" => {
+                    if editor.level.goal == LevelGoal::ClearField && editor.level.events.is_empty()
+                    {
+                        status = "Add at least one asteroid to test a 'clear' level".to_string();
+                    } else {
+                        play(
+                            "level",
+                            skins,
+                            get_time(),
+                            sound,
+                            bindings,
+                            Some(&editor.level),
+                            None,
+                        )
+                        .await;
+                        status = "Back from test play".to_string();
+                    }
+                }
+                _ => return,
+            }
+        }
+
+        // Aide et état
+        let edge_spawns = editor
+            .level
+            .events
+            .iter()
+            .filter(|event| matches!(event.origin, SpawnOrigin::Edge(_)))
+            .count();
+        let lines = [
+            format!("Level editor : {}", editor.level.name),
+            "Left click : place / select and drag   Right drag : velocity   Wheel : size   Shift + wheel : rotation".to_string(),
+            "Up / Down : spawn time   Delete : remove   Esc : back".to_string(),
+            format!("{} ({} edge spawn(s) kept)", status, edge_spawns),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(
+                line,
+                10.0,
+                30.0 + i as f32 * 25.0,
+                if i == 0 { 30.0 } else { 22.0 },
+                if i == 0 { YELLOW } else { WHITE },
+            );
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_place_pick_and_edit() {
+        let screen = vec2(1000.0, 500.0);
+        let mut editor = LevelEditor::new(Level::default(), None);
+        editor.place(vec2(500.0, 250.0), screen);
+        assert_eq!(editor.selected, Some(Selection::Asteroid(0)));
+        editor.set_velocity(vec2(580.0, 250.0), screen);
+        assert_eq!(editor.level.events[0].velocity, vec2(2.0, 0.0));
+        for _ in 0..10 {
+            editor.resize_selected(-1.0);
+        }
+        assert_eq!(editor.level.events[0].size, 10.0);
+        assert_eq!(editor.level.events[0].shape, AsteroidShape::Small);
+        editor.move_selected(vec2(100.0, 100.0), screen);
+        editor.shift_time(3.0);
+        assert_eq!(editor.level.events[0].time, 1.5);
+
+        editor.tool = Tool::BlackHole;
+        editor.place(vec2(800.0, 400.0), screen);
+        editor.rotate_selected(1.0);
+        editor.tool = Tool::Start;
+        editor.place(vec2(300.0, 300.0), screen);
+        assert_eq!(
+            editor.pick(vec2(105.0, 100.0), screen),
+            Some(Selection::Asteroid(0))
+        );
+        assert_eq!(
+            editor.pick(vec2(800.0, 390.0), screen),
+            Some(Selection::BlackHole(0))
+        );
+        assert_eq!(
+            editor.pick(vec2(300.0, 300.0), screen),
+            Some(Selection::Start)
+        );
+        assert_eq!(editor.pick(vec2(600.0, 50.0), screen), None);
+
+        editor.delete_selected();
+        assert_eq!(editor.level.start, None);
+        assert_eq!(editor.level.blackholes[0].rotation, 0.2);
+    }
+
+    #[test]
+    fn test_edited_level_round_trip() {
+        let screen = vec2(1000.0, 500.0);
+        let mut editor = LevelEditor::new(Level::default(), None);
+        editor.level.goal = LevelGoal::Survive(45.0);
+        editor.place(vec2(250.0, 125.0), screen);
+        editor.rotate_selected(2.0);
+        editor.set_velocity(vec2(250.0, 165.0), screen);
+        editor.tool = Tool::BlackHole;
+        editor.place(vec2(500.0, 250.0), screen);
+        editor.tool = Tool::Start;
+        editor.place(vec2(750.0, 375.0), screen);
+
+        let level = Level::parse(&editor.level.to_text()).unwrap();
+        assert_eq!(level, editor.level);
+    }
+}
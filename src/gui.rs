@@ -1,7 +1,16 @@
+use crate::achievement::Achievements;
 use crate::asteroid::Asteroid;
-use crate::bind::Bind;
+use crate::autopilot::AiSkill;
+use crate::bind::{Action, Bind};
+use crate::boss::Boss;
+use crate::highscore::HighScores;
+use crate::level::LevelGoal;
 use crate::option::option_menu;
+use crate::score::ScoreEngine;
 use crate::sound::Sound;
+use crate::station::Station;
+use crate::stats::{draw_stats_panel, LifetimeStats};
+use crate::tuning::{self, tuning, TUNING_FILE};
 use crate::{score, spaceship::Spaceship};
 use macroquad::{color, prelude::*};
 
@@ -12,7 +21,7 @@ use macroquad::{color, prelude::*};
 /// - `background_texture` La texture de l'arrière-plan.
 /// - `asteroid_texture` La texture des astéroids.
 /// - `sound` Les sons du jeu.
-/// - `keybinding` Le Bind actuel.
+/// - `bindings` Les Binds actuels des deux joueurs.
 ///
 /// # Returns
 /// Le temps de pause (pour ne pas le prendre en compte comme 'temps de jeu').
@@ -21,16 +30,16 @@ pub async fn pause(
     background_texture: &Texture2D,
     asteroid_texture: &Texture2D,
     sound: &mut Sound,
-    keybinding: &mut Bind,
+    bindings: &mut [Bind; 2],
 ) -> f64 {
-    if is_key_pressed(KeyCode::Escape) {
+    if bindings[0].is_pressed(Action::Pause) {
         let starting_pause_time = get_time();
         option_menu(
             background_texture,
             asteroid_texture,
             &mut vec![],
             sound,
-            keybinding,
+            bindings,
             get_time(),
         )
         .await;
@@ -42,13 +51,41 @@ pub async fn pause(
 
 /// Affichage du bouclier, représenté par des rectangles verts.
 /// Lorsque le bouclier diminue, les rectangles verts correspondant disparaissent.
+/// Le bouclier d'énergie est représenté par une barre (rouge lorsqu'il est brisé).
 ///
 /// # Arguments
-/// - `shield` Le nombre de rectangles verts à afficher.
+/// - `spaceship` Le vaisseau du joueur.
+/// - `time` Le temps actuel (en secondes).
+///
+pub fn draw_shield(spaceship: &Spaceship, time: f64) {
+    draw_shield_at(spaceship, time, 10.0, 70.0);
+}
+
+/// Affichage du bouclier d'un vaisseau à une position donnée (voir `draw_shield`).
 ///
-pub fn draw_shield(shield: u32) {
-    for i in 0..shield {
-        draw_rectangle(10.0 + 30.0 * i as f32, 70.0, 25.0, 15.0, GREEN);
+/// # Arguments
+/// - `spaceship` Le vaisseau du joueur.
+/// - `time` Le temps actuel (en secondes).
+/// - `x` La position horizontale du bouclier.
+/// - `y` La position verticale du bouclier.
+///
+fn draw_shield_at(spaceship: &Spaceship, time: f64, x: f32, y: f32) {
+    match spaceship.get_energy_shield() {
+        Some(energy_shield) if spaceship.shield > 0 => {
+            let color = if energy_shield.is_broken(time) {
+                RED
+            } else {
+                GREEN
+            };
+            draw_rectangle_lines(x, y, 145.0, 15.0, 2.0, color);
+            draw_rectangle(x, y, 145.0 * energy_shield.ratio(time), 15.0, color);
+        }
+        Some(_) => {}
+        None => {
+            for i in 0..spaceship.shield {
+                draw_rectangle(x + 30.0 * i as f32, y, 25.0, 15.0, GREEN);
+            }
+        }
     }
 }
 
@@ -67,6 +104,154 @@ pub fn draw_score(score: u32) {
     );
 }
 
+/// Affichage du minerai ramassé (sous le score).
+///
+/// # Arguments
+/// - `ore` La quantité de minerai.
+///
+pub fn draw_ore(ore: u32) {
+    draw_text(
+        &format!("Ore : {}", ore),
+        screen_width() - 180.0,
+        160.0,
+        30.0,
+        Color::new(0.3, 0.9, 1.0, 1.0),
+    );
+}
+
+/// Affichage du niveau du pilote automatique qui contrôle le vaisseau (mode "Watch AI").
+///
+/// # Arguments
+/// - `skill` Le niveau du pilote automatique.
+///
+pub fn draw_autopilot(skill: AiSkill) {
+    draw_text(
+        &format!("Autopilot : {}", skill.name()),
+        10.0,
+        screen_height() - 50.0,
+        30.0,
+        ORANGE,
+    );
+}
+
+/// Affichage du combo et du multiplicateur en cours (sous le score).
+///
+/// # Arguments
+/// - `score_engine` Le moteur de score du joueur.
+///
+pub fn draw_combo(score_engine: &ScoreEngine) {
+    if score_engine.get_multiplier() <= 1.0 {
+        return;
+    }
+    let text = if score_engine.get_combo() > 1 {
+        format!(
+            "Combo {} x{:.2}",
+            score_engine.get_combo(),
+            score_engine.get_multiplier()
+        )
+    } else {
+        format!("x{:.2}", score_engine.get_multiplier())
+    };
+    draw_text(&text, screen_width() - 180.0, 110.0, 25.0, YELLOW);
+}
+
+/// Affichage des erreurs du fichier d'équilibrage (bandeau en haut de l'écran).
+/// Rien n'est affiché si le fichier est valide (la relecture est faite par les boucles de jeu et de menu).
+pub fn draw_tuning_errors() {
+    let errors = tuning::errors();
+    if errors.is_empty() {
+        return;
+    }
+    let shown = errors.len().min(5);
+    draw_rectangle(
+        0.0,
+        0.0,
+        screen_width(),
+        35.0 + shown as f32 * 22.0,
+        Color::new(0.5, 0.0, 0.0, 0.85),
+    );
+    draw_text(
+        &format!("{} : {} error(s)", TUNING_FILE, errors.len()),
+        10.0,
+        25.0,
+        25.0,
+        WHITE,
+    );
+    for (i, error) in errors.iter().take(shown).enumerate() {
+        draw_text(error, 20.0, 48.0 + i as f32 * 22.0, 22.0, WHITE);
+    }
+}
+
+/// Affichage des points gagnés sous forme de textes flottants.
+/// Les textes montent et s'estompent pendant leur durée de vie.
+///
+/// # Arguments
+/// - `score_engine` Le moteur de score du joueur.
+/// - `time` Le temps de jeu.
+///
+pub fn draw_score_popups(score_engine: &ScoreEngine, time: f64) {
+    let mut bonus_line = 0;
+    for popup in &score_engine.popups {
+        let progress = ((time - popup.birth_time) / tuning().popup_duration) as f32;
+        let color = Color::new(1.0, 1.0, 0.0, 1.0 - progress.clamp(0.0, 1.0));
+        match popup.position {
+            Some(position) => {
+                let dimension = measure_text(&popup.text, None, 25, 1.0);
+                draw_text(
+                    &popup.text,
+                    position.x - dimension.width / 2.0,
+                    position.y - 30.0 * progress,
+                    25.0,
+                    color,
+                );
+            }
+            None => {
+                draw_text(
+                    &popup.text,
+                    screen_width() - 280.0,
+                    140.0 + bonus_line as f32 * 25.0,
+                    25.0,
+                    color,
+                );
+                bonus_line += 1;
+            }
+        }
+    }
+}
+
+/// Affichage du panneau d'un joueur (mode coopératif) : nom, bouclier, score et combo.
+///
+/// # Arguments
+/// - `label` Le nom du joueur.
+/// - `spaceship` Le vaisseau du joueur.
+/// - `x` La position horizontale du panneau.
+/// - `color` La couleur du joueur.
+/// - `time` Le temps actuel (en secondes).
+///
+pub fn draw_player_panel(label: &str, spaceship: &Spaceship, x: f32, color: Color, time: f64) {
+    draw_rectangle_lines(x, 45.0, 250.0, 110.0, 2.0, color);
+    let status = if spaceship.shield == 0 { " (out)" } else { "" };
+    draw_text(&format!("{}{}", label, status), x + 10.0, 72.0, 28.0, color);
+    draw_shield_at(spaceship, time, x + 10.0, 82.0);
+    draw_text(
+        &format!("Score : {}", spaceship.score),
+        x + 10.0,
+        122.0,
+        25.0,
+        WHITE,
+    );
+    let engine = &spaceship.score_engine;
+    if engine.get_multiplier() > 1.0 {
+        draw_text(
+            &format!("x{:.2}", engine.get_multiplier()),
+            x + 10.0,
+            146.0,
+            22.0,
+            YELLOW,
+        );
+    }
+}
+
 /// Affichage du nombre d'astéroids.
 ///
 /// # Arguments
@@ -82,12 +267,124 @@ pub fn draw_asteroid_number(asteroids: &[Asteroid]) {
     );
 }
 
+/// Affichage de la barre de vie du boss (en haut de l'écran).
+///
+/// # Arguments
+/// - `boss` Le boss en cours de combat.
+///
+pub fn draw_boss_health(boss: &Boss) {
+    let width = screen_width() * 0.4;
+    let x = (screen_width() - width) / 2.0;
+    let name = boss.get_kind().name();
+    let dimension = measure_text(name, None, 30, 1.0);
+    draw_text(
+        name,
+        (screen_width() - dimension.width) / 2.0,
+        30.0,
+        30.0,
+        WHITE,
+    );
+    let ratio = boss.health() as f32 / boss.get_max_health() as f32;
+    draw_rectangle(x, 40.0, width * ratio, 15.0, RED);
+    draw_rectangle_lines(x, 40.0, width, 15.0, 2.0, WHITE);
+}
+
+/// Affichage des points de vie de la structure à protéger (en bas de l'écran).
+///
+/// # Arguments
+/// - `station` La station ou la capsule de cargaison.
+///
+pub fn draw_station_health(station: &Station) {
+    let width = screen_width() * 0.3;
+    let x = (screen_width() - width) / 2.0;
+    let y = screen_height() - 40.0;
+    let text = format!(
+        "{} : {}/{}",
+        station.get_kind().name(),
+        station.get_hit_points(),
+        station.get_kind().max_hit_points()
+    );
+    let dimension = measure_text(&text, None, 30, 1.0);
+    draw_text(
+        &text,
+        (screen_width() - dimension.width) / 2.0,
+        y - 10.0,
+        30.0,
+        WHITE,
+    );
+    let color = if station.health_ratio() <= 0.25 {
+        RED
+    } else {
+        GREEN
+    };
+    draw_rectangle(x, y, width * station.health_ratio(), 15.0, color);
+    draw_rectangle_lines(x, y, width, 15.0, 2.0, WHITE);
+}
+
 /// Affichage du temps de jeu.
 ///
 pub fn draw_time(time_str: &str) {
     draw_text(time_str, 10.0, 30.0, 30.0, WHITE);
 }
 
+/// Affichage du temps restant (mode Time Attack).
+///
+/// # Arguments
+/// - `time_left` Le temps restant, en secondes.
+///
+pub fn draw_time_left(time_left: f64) {
+    let text = format!("Time left : {:.0}", time_left.ceil());
+    let dimension = measure_text(&text, None, 40, 1.0);
+    let color = if time_left < 10.0 { RED } else { WHITE };
+    draw_text(
+        &text,
+        (screen_width() - dimension.width) / 2.0,
+        90.0,
+        40.0,
+        color,
+    );
+}
+
+/// Affichage de l'avancement de l'objectif d'un niveau (sous le temps restant).
+///
+/// # Arguments
+/// - `text` Le texte de l'objectif.
+///
+pub fn draw_objective(text: &str) {
+    let dimension = measure_text(text, None, 30, 1.0);
+    draw_text(
+        text,
+        (screen_width() - dimension.width) / 2.0,
+        130.0,
+        30.0,
+        YELLOW,
+    );
+}
+
+/// Dessine la balise à atteindre (cercle pulsant).
+///
+/// # Arguments
+/// - `position` La position de la balise.
+/// - `time` Le temps de jeu (pour la pulsation).
+///
+pub fn draw_beacon(position: Vec2, time: f64) {
+    let pulse = (time * 4.0).sin() as f32 * 0.5 + 0.5;
+    let radius = LevelGoal::BEACON_RADIUS * screen_width();
+    draw_circle(
+        position.x,
+        position.y,
+        radius,
+        Color::new(0.2, 1.0, 0.4, 0.15 + 0.15 * pulse),
+    );
+    draw_circle_lines(
+        position.x,
+        position.y,
+        radius * (1.0 + 0.3 * pulse),
+        2.0,
+        GREEN,
+    );
+}
+
 /// Affichage du score final.
 /// Affiche le score du joueur à la fin de la partie.
 ///
@@ -107,13 +404,148 @@ pub fn draw_final_score(score: u32) {
     );
 }
 
+/// Affichage du détail du score final (points de base, combos et bonus).
+///
+/// # Arguments
+/// - `spaceship` Le vaisseau du joueur (moteur de score et statistiques de la partie).
+///
+pub fn draw_score_breakdown(spaceship: &Spaceship) {
+    let score_engine = &spaceship.score_engine;
+    let breakdown = score_engine.get_breakdown();
+    let lines = [
+        format!("Asteroids : {}", breakdown.base),
+        format!(
+            "Combos : {} (best chain {})",
+            breakdown.combo,
+            score_engine.get_max_combo()
+        ),
+        format!(
+            "Accuracy : {} ({:.0}%)",
+            breakdown.accuracy,
+            spaceship.stats.accuracy() * 100.0
+        ),
+        format!("No damage waves : {}", breakdown.no_damage),
+        format!("Quick clears : {}", breakdown.quick_clear),
+    ];
+    let font_size = 25;
+    for (i, line) in lines.iter().enumerate() {
+        let dimension = measure_text(line, None, font_size, 1.0);
+        draw_text(
+            line,
+            (screen_width() - dimension.width) / 2.0,
+            290.0 + i as f32 * 30.0,
+            font_size as f32,
+            WHITE,
+        );
+    }
+}
+
+/// Affichage de l'écran de fin de partie du mode coopératif (scores de chaque joueur).
+///
+/// # Arguments
+/// - `players` Les vaisseaux des joueurs.
+/// - `sound` Les sons du jeu (pour jouer le son correspondant).
+/// - `win` Vrai si les joueurs ont gagné.
+///
+/// # Returns
+/// - `true` pour revenir au menu, `false` pour quitter le jeu.
+///
+pub async fn coop_game_over(players: &[Spaceship], sound: &Sound, win: bool) -> bool {
+    let (text, color) = if win {
+        sound.play_sound_effect("win");
+        ("You Won !", Color::from_rgba(0, 213, 109, 1))
+    } else {
+        sound.play_sound_effect("gameover");
+        ("Game Over", color::RED)
+    };
+    let total: u32 = players.iter().map(score::get_score).sum();
+    loop {
+        clear_background(color);
+        let dimension = measure_text(text, None, 60, 1.0);
+        draw_text(
+            text,
+            (screen_width() - dimension.width) / 2.0,
+            200_f32,
+            60.0,
+            WHITE,
+        );
+
+        let column_width = screen_width() / players.len() as f32;
+        for (i, player) in players.iter().enumerate() {
+            let breakdown = player.score_engine.get_breakdown();
+            let lines = [
+                format!("Player {}", i + 1),
+                format!("Score : {}", score::get_score(player)),
+                format!("Asteroids : {}", breakdown.base),
+                format!("Combos : {}", breakdown.combo),
+                format!(
+                    "Accuracy : {} ({:.0}%)",
+                    breakdown.accuracy,
+                    player.stats.accuracy() * 100.0
+                ),
+            ];
+            for (j, line) in lines.iter().enumerate() {
+                let font_size = if j == 0 { 35 } else { 25 };
+                let dimension = measure_text(line, None, font_size, 1.0);
+                draw_text(
+                    line,
+                    column_width * i as f32 + (column_width - dimension.width) / 2.0,
+                    290.0 + j as f32 * 35.0,
+                    font_size as f32,
+                    WHITE,
+                );
+            }
+        }
+
+        let team = format!("Team Score : {}", total);
+        let dimension = measure_text(&team, None, 40, 1.0);
+        draw_text(
+            &team,
+            (screen_width() - dimension.width) / 2.0,
+            500.0,
+            40.0,
+            WHITE,
+        );
+
+        let text2 = "Press 'R' to Restart or 'Esc' to Exit";
+        let dimension2 = measure_text(text2, None, 40, 1.0);
+        draw_text(
+            text2,
+            (screen_width() - dimension2.width) / 2.0,
+            600_f32,
+            40.0,
+            WHITE,
+        );
+
+        if is_key_pressed(KeyCode::R) {
+            return true;
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            return false;
+        }
+        next_frame().await;
+    }
+}
+
 /// Affichage de l'écran de Game Over.
 ///
 /// # Arguments
 /// - `spaceship` Le vaisseau du joueur.
+/// - `lifetime` Les statistiques cumulées de toutes les parties.
+/// - `high_scores` Les meilleurs scores de chaque mode.
+/// - `new_record` Le score de la partie bat le record du mode.
+/// - `achievements` Les succès (pour afficher les notifications des succès débloqués).
 /// - `sound` Les sons du jeu (pour jouer le son correspondant).
 ///
-pub async fn game_over(spaceship: &Spaceship, sound: &Sound, win: bool) -> bool {
+pub async fn game_over(
+    spaceship: &Spaceship,
+    lifetime: &LifetimeStats,
+    high_scores: &HighScores,
+    new_record: bool,
+    achievements: &mut Achievements,
+    sound: &Sound,
+    win: bool,
+) -> bool {
     let text: &str;
     let color: Color;
     if win {
@@ -137,7 +569,32 @@ pub async fn game_over(spaceship: &Spaceship, sound: &Sound, win: bool) -> bool
             WHITE,
         );
 
+        draw_score_breakdown(spaceship);
         draw_final_score(score::get_score(spaceship));
+        draw_stats_panel("This game", &spaceship.stats.describe(), 40.0, 300.0);
+        draw_stats_panel(
+            "Lifetime",
+            &lifetime.describe(),
+            screen_width() - 480.0,
+            300.0,
+        );
+        draw_stats_panel(
+            "High scores",
+            &high_scores.describe(),
+            screen_width() / 2.0 - 200.0,
+            620.0,
+        );
+        if new_record {
+            let record = "New high score !";
+            let dimension = measure_text(record, None, 40, 1.0);
+            draw_text(
+                record,
+                (screen_width() - dimension.width) / 2.0,
+                500_f32,
+                40.0,
+                YELLOW,
+            );
+        }
 
         let text2 = "Press 'R' to Restart or 'Esc' to Exit";
         let font_size2 = 40;
@@ -149,6 +606,7 @@ pub async fn game_over(spaceship: &Spaceship, sound: &Sound, win: bool) -> bool
             font_size2 as f32,
             WHITE,
         );
+        achievements.draw_toasts(get_time());
 
         if is_key_pressed(KeyCode::R) {
             return true;
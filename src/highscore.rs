@@ -0,0 +1,122 @@
+//! # High Score Module
+//!
+//! Ce module conserve le meilleur score de chaque mode de jeu solo (une catégorie par mode),
+//! avec le niveau de difficulté de la partie, sauvegardé dans `saves/highscores.txt`
+//! au format `mode=score` et `mode.difficulty=niveau`.
+use crate::difficulty::Difficulty;
+use crate::rules::ModeRules;
+use crate::save::{get_or_default, read_key_values, write_key_values};
+use std::collections::HashMap;
+
+/// Meilleurs scores, par mode de jeu.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HighScores {
+    /// Meilleur score de chaque mode et sa difficulté (identifiant du mode → score, difficulté).
+    scores: HashMap<String, (u32, Difficulty)>,
+}
+
+impl HighScores {
+    /// Nom du fichier de sauvegarde des meilleurs scores.
+    const FILE_NAME: &'static str = "highscores.txt";
+
+    /// Charge les meilleurs scores depuis le disque.
+    pub fn load() -> Self {
+        let values = read_key_values(Self::FILE_NAME);
+        Self {
+            scores: ModeRules::ALL
+                .iter()
+                .map(|rules| {
+                    let difficulty = values
+                        .get(&format!("{}.difficulty", rules.name))
+                        .and_then(|name| Difficulty::from_name(name))
+                        .unwrap_or_default();
+                    (
+                        rules.name.to_string(),
+                        (get_or_default(&values, rules.name), difficulty),
+                    )
+                })
+                .filter(|(_, (score, _))| *score > 0)
+                .collect(),
+        }
+    }
+
+    /// Sauvegarde les meilleurs scores sur le disque.
+    pub fn save(&self) {
+        let entries: Vec<(String, String)> = ModeRules::ALL
+            .iter()
+            .filter_map(|rules| self.scores.get(rules.name).map(|entry| (rules.name, entry)))
+            .flat_map(|(name, (score, difficulty))| {
+                [
+                    (name.to_string(), score.to_string()),
+                    (
+                        format!("{}.difficulty", name),
+                        difficulty.name().to_string(),
+                    ),
+                ]
+            })
+            .collect();
+        write_key_values(Self::FILE_NAME, &entries);
+    }
+
+    /// Meilleur score d'un mode (0 si aucune partie n'a été jouée).
+    ///
+    /// # Arguments
+    /// - `mode` L'identifiant du mode.
+    ///
+    pub fn get(&self, mode: &str) -> u32 {
+        self.scores.get(mode).map_or(0, |(score, _)| *score)
+    }
+
+    /// Enregistre le score d'une partie terminée.
+    /// Les parties personnalisées (modes hors de `ModeRules::ALL`) n'ont pas de catégorie.
+    ///
+    /// # Arguments
+    /// - `mode` L'identifiant du mode.
+    /// - `score` Le score final de la partie.
+    /// - `difficulty` Le niveau de difficulté de la partie.
+    ///
+    /// # Returns
+    /// - `true` si le score bat le record du mode.
+    /// - `false` sinon.
+    ///
+    pub fn submit(&mut self, mode: &str, score: u32, difficulty: Difficulty) -> bool {
+        if ModeRules::for_mode(mode).is_some() && score > self.get(mode) {
+            self.scores.insert(mode.to_string(), (score, difficulty));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Lignes décrivant le meilleur score de chaque mode (et sa difficulté).
+    pub fn describe(&self) -> Vec<String> {
+        ModeRules::ALL
+            .iter()
+            .map(|rules| match self.scores.get(rules.name) {
+                Some((score, difficulty)) => {
+                    format!("{} : {} ({})", rules.title, score, difficulty.name())
+                }
+                None => format!("{} : 0", rules.title),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_keeps_best_score_per_mode() {
+        let mut high_scores = HighScores::default();
+        assert!(high_scores.submit("survival", 1200, Difficulty::Hard));
+        assert!(!high_scores.submit("survival", 800, Difficulty::Normal));
+        assert!(high_scores.submit("time_attack", 300, Difficulty::Easy));
+        assert_eq!(high_scores.get("survival"), 1200);
+        assert_eq!(high_scores.get("zen"), 0);
+        assert!(!high_scores.submit("custom", 5000, Difficulty::Normal));
+        let lines = high_scores.describe();
+        assert!(lines.contains(&"Time Attack : 300 (Easy)".to_string()));
+        assert!(lines.contains(&"Survival : 1200 (Hard)".to_string()));
+    }
+}
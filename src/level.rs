@@ -0,0 +1,900 @@
+//! # Level Module
+//!
+//! Ce module gère les niveaux écrits à la main, rangés dans `assets/levels` (fichiers `.level`).
+//! Un niveau décrit des apparitions d'astéroïdes à des instants précis, des trous noirs
+//! placés dès le début, sa condition de victoire et son arrière-plan.
+//!
+//! ## Format
+//! Une instruction par ligne ; les lignes vides et les commentaires (`#`) sont ignorés.
+//! Les positions sont des fractions de l'écran (entre 0 et 1), les vitesses sont en pixels par frame.
+//!
+//! ```text
+//! name=First Contact
+//! flavor=modern                    # 'classic' ou 'modern'
+//! background=assets/pictures/backgrounds/background2.png
+//! win=clear                        # 'clear', 'survive <secondes>', 'score <points>',
+//!                                  # 'destroy <gros astéroïdes>', 'escort <secondes>' ou 'beacon <x> <y>'
+//! start=0.5 0.8                    # position de départ du vaisseau (centre de l'écran sinon)
+//! cargo=0.1 0.5 0.5 0.0            # capsule à escorter : position et vitesse (objectif 'escort')
+//! briefing=Texte du briefing       # une ligne de briefing par instruction (campagne)
+//! par=2000                         # score de référence pour les étoiles (campagne)
+//! blackhole 0.5 0.5                # taille et rotation facultatives : blackhole 0.5 0.5 60 1.2
+//! spawn 2.0 large 60 at 0.1 0.2 1.0 0.5      # rotation facultative en dernier
+//! spawn 4.5 medium 40 edge top 0.0 1.2
+//! ```
+//!
+//! Les erreurs de lecture indiquent le numéro de la ligne fautive.
+//! Les niveaux peuvent aussi être créés avec l'éditeur (voir le module `editor`).
+//! `Spawner` permet à `update_classic` / `update_modern` de suivre un niveau
+//! au lieu des apparitions aléatoires du mode.
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::blackhole::BlackHole;
+use crate::rng::game_rng;
+use crate::rules::ModeRules;
+use crate::sound::Sound;
+use crate::tuning::tuning;
+use crate::utils::{background_asteroids, handle_enter_press};
+use ::rand::Rng;
+use macroquad::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Condition de victoire d'un niveau.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelGoal {
+    /// Détruire tous les astéroïdes (une fois toutes les apparitions passées).
+    ClearField,
+    /// Survivre pendant une durée donnée (en secondes).
+    Survive(f64),
+    /// Atteindre un score donné.
+    ReachScore(u32),
+    /// Détruire un nombre donné de gros astéroïdes.
+    DestroyLarge(u32),
+    /// Protéger la capsule de cargaison pendant une durée donnée (en secondes).
+    Escort(f64),
+    /// Atteindre une balise (position en fraction de l'écran).
+    Beacon(Vec2),
+}
+
+/// Avancement de la partie, pour vérifier la condition de victoire.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoalProgress {
+    /// Le temps de jeu, en secondes.
+    pub time: f64,
+    /// Vrai si le terrain est nettoyé et qu'aucun astéroïde ne doit plus apparaître.
+    pub field_cleared: bool,
+    /// Le score.
+    pub score: u32,
+    /// Le nombre de gros astéroïdes détruits.
+    pub large_destroyed: u32,
+    /// La position du vaisseau (fraction de l'écran).
+    pub ship_position: Vec2,
+}
+
+impl LevelGoal {
+    /// Distance (fraction de la largeur de l'écran) à laquelle la balise est atteinte.
+    pub const BEACON_RADIUS: f32 = 0.03;
+
+    /// Vérifie si la condition de victoire est remplie.
+    ///
+    /// # Arguments
+    /// - `progress` L'avancement de la partie.
+    ///
+    pub fn is_reached(&self, progress: &GoalProgress) -> bool {
+        match *self {
+            LevelGoal::ClearField => progress.field_cleared,
+            LevelGoal::Survive(duration) | LevelGoal::Escort(duration) => progress.time >= duration,
+            LevelGoal::ReachScore(score) => progress.score >= score,
+            LevelGoal::DestroyLarge(count) => progress.large_destroyed >= count,
+            LevelGoal::Beacon(beacon) => {
+                progress.ship_position.distance(beacon) <= Self::BEACON_RADIUS
+            }
+        }
+    }
+
+    /// Description de l'objectif (briefing et affichage en jeu).
+    pub fn describe(&self) -> String {
+        match *self {
+            LevelGoal::ClearField => "Destroy every asteroid".to_string(),
+            LevelGoal::Survive(duration) => format!("Survive for {} seconds", duration),
+            LevelGoal::ReachScore(score) => format!("Reach {} points", score),
+            LevelGoal::DestroyLarge(count) => format!("Destroy {} large asteroids", count),
+            LevelGoal::Escort(duration) => {
+                format!("Protect the cargo pod for {} seconds", duration)
+            }
+            LevelGoal::Beacon(_) => "Reach the beacon".to_string(),
+        }
+    }
+
+    /// Texte de l'avancement de l'objectif affiché pendant la partie (s'il y a lieu).
+    ///
+    /// # Arguments
+    /// - `progress` L'avancement de la partie.
+    ///
+    pub fn progress_text(&self, progress: &GoalProgress) -> Option<String> {
+        match *self {
+            LevelGoal::DestroyLarge(count) => Some(format!(
+                "Large asteroids: {}/{}",
+                progress.large_destroyed.min(count),
+                count
+            )),
+            LevelGoal::Escort(_) => Some("Protect the cargo pod".to_string()),
+            LevelGoal::Beacon(_) => Some("Reach the beacon".to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Bord de l'écran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Bord haut.
+    Top,
+    /// Bord bas.
+    Bottom,
+    /// Bord gauche.
+    Left,
+    /// Bord droit.
+    Right,
+}
+
+impl Edge {
+    /// Nom du bord (dans les fichiers de niveau).
+    pub fn name(self) -> &'static str {
+        match self {
+            Edge::Top => "top",
+            Edge::Bottom => "bottom",
+            Edge::Left => "left",
+            Edge::Right => "right",
+        }
+    }
+}
+
+/// Point d'apparition d'un astéroïde.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnOrigin {
+    /// Position précise (fraction de l'écran).
+    At(Vec2),
+    /// Position aléatoire le long d'un bord de l'écran.
+    Edge(Edge),
+}
+
+/// Apparition d'un astéroïde à un instant donné.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnEvent {
+    /// Instant de l'apparition, en secondes.
+    pub time: f64,
+    /// Forme de l'astéroïde.
+    pub shape: AsteroidShape,
+    /// Taille de l'astéroïde.
+    pub size: f32,
+    /// Point d'apparition.
+    pub origin: SpawnOrigin,
+    /// Vitesse initiale.
+    pub velocity: Vec2,
+    /// Orientation initiale, en radians (0 : aléatoire).
+    pub rotation: f32,
+}
+
+/// Trou noir présent dès le début d'un niveau.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacedBlackHole {
+    /// Position (fraction de l'écran).
+    pub position: Vec2,
+    /// Taille du trou noir.
+    pub size: f32,
+    /// Orientation, en radians.
+    pub rotation: f32,
+}
+
+/// Niveau lu depuis un fichier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    /// Nom affiché du niveau.
+    pub name: String,
+    /// Variante de jeu ('classic' ou 'modern').
+    pub flavor: &'static str,
+    /// Chemin de l'arrière-plan (celui des skins sinon).
+    pub background: Option<String>,
+    /// Condition de victoire.
+    pub goal: LevelGoal,
+    /// Position de départ du vaisseau (fraction de l'écran ; centre de l'écran si absente).
+    pub start: Option<Vec2>,
+    /// Trous noirs présents dès le début.
+    pub blackholes: Vec<PlacedBlackHole>,
+    /// Apparitions d'astéroïdes, triées par instant.
+    pub events: Vec<SpawnEvent>,
+    /// Capsule de cargaison à escorter : position (fraction de l'écran) et vitesse.
+    pub cargo: Option<(Vec2, Vec2)>,
+    /// Lignes du briefing (campagne).
+    pub briefing: Vec<String>,
+    /// Score de référence pour les étoiles (campagne).
+    pub par: Option<u32>,
+}
+
+impl Level {
+    /// Dossier contenant les niveaux.
+    pub const FOLDER: &'static str = "assets/levels";
+
+    /// Lit un niveau.
+    ///
+    /// # Arguments
+    /// - `content` Le contenu du fichier de niveau.
+    ///
+    /// # Returns
+    /// - `Ok(Level)` Le niveau lu.
+    /// - `Err(Vec<String>)` Les erreurs rencontrées, avec leur numéro de ligne.
+    ///
+    pub fn parse(content: &str) -> Result<Self, Vec<String>> {
+        let mut level = Level::default();
+        let mut errors = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Err(e) = level.parse_line(line) {
+                errors.push(format!("line {}: {}", number + 1, e));
+            }
+        }
+        if level.events.is_empty() && level.goal == LevelGoal::ClearField {
+            errors.push("a level won by clearing the field needs at least one 'spawn'".to_string());
+        }
+        if matches!(level.goal, LevelGoal::Escort(_)) && level.cargo.is_none() {
+            errors.push("an 'escort' level needs a 'cargo' line".to_string());
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        level.events.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(level)
+    }
+
+    /// Lit une instruction du fichier de niveau.
+    ///
+    /// # Arguments
+    /// - `line` La ligne (sans commentaire).
+    ///
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim();
+            match key.trim() {
+                "name" => self.name = value.to_string(),
+                "flavor" => {
+                    self.flavor = match value {
+                        "classic" => "classic",
+                        "modern" => "modern",
+                        _ => return Err(format!("unknown flavor '{}'", value)),
+                    }
+                }
+                "background" => self.background = Some(value.to_string()),
+                "win" => self.goal = parse_goal(value)?,
+                "start" => match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    [x, y] => self.start = Some(parse_position(x, y)?),
+                    _ => return Err("expected 'start=<x> <y>'".to_string()),
+                },
+                "cargo" => match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    [x, y, vx, vy] => {
+                        self.cargo = Some((
+                            parse_position(x, y)?,
+                            vec2(parse_number(vx, "vx")?, parse_number(vy, "vy")?),
+                        ))
+                    }
+                    _ => return Err("expected 'cargo=<x> <y> <vx> <vy>'".to_string()),
+                },
+                "briefing" => self.briefing.push(value.to_string()),
+                "par" => self.par = Some(parse_number(value, "par")?),
+                key => return Err(format!("unknown setting '{}'", key)),
+            }
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["blackhole", x, y, extra @ ..] if extra.len() <= 2 => {
+                let size = match extra.first() {
+                    Some(size) => parse_number(size, "size")?,
+                    None => tuning().blackhole_size,
+                };
+                if size <= 0.0 {
+                    return Err("black hole size must be positive".to_string());
+                }
+                self.blackholes.push(PlacedBlackHole {
+                    position: parse_position(x, y)?,
+                    size,
+                    rotation: match extra.get(1) {
+                        Some(rotation) => parse_number(rotation, "rotation")?,
+                        None => 0.0,
+                    },
+                });
+            }
+            ["spawn", time, shape, size, origin @ ..] => {
+                let time: f64 = parse_number(time, "time")?;
+                let size: f32 = parse_number(size, "size")?;
+                if time < 0.0 || size <= 0.0 {
+                    return Err(
+                        "spawn time must not be negative and size must be positive".to_string()
+                    );
+                }
+                let shape = match *shape {
+                    "small" => AsteroidShape::Small,
+                    "medium" => AsteroidShape::Medium,
+                    "large" => AsteroidShape::Large,
+                    _ => return Err(format!("unknown asteroid shape '{}'", shape)),
+                };
+                let (origin, vx, vy, rotation) = match origin {
+                    ["at", x, y, vx, vy] => (SpawnOrigin::At(parse_position(x, y)?), vx, vy, 0.0),
+                    ["at", x, y, vx, vy, rotation] => (
+                        SpawnOrigin::At(parse_position(x, y)?),
+                        vx,
+                        vy,
+                        parse_number(rotation, "rotation")?,
+                    ),
+                    ["edge", side, vx, vy] => (SpawnOrigin::Edge(parse_edge(side)?), vx, vy, 0.0),
+                    _ => {
+                        return Err("expected 'at <x> <y> <vx> <vy>' or 'edge <side> <vx> <vy>'"
+                            .to_string())
+                    }
+                };
+                self.events.push(SpawnEvent {
+                    time,
+                    shape,
+                    size,
+                    origin,
+                    velocity: vec2(parse_number(vx, "vx")?, parse_number(vy, "vy")?),
+                    rotation,
+                });
+            }
+            [command, ..] => return Err(format!("unknown instruction '{}'", command)),
+            [] => {}
+        }
+        Ok(())
+    }
+
+    /// Écrit le niveau au format des fichiers de niveau (voir `parse`).
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("name={}", self.name),
+            format!("flavor={}", self.flavor),
+        ];
+        if let Some(background) = &self.background {
+            lines.push(format!("background={}", background));
+        }
+        lines.push(format!(
+            "win={}",
+            match self.goal {
+                LevelGoal::ClearField => "clear".to_string(),
+                LevelGoal::Survive(duration) => format!("survive {}", duration),
+                LevelGoal::ReachScore(score) => format!("score {}", score),
+                LevelGoal::DestroyLarge(count) => format!("destroy {}", count),
+                LevelGoal::Escort(duration) => format!("escort {}", duration),
+                LevelGoal::Beacon(beacon) => format!("beacon {} {}", beacon.x, beacon.y),
+            }
+        ));
+        if let Some(start) = self.start {
+            lines.push(format!("start={} {}", start.x, start.y));
+        }
+        if let Some((position, velocity)) = self.cargo {
+            lines.push(format!(
+                "cargo={} {} {} {}",
+                position.x, position.y, velocity.x, velocity.y
+            ));
+        }
+        for line in &self.briefing {
+            lines.push(format!("briefing={}", line));
+        }
+        if let Some(par) = self.par {
+            lines.push(format!("par={}", par));
+        }
+        lines.push(String::new());
+        for blackhole in &self.blackholes {
+            lines.push(format!(
+                "blackhole {} {} {} {}",
+                blackhole.position.x, blackhole.position.y, blackhole.size, blackhole.rotation
+            ));
+        }
+        for event in &self.events {
+            let shape = match event.shape {
+                AsteroidShape::Small => "small",
+                AsteroidShape::Medium => "medium",
+                AsteroidShape::Large => "large",
+            };
+            let origin = match event.origin {
+                SpawnOrigin::At(position) => format!("at {} {}", position.x, position.y),
+                SpawnOrigin::Edge(edge) => format!("edge {}", edge.name()),
+            };
+            let mut line = format!(
+                "spawn {} {} {} {} {} {}",
+                event.time, shape, event.size, origin, event.velocity.x, event.velocity.y
+            );
+            if matches!(event.origin, SpawnOrigin::At(_)) && event.rotation != 0.0 {
+                line.push_str(&format!(" {}", event.rotation));
+            }
+            lines.push(line);
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Sauvegarde le niveau dans un fichier (le dossier est créé si besoin).
+    ///
+    /// # Arguments
+    /// - `path` Le chemin du fichier.
+    ///
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// Charge un niveau depuis un fichier.
+    ///
+    /// # Arguments
+    /// - `path` Le chemin du fichier.
+    ///
+    pub fn load(path: &Path) -> Result<Self, Vec<String>> {
+        let content = fs::read_to_string(path).map_err(|e| vec![e.to_string()])?;
+        Self::parse(&content)
+    }
+
+    /// Charge tous les niveaux du dossier `assets/levels`, triés par nom de fichier
+    /// (les niveaux invalides sont conservés avec leurs erreurs).
+    pub fn load_all() -> Vec<(PathBuf, Result<Self, Vec<String>>)> {
+        Self::load_folder(Self::FOLDER)
+    }
+
+    /// Charge tous les niveaux d'un dossier, triés par nom de fichier
+    /// (les niveaux invalides sont conservés avec leurs erreurs).
+    ///
+    /// # Arguments
+    /// - `folder` Le dossier des fichiers `.level`.
+    ///
+    pub fn load_folder(folder: &str) -> Vec<(PathBuf, Result<Self, Vec<String>>)> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(folder)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "level"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let level = Self::load(&path);
+                (path, level)
+            })
+            .collect()
+    }
+
+    /// Règles de la partie pour ce niveau (les apparitions sont gérées par le niveau).
+    pub fn rules(&self) -> ModeRules {
+        let base = ModeRules::for_mode(self.flavor).unwrap_or(ModeRules::ALL[1]);
+        ModeRules {
+            name: "level",
+            title: "Level",
+            spawn_until: None,
+            clear_after: (self.goal == LevelGoal::ClearField).then_some(0.0),
+            time_limit: match self.goal {
+                LevelGoal::Survive(duration) | LevelGoal::Escort(duration) => Some(duration),
+                _ => None,
+            },
+            score_target: match self.goal {
+                LevelGoal::ReachScore(score) => Some(score),
+                _ => None,
+            },
+            ore_shop: false,
+            bosses: false,
+            ..base
+        }
+    }
+
+    /// Trous noirs présents au début du niveau.
+    ///
+    /// # Arguments
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn place_blackholes(&self, screen: Vec2) -> Vec<BlackHole> {
+        self.blackholes
+            .iter()
+            .map(|placed| BlackHole {
+                position: placed.position * screen,
+                size: placed.size,
+                rotation: placed.rotation,
+            })
+            .collect()
+    }
+}
+
+impl Default for Level {
+    /// Niveau vide, gagné en nettoyant le terrain.
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            flavor: "modern",
+            background: None,
+            goal: LevelGoal::ClearField,
+            start: None,
+            blackholes: Vec::new(),
+            events: Vec::new(),
+            cargo: None,
+            briefing: Vec::new(),
+            par: None,
+        }
+    }
+}
+
+/// Lit une condition de victoire ('clear', 'survive <secondes>', 'score <points>',
+/// 'destroy <nombre>', 'escort <secondes>' ou 'beacon <x> <y>').
+fn parse_goal(value: &str) -> Result<LevelGoal, String> {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    match words.as_slice() {
+        ["clear"] => Ok(LevelGoal::ClearField),
+        ["survive", duration] => match parse_number::<f64>(duration, "duration")? {
+            duration if duration > 0.0 => Ok(LevelGoal::Survive(duration)),
+            _ => Err("survival duration must be positive".to_string()),
+        },
+        ["score", score] => Ok(LevelGoal::ReachScore(parse_number(score, "score")?)),
+        ["destroy", count] => match parse_number::<u32>(count, "count")? {
+            0 => Err("the number of large asteroids must be positive".to_string()),
+            count => Ok(LevelGoal::DestroyLarge(count)),
+        },
+        ["escort", duration] => match parse_number::<f64>(duration, "duration")? {
+            duration if duration > 0.0 => Ok(LevelGoal::Escort(duration)),
+            _ => Err("escort duration must be positive".to_string()),
+        },
+        ["beacon", x, y] => Ok(LevelGoal::Beacon(parse_position(x, y)?)),
+        _ => Err(format!(
+            "invalid win condition '{}' (expected 'clear', 'survive <seconds>', 'score <points>', \
+             'destroy <count>', 'escort <seconds>' or 'beacon <x> <y>')",
+            value
+        )),
+    }
+}
+
+/// Lit une position (fractions de l'écran, entre 0 et 1).
+fn parse_position(x: &str, y: &str) -> Result<Vec2, String> {
+    let position = vec2(parse_number(x, "x")?, parse_number(y, "y")?);
+    if !(0.0..=1.0).contains(&position.x) || !(0.0..=1.0).contains(&position.y) {
+        return Err(format!(
+            "position ({}, {}) must be between 0 and 1 (fraction of the screen)",
+            x, y
+        ));
+    }
+    Ok(position)
+}
+
+/// Lit un bord de l'écran ('top', 'bottom', 'left' ou 'right').
+fn parse_edge(side: &str) -> Result<Edge, String> {
+    match side {
+        "top" => Ok(Edge::Top),
+        "bottom" => Ok(Edge::Bottom),
+        "left" => Ok(Edge::Left),
+        "right" => Ok(Edge::Right),
+        _ => Err(format!("unknown edge '{}'", side)),
+    }
+}
+
+/// Lit une valeur numérique.
+fn parse_number<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {} '{}'", name, value))
+}
+
+/// Déroulement des apparitions d'un niveau pendant la partie.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelScript {
+    /// Apparitions du niveau, triées par instant.
+    events: Vec<SpawnEvent>,
+    /// Indice de la prochaine apparition.
+    next: usize,
+}
+
+impl LevelScript {
+    /// Démarre le déroulement d'un niveau.
+    ///
+    /// # Arguments
+    /// - `level` Le niveau.
+    ///
+    pub fn new(level: &Level) -> Self {
+        Self {
+            events: level.events.clone(),
+            next: 0,
+        }
+    }
+
+    /// Fait apparaître les astéroïdes dont l'instant est passé.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `asteroids` Les astéroïdes.
+    /// - `screen` La taille de l'écran.
+    ///
+    pub fn spawn_due(&mut self, time: f64, asteroids: &mut Vec<Asteroid>, screen: Vec2) {
+        while let Some(event) = self
+            .events
+            .get(self.next)
+            .filter(|event| event.time <= time)
+        {
+            let position = match event.origin {
+                SpawnOrigin::At(position) => position * screen,
+                SpawnOrigin::Edge(edge) => {
+                    let mut rng = game_rng();
+                    let along_x = rng.gen_range(0.0..=screen.x);
+                    let along_y = rng.gen_range(0.0..=screen.y);
+                    match edge {
+                        Edge::Top => vec2(along_x, event.size),
+                        Edge::Bottom => vec2(along_x, screen.y - event.size),
+                        Edge::Left => vec2(event.size, along_y),
+                        Edge::Right => vec2(screen.x - event.size, along_y),
+                    }
+                }
+            };
+            let mut asteroid = Asteroid::new(position, event.velocity, event.shape, event.size);
+            if event.rotation != 0.0 {
+                asteroid.set_rotation(event.rotation);
+            }
+            asteroids.push(asteroid);
+            self.next += 1;
+        }
+    }
+
+    /// Vérifie si toutes les apparitions ont eu lieu.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.events.len()
+    }
+}
+
+/// Résultat d'une partie jouée sur un niveau.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelOutcome {
+    /// Vrai si la condition de victoire a été remplie.
+    pub won: bool,
+    /// Le score final.
+    pub score: u32,
+    /// Le nombre de coups reçus par le bouclier.
+    pub shield_hits: u32,
+    /// La durée de la partie, en secondes.
+    pub time: f64,
+}
+
+/// Source des apparitions d'astéroïdes d'une partie solo.
+pub enum Spawner {
+    /// Apparitions aléatoires, selon les règles du mode.
+    Random {
+        /// Le temps de la dernière apparition.
+        last_add: f64,
+    },
+    /// Apparitions d'un niveau.
+    Script(LevelScript),
+    /// Apparitions aléatoires lancées vers une cible (la station du mode défense).
+    Toward {
+        /// Le temps de la dernière apparition.
+        last_add: f64,
+        /// La position visée.
+        target: Vec2,
+    },
+}
+
+impl Spawner {
+    /// Fait apparaître les nouveaux astéroïdes.
+    ///
+    /// # Arguments
+    /// - `asteroids` Les astéroïdes.
+    /// - `time` Le temps de jeu.
+    /// - `rules` Les règles de la partie (apparitions aléatoires).
+    ///
+    pub fn update(&mut self, asteroids: &mut Vec<Asteroid>, time: f64, rules: &ModeRules) {
+        match self {
+            Spawner::Random { last_add } => {
+                if rules.spawns(time) {
+                    Asteroid::push_or_dont(
+                        asteroids,
+                        last_add,
+                        time,
+                        rules.spawn_interval(time),
+                        rules.size_bias,
+                    );
+                }
+            }
+            Spawner::Script(script) => {
+                script.spawn_due(time, asteroids, vec2(screen_width(), screen_height()))
+            }
+            Spawner::Toward { last_add, target } => {
+                if rules.spawns(time)
+                    && Asteroid::push_or_dont(
+                        asteroids,
+                        last_add,
+                        time,
+                        rules.spawn_interval(time),
+                        rules.size_bias,
+                    )
+                {
+                    if let Some(asteroid) = asteroids.last_mut() {
+                        asteroid.aim_at(*target);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Change la cible des apparitions dirigées (sans effet pour les autres apparitions).
+    ///
+    /// # Arguments
+    /// - `position` La nouvelle position visée.
+    ///
+    pub fn set_target(&mut self, position: Vec2) {
+        if let Spawner::Toward { target, .. } = self {
+            *target = position;
+        }
+    }
+
+    /// Vérifie si plus aucun astéroïde ne doit apparaître d'un niveau
+    /// (toujours vrai pour les apparitions aléatoires, gérées par les règles).
+    pub fn is_finished(&self) -> bool {
+        match self {
+            Spawner::Random { .. } | Spawner::Toward { .. } => true,
+            Spawner::Script(script) => script.is_finished(),
+        }
+    }
+}
+
+/// Sélecteur de niveau.
+/// Les niveaux invalides sont listés avec leurs erreurs (et ne peuvent pas être lancés).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some((PathBuf, Level))` Le fichier et le niveau choisis.
+/// - `None` si le joueur revient en arrière.
+///
+pub async fn level_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<(PathBuf, Level)> {
+    let levels = Level::load_all();
+    let mut selected_index = 0;
+    sound.play_sound_effect("select_menu");
+
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Levels", None, 50, 1.0);
+        draw_text(
+            "Levels",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        if levels.is_empty() {
+            draw_text(
+                &format!("No level found in {}", Level::FOLDER),
+                screen_width() / 2.0 - 200.0,
+                350.0,
+                30.0,
+                GRAY,
+            );
+        }
+
+        for (i, (path, level)) in levels.iter().enumerate() {
+            let label = match level {
+                Ok(level) => level.name.clone(),
+                Err(_) => format!("{} (invalid)", path.display()),
+            };
+            let color = match (i == selected_index, level.is_ok()) {
+                (true, _) => YELLOW,
+                (false, true) => WHITE,
+                (false, false) => RED,
+            };
+            draw_text(
+                &label,
+                screen_width() / 2.0 - 200.0,
+                300.0 + i as f32 * 45.0,
+                40.0,
+                color,
+            );
+        }
+        if let Some((_, Err(errors))) = levels.get(selected_index) {
+            for (i, error) in errors.iter().take(8).enumerate() {
+                draw_text(
+                    error,
+                    screen_width() / 2.0 - 200.0,
+                    screen_height() - 250.0 + i as f32 * 25.0,
+                    25.0,
+                    RED,
+                );
+            }
+        }
+
+        if is_key_pressed(KeyCode::Down) && !levels.is_empty() {
+            selected_index = (selected_index + 1) % levels.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) && !levels.is_empty() {
+            selected_index = if selected_index == 0 {
+                levels.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            if let Some((path, Ok(level))) = levels.get(selected_index) {
+                return Some((path.clone(), level.clone()));
+            }
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_and_run_script() {
+        let content = "name=Test\nflavor=classic\nwin=survive 30\n\nblackhole 0.5 0.5\n\
+                       spawn 2 large 60 at 0.5 0.25 1 0 # commentaire\n\
+                       spawn 1 small 20 edge left 1 0\n";
+        let level = Level::parse(content).unwrap();
+        assert_eq!(level.name, "Test");
+        assert_eq!(level.goal, LevelGoal::Survive(30.0));
+        assert_eq!(level.rules().time_limit, Some(30.0));
+        assert_eq!(level.rules().flavor, "classic");
+        assert_eq!(level.events[0].time, 1.0);
+
+        let mut script = LevelScript::new(&level);
+        let mut asteroids = Vec::new();
+        let screen = vec2(800.0, 600.0);
+        script.spawn_due(0.5, &mut asteroids, screen);
+        assert!(asteroids.is_empty());
+        script.spawn_due(2.0, &mut asteroids, screen);
+        assert_eq!(asteroids.len(), 2);
+        assert_eq!(asteroids[0].position.x, 20.0);
+        assert_eq!(asteroids[1].position, vec2(400.0, 150.0));
+        assert!(script.is_finished());
+    }
+
+    #[test]
+    fn test_parse_errors_point_to_lines() {
+        let content = "name=Broken\nwin=forever\nspawn 1 huge 30 at 0 0 0 0\n\nblackhole 2 0.5\nlaser 1\nspawn 1 small 20 edge top";
+        assert_eq!(
+            Level::parse(content).unwrap_err(),
+            vec![
+                "line 2: invalid win condition 'forever' (expected 'clear', 'survive <seconds>', 'score <points>', \
+                 'destroy <count>', 'escort <seconds>' or 'beacon <x> <y>')",
+                "line 3: unknown asteroid shape 'huge'",
+                "line 5: position (2, 0.5) must be between 0 and 1 (fraction of the screen)",
+                "line 6: unknown instruction 'laser'",
+                "line 7: expected 'at <x> <y> <vx> <vy>' or 'edge <side> <vx> <vy>'",
+                "a level won by clearing the field needs at least one 'spawn'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundled_levels_are_valid() {
+        let levels = Level::load_all();
+        assert!(!levels.is_empty());
+        for (path, level) in levels {
+            assert!(level.is_ok(), "{}: {:?}", path.display(), level);
+        }
+    }
+}
@@ -0,0 +1,821 @@
+//! # Lobby Module
+//!
+//! Ce module contient les écrans du multijoueur en réseau (héberger ou rejoindre une partie)
+//! ainsi que les boucles de jeu de l'hôte et des clients.
+//! Les règles sont celles du mode 'classic', avec formation de trous noirs,
+//! à la difficulté choisie par l'hôte.
+use super::draw_background;
+use super::draw_time;
+use crate::bind::{Action, Bind};
+use crate::blackhole::BlackHole;
+use crate::difficulty::Difficulty;
+use crate::gui::{draw_asteroid_number, draw_player_panel};
+use crate::network::{
+    reconcile, ClientEvent, NetClient, NetHost, WorldSnapshot, WorldView, DEFAULT_PORT,
+    MAX_PLAYERS, NET_SCHEME,
+};
+use crate::rules::ModeRules;
+use crate::score::{add_score, finalize_score};
+use crate::settings::Settings;
+use crate::shipclass::ShipDefinition;
+use crate::sound::Sound;
+use crate::spaceship::ShipInput;
+use crate::stellarobject::StellarObject;
+use crate::utils::{background_asteroids, handle_enter_press};
+use crate::{asteroid::Asteroid, missile::Missile, spaceship::Spaceship};
+use macroquad::prelude::*;
+use std::time::Instant;
+
+/// Couleurs associées à chaque joueur en réseau (panneaux et missiles).
+pub const NET_PLAYER_COLORS: [Color; MAX_PLAYERS] = [SKYBLUE, ORANGE, LIME, PINK];
+
+/// Position de départ d'un joueur (alignés au centre de l'écran).
+///
+/// # Arguments
+/// - `player_id` L'indice du joueur.
+///
+fn spawn_position(player_id: usize) -> Vec2 {
+    let offset = (player_id as f32 - (MAX_PLAYERS - 1) as f32 / 2.0) * 120.0;
+    vec2(screen_width() / 2.0 + offset, screen_height() / 2.0)
+}
+
+/// Règles des parties en réseau : celles du mode 'classic', avec formation de trous noirs.
+///
+/// # Arguments
+/// - `difficulty` Le niveau de difficulté choisi par l'hôte.
+///
+fn net_rules(difficulty: Difficulty) -> ModeRules {
+    let classic = ModeRules::for_mode("classic").unwrap_or(ModeRules::ALL[0]);
+    ModeRules {
+        black_holes: true,
+        ..classic
+    }
+    .with_difficulty(difficulty)
+}
+
+/// Monde simulé par l'hôte.
+struct NetWorld {
+    rules: ModeRules,
+    ships: Vec<Spaceship>,
+    last_shoot: [f64; MAX_PLAYERS],
+    asteroids: Vec<Asteroid>,
+    blackholes: Vec<BlackHole>,
+    missiles: Vec<Missile>,
+    last_add_asteroid: f64,
+}
+
+impl NetWorld {
+    /// Créer un monde vide, avec un vaisseau par joueur possible.
+    ///
+    /// # Arguments
+    /// - `rules` Les règles de la partie.
+    ///
+    fn new(rules: ModeRules) -> Self {
+        let ships = (0..MAX_PLAYERS)
+            .map(|player_id| {
+                let mut ship = Spaceship::new(&ShipDefinition::default());
+                ship.set_position(spawn_position(player_id));
+                ship
+            })
+            .collect();
+        Self {
+            rules,
+            ships,
+            last_shoot: [0.0; MAX_PLAYERS],
+            asteroids: Vec::new(),
+            blackholes: Vec::new(),
+            missiles: Vec::new(),
+            last_add_asteroid: 0.0,
+        }
+    }
+
+    /// Vue sur le monde pour l'envoi aux clients.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `active` Les joueurs connectés.
+    /// - `over` Vrai si la partie est terminée.
+    /// - `won` Vrai si les joueurs ont gagné.
+    ///
+    fn view(
+        &self,
+        time: f64,
+        active: &[bool; MAX_PLAYERS],
+        over: bool,
+        won: bool,
+    ) -> WorldView<'_> {
+        WorldView {
+            time,
+            over,
+            won,
+            ships: self
+                .ships
+                .iter()
+                .enumerate()
+                .filter(|(player_id, _)| active[*player_id])
+                .collect(),
+            asteroids: &self.asteroids,
+            blackholes: &self.blackholes,
+            missiles: &self.missiles,
+        }
+    }
+
+    /// Avance la simulation d'une frame.
+    /// Chaque commande reçue d'un joueur fait avancer son vaisseau d'un pas,
+    /// exactement comme la prédiction du client.
+    ///
+    /// # Arguments
+    /// - `inputs` Les commandes reçues de chaque joueur.
+    /// - `active` Les joueurs connectés.
+    /// - `time` Le temps de jeu.
+    /// - `sound` Les sons du jeu.
+    ///
+    /// # Returns
+    /// - `true` si tous les joueurs sont éliminés.
+    ///
+    async fn step(
+        &mut self,
+        inputs: &[Vec<ShipInput>; MAX_PLAYERS],
+        active: &[bool; MAX_PLAYERS],
+        time: f64,
+        sound: &Sound,
+    ) -> bool {
+        if self.rules.spawns(time)
+            && Asteroid::push_or_dont(
+                &mut self.asteroids,
+                &mut self.last_add_asteroid,
+                time,
+                self.rules.spawn_interval(time),
+                self.rules.size_bias,
+            )
+        {
+            self.last_add_asteroid = time;
+        }
+        for asteroid in &mut self.asteroids {
+            asteroid.move_asteroid(true, self.rules.asteroid_speed(time));
+        }
+        if self.rules.black_holes {
+            Asteroid::what_collide_asteroids(
+                &mut self.asteroids,
+                &mut self.blackholes,
+                self.rules.difficulty.blackhole_chance(),
+            );
+            for blackhole in &mut self.blackholes {
+                blackhole.update_rotation();
+            }
+        }
+
+        for (player_id, ship) in self.ships.iter_mut().enumerate() {
+            if !active[player_id] || ship.shield == 0 {
+                continue;
+            }
+            for input in &inputs[player_id] {
+                ship.apply_input(NET_SCHEME, input);
+                if input.fire
+                    && time - self.last_shoot[player_id] > ship.get_definition().fire_delay
+                {
+                    let volley = Missile::fire(ship, self.rules.missile_speed, player_id);
+                    ship.register_shots(volley.len());
+                    self.missiles.extend(volley);
+                    self.last_shoot[player_id] = time;
+                    sound.play_sound_effect("shoot");
+                }
+            }
+            ship.asteroid_impact(&mut self.asteroids, time, sound).await;
+            if ship
+                .spaceship_blackhole_collision(&mut self.blackholes)
+                .await
+            {
+                ship.shield = 0;
+            }
+        }
+
+        for missile in &mut self.missiles {
+            missile.move_missile();
+        }
+        Missile::clear_missiles(&mut self.missiles);
+        Missile::what_collide_missile(
+            &mut self.missiles,
+            &mut self.asteroids,
+            &mut self.ships,
+            sound,
+            time,
+        );
+
+        for ship in &mut self.ships {
+            let bonus = ship.score_engine.update(time, self.asteroids.is_empty());
+            add_score(ship, bonus);
+        }
+
+        !self
+            .ships
+            .iter()
+            .enumerate()
+            .any(|(player_id, ship)| active[player_id] && ship.shield > 0)
+    }
+}
+
+/// Affichage d'une partie en réseau (identique pour l'hôte et les clients).
+///
+/// # Arguments
+/// - `textures` Les textures (astéroids, trous noirs, arrière-plan, vaisseaux).
+/// - `time` Le temps de jeu.
+/// - `ships` Les vaisseaux en jeu, avec l'indice de leur joueur.
+/// - `asteroids` Les astéroids.
+/// - `blackholes` Les trous noirs.
+/// - `missiles` Les missiles.
+///
+fn draw_network(
+    textures: [&Texture2D; 4],
+    time: f64,
+    ships: &[(usize, &Spaceship)],
+    asteroids: &[Asteroid],
+    blackholes: &[BlackHole],
+    missiles: &[Missile],
+) {
+    draw_background(textures[2]);
+    for asteroid in asteroids {
+        asteroid.draw_asteroid(textures[0]);
+    }
+    for blackhole in blackholes {
+        blackhole.draw_blackhole(textures[1]);
+    }
+    for (_, ship) in ships.iter().filter(|(_, ship)| ship.shield > 0) {
+        ship.draw_spaceship(textures[3], time);
+    }
+    for missile in missiles {
+        missile.draw_missile_with_color(NET_PLAYER_COLORS[missile.owner % MAX_PLAYERS]);
+    }
+
+    draw_time(&format!("{:.2}", time));
+    draw_asteroid_number(asteroids);
+    for (player_id, ship) in ships {
+        draw_player_panel(
+            &format!("P{}", player_id + 1),
+            ship,
+            10.0 + *player_id as f32 * 260.0,
+            NET_PLAYER_COLORS[*player_id % MAX_PLAYERS],
+            time,
+        );
+    }
+}
+
+/// Charge les textures d'une partie (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+///
+/// # Arguments
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+///
+pub async fn load_textures(skins: [&str; 4]) -> [Texture2D; 4] {
+    let mut textures = Vec::with_capacity(4);
+    for (i, skin) in skins.iter().enumerate() {
+        let texture = load_texture(skin).await.unwrap();
+        texture.set_filter(if i == 2 {
+            FilterMode::Nearest
+        } else {
+            FilterMode::Linear
+        });
+        textures.push(texture);
+    }
+    textures.try_into().unwrap()
+}
+
+/// Affiche un message jusqu'à ce que le joueur appuie sur 'Entrée'.
+///
+/// # Arguments
+/// - `text` Le message.
+/// - `sound` Les sons du jeu.
+///
+pub async fn show_message(text: &str, sound: &Sound) {
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        for (i, line) in [text, "Press 'Enter' to continue"].iter().enumerate() {
+            let dimension = measure_text(line, None, 40, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                screen_height() / 2.0 + i as f32 * 80.0,
+                40.0,
+                if i == 0 { WHITE } else { YELLOW },
+            );
+        }
+        if handle_enter_press(&mut last_enter_press) || is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+/// Écran de fin d'une partie en réseau (score de chaque joueur).
+///
+/// # Arguments
+/// - `ships` Les vaisseaux en jeu, avec l'indice de leur joueur.
+/// - `won` Vrai si les joueurs ont gagné.
+/// - `sound` Les sons du jeu.
+/// - `on_frame` Appelée à chaque frame (l'hôte continue d'envoyer l'état final).
+///
+async fn network_game_over(
+    ships: &[(usize, &Spaceship)],
+    won: bool,
+    sound: &Sound,
+    mut on_frame: impl FnMut(),
+) {
+    let (text, color) = if won {
+        sound.play_sound_effect("win");
+        ("You Won !", Color::from_rgba(0, 213, 109, 1))
+    } else {
+        sound.play_sound_effect("gameover");
+        ("Game Over", RED)
+    };
+    let mut last_enter_press = Instant::now();
+    loop {
+        on_frame();
+        clear_background(color);
+        let dimension = measure_text(text, None, 60, 1.0);
+        draw_text(
+            text,
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            60.0,
+            WHITE,
+        );
+        for (i, (player_id, ship)) in ships.iter().enumerate() {
+            let line = format!("Player {} : {}", player_id + 1, ship.score);
+            let dimension = measure_text(&line, None, 40, 1.0);
+            draw_text(
+                &line,
+                (screen_width() - dimension.width) / 2.0,
+                300.0 + i as f32 * 50.0,
+                40.0,
+                NET_PLAYER_COLORS[*player_id % MAX_PLAYERS],
+            );
+        }
+        let text2 = "Press 'Enter' to return to the menu";
+        let dimension = measure_text(text2, None, 40, 1.0);
+        draw_text(
+            text2,
+            (screen_width() - dimension.width) / 2.0,
+            600.0,
+            40.0,
+            WHITE,
+        );
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+/// Boucle de jeu de l'hôte : simule le monde et l'envoie aux clients à chaque frame.
+///
+/// # Arguments
+/// - `host` L'hôte (clients déjà connectés).
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+/// - `sound` Les sons du jeu.
+/// - `keybinding` Les touches du joueur local.
+///
+async fn play_host(mut host: NetHost, skins: [&str; 4], sound: &Sound, keybinding: &Bind) {
+    let textures = load_textures(skins).await;
+    let textures = [&textures[0], &textures[1], &textures[2], &textures[3]];
+    let mut world = NetWorld::new(net_rules(Settings::load().difficulty));
+    let start_time = get_time();
+
+    loop {
+        let time = get_time() - start_time;
+        host.poll();
+        if keybinding.is_pressed(Action::Quit) {
+            host.close();
+            return;
+        }
+
+        let mut active = [false; MAX_PLAYERS];
+        let mut inputs: [Vec<ShipInput>; MAX_PLAYERS] = Default::default();
+        active[0] = true;
+        inputs[0] = vec![ShipInput::from_keyboard(keybinding, NET_SCHEME)];
+        for player_id in 1..MAX_PLAYERS {
+            active[player_id] = host.is_connected(player_id);
+            inputs[player_id] = host.take_inputs(player_id);
+        }
+
+        let lost = world.step(&inputs, &active, time, sound).await;
+        let won = !lost && world.rules.is_won(time, world.asteroids.is_empty(), 0);
+        if lost || won {
+            for ship in &mut world.ships {
+                finalize_score(ship);
+            }
+        }
+        let view = world.view(time, &active, lost || won, won);
+        let encoded = view.encode();
+        host.broadcast(&encoded);
+
+        if lost || won {
+            network_game_over(&view.ships, won, sound, || {
+                host.poll();
+                host.broadcast(&encoded);
+            })
+            .await;
+            host.close();
+            return;
+        }
+
+        draw_network(
+            textures,
+            time,
+            &view.ships,
+            view.asteroids,
+            view.blackholes,
+            view.missiles,
+        );
+        next_frame().await;
+    }
+}
+
+/// Boucle de jeu d'un client : envoie les commandes du joueur, prédit son vaisseau
+/// et affiche le dernier état reçu de l'hôte.
+///
+/// # Arguments
+/// - `client` Le client (connexion acceptée).
+/// - `player_id` L'indice du joueur local.
+/// - `snapshot` Le premier état du monde reçu.
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+/// - `sound` Les sons du jeu.
+/// - `keybinding` Les touches du joueur local.
+///
+async fn play_client(
+    mut client: NetClient,
+    player_id: usize,
+    mut snapshot: WorldSnapshot,
+    skins: [&str; 4],
+    sound: &Sound,
+    keybinding: &Bind,
+) {
+    let textures = load_textures(skins).await;
+    let textures = [&textures[0], &textures[1], &textures[2], &textures[3]];
+    let mut predicted = Spaceship::new(&ShipDefinition::default());
+    if let Some(ship) = snapshot.get_ship(player_id) {
+        reconcile(&mut predicted, ship, client.pending_inputs());
+    }
+
+    loop {
+        for event in client.poll() {
+            match event {
+                ClientEvent::Snapshot(world) => {
+                    if let Some(ship) = world.get_ship(player_id) {
+                        reconcile(&mut predicted, ship, client.pending_inputs());
+                    }
+                    snapshot = world;
+                }
+                ClientEvent::HostLeft => {
+                    show_message("The host left the game", sound).await;
+                    return;
+                }
+                ClientEvent::Welcome | ClientEvent::Refused => {}
+            }
+        }
+        if client.is_timed_out() {
+            show_message("Connection to the host lost", sound).await;
+            return;
+        }
+        if keybinding.is_pressed(Action::Quit) {
+            client.leave();
+            return;
+        }
+
+        let ships: Vec<(usize, &Spaceship)> = snapshot
+            .ships
+            .iter()
+            .map(|(id, ship)| (*id, if *id == player_id { &predicted } else { ship }))
+            .collect();
+        if snapshot.over {
+            network_game_over(&ships, snapshot.won, sound, || {}).await;
+            client.leave();
+            return;
+        }
+        draw_network(
+            textures,
+            snapshot.time,
+            &ships,
+            &snapshot.asteroids,
+            &snapshot.blackholes,
+            &snapshot.missiles,
+        );
+
+        let input = ShipInput::from_keyboard(keybinding, NET_SCHEME);
+        if predicted.shield > 0 {
+            predicted.apply_input(NET_SCHEME, &input);
+        }
+        client.send_input(input);
+        next_frame().await;
+    }
+}
+
+/// Salon de l'hôte : attend les joueurs puis lance la partie avec 'Entrée'.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `keybinding` Les touches du joueur local.
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+///
+async fn host_lobby(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    keybinding: &Bind,
+    skins: [&str; 4],
+) {
+    let mut host = match NetHost::bind(DEFAULT_PORT) {
+        Ok(host) => host,
+        Err(e) => {
+            show_message(&format!("Unable to host : {}", e), sound).await;
+            return;
+        }
+    };
+    let mut last_enter_press = Instant::now();
+    loop {
+        host.poll();
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let mut lines = vec![
+            format!("Hosting on port {}", host.port()),
+            "Player 1 : you".to_string(),
+        ];
+        for (player_id, address) in host.players() {
+            lines.push(format!("Player {} : {}", player_id + 1, address));
+        }
+        lines.push("Press 'Enter' to start or 'Backspace' to cancel".to_string());
+        for (i, line) in lines.iter().enumerate() {
+            let font_size = if i == 0 { 50 } else { 35 };
+            let dimension = measure_text(line, None, font_size, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                200.0 + i as f32 * 70.0,
+                font_size as f32,
+                if i == lines.len() - 1 { YELLOW } else { WHITE },
+            );
+        }
+
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            play_host(host, skins, sound, keybinding).await;
+            return;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            host.close();
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+/// Saisie de l'adresse d'un hôte (`ip` ou `ip:port`).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some(String)` L'adresse saisie.
+/// - `None` si le joueur revient au menu ('Échap').
+///
+pub async fn enter_address(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<String> {
+    let mut address = String::from("127.0.0.1");
+    let mut last_enter_press = Instant::now();
+    while get_char_pressed().is_some() {}
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        while let Some(c) = get_char_pressed() {
+            if c.is_ascii_alphanumeric() || c == '.' || c == ':' || c == '-' {
+                address.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            address.pop();
+        }
+
+        let lines = [
+            "Host address".to_string(),
+            format!("{}_", address),
+            "Press 'Enter' to join or 'Esc' to cancel".to_string(),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let font_size = if i == 0 { 50 } else { 40 };
+            let dimension = measure_text(line, None, font_size, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                200.0 + i as f32 * 150.0,
+                font_size as f32,
+                if i == 1 { YELLOW } else { WHITE },
+            );
+        }
+
+        if !address.is_empty() && handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            return Some(address);
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        next_frame().await;
+    }
+}
+
+/// Salon d'un client : se connecte à l'hôte puis attend le lancement de la partie.
+///
+/// # Arguments
+/// - `address` L'adresse de l'hôte.
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `keybinding` Les touches du joueur local.
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+///
+async fn client_lobby(
+    address: &str,
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    keybinding: &Bind,
+    skins: [&str; 4],
+) {
+    let mut client = match NetClient::connect(address) {
+        Ok(client) => client,
+        Err(e) => {
+            show_message(&format!("Unable to join {} : {}", address, e), sound).await;
+            return;
+        }
+    };
+    loop {
+        for event in client.poll() {
+            match event {
+                ClientEvent::Snapshot(world) => {
+                    let player_id = client.get_player_id().unwrap_or(1);
+                    play_client(client, player_id, world, skins, sound, keybinding).await;
+                    return;
+                }
+                ClientEvent::Refused => {
+                    show_message("The game is full", sound).await;
+                    return;
+                }
+                ClientEvent::HostLeft => {
+                    show_message("The host closed the game", sound).await;
+                    return;
+                }
+                ClientEvent::Welcome => sound.play_sound_effect("select_menu"),
+            }
+        }
+        if client.is_timed_out() {
+            show_message(&format!("No answer from {}", address), sound).await;
+            return;
+        }
+
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+        let status = match client.get_player_id() {
+            Some(player_id) => format!(
+                "Connected as Player {}, waiting for the host...",
+                player_id + 1
+            ),
+            None => format!("Connecting to {}...", address),
+        };
+        for (i, line) in [status.as_str(), "Press 'Backspace' to cancel"]
+            .iter()
+            .enumerate()
+        {
+            let dimension = measure_text(line, None, 40, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                300.0 + i as f32 * 100.0,
+                40.0,
+                if i == 0 { WHITE } else { YELLOW },
+            );
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            client.leave();
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+/// Menu du multijoueur en réseau : héberger ou rejoindre une partie.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `keybinding` Les touches du joueur local.
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseaux).
+///
+pub async fn lobby_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    keybinding: &Bind,
+    skins: [&str; 4],
+) {
+    let options = ["Host", "Join", "Back"];
+    let mut selected_index = 0;
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Multiplayer", None, 50, 1.0);
+        draw_text(
+            "Multiplayer",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        for (i, &option) in options.iter().enumerate() {
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            draw_text(
+                option,
+                screen_width() / 2.0 - 100.0,
+                350.0 + i as f32 * 50.0,
+                40.0,
+                color,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % options.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                options.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            match selected_index {
+                0 => {
+                    host_lobby(
+                        background_texture,
+                        asteroid_texture,
+                        asteroids,
+                        sound,
+                        keybinding,
+                        skins,
+                    )
+                    .await
+                }
+                1 => {
+                    if let Some(address) =
+                        enter_address(background_texture, asteroid_texture, asteroids, sound).await
+                    {
+                        client_lobby(
+                            &address,
+                            background_texture,
+                            asteroid_texture,
+                            asteroids,
+                            sound,
+                            keybinding,
+                            skins,
+                        )
+                        .await;
+                    }
+                }
+                _ => return,
+            }
+        }
+        next_frame().await;
+    }
+}
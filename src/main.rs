@@ -1,33 +1,85 @@
 // Modules
+mod achievement;
 mod asteroid;
+mod autopilot;
 mod bind;
 mod blackhole;
+mod boss;
+mod campaign;
 mod classic;
+mod control;
+mod coop;
+mod custom;
+mod daily;
+mod difficulty;
+mod director;
+mod editor;
 mod gui;
+mod highscore;
 mod import;
+mod level;
+mod lobby;
+mod material;
 mod menu;
 mod missile;
 mod modern;
+mod network;
 mod option;
+mod ore;
+mod rng;
+mod rules;
+mod save;
 mod score;
+mod settings;
+mod shield;
+mod shipclass;
+mod shop;
 mod skins;
 mod sound;
 mod spaceship;
+mod spectate;
+mod station;
+mod stats;
 mod stellarobject;
+mod tuning;
 mod utils;
+mod versus;
 
 // Dépendances externes
 use crate::blackhole::BlackHole;
+use achievement::{Achievements, GameContext};
 use asteroid::Asteroid;
-use bind::Bind;
+use asteroid::AsteroidShape;
+use autopilot::{AiSkill, Autopilot, Situation};
+use bind::{Action, Bind};
+use boss::Boss;
 use classic::{draw_classic, update_classic};
-use gui::{draw_time, game_over, pause};
+use custom::CustomConfig;
+use daily::DailyChallenge;
+use difficulty::Difficulty;
+use director::Director;
+use gui::{
+    draw_autopilot, draw_beacon, draw_boss_health, draw_objective, draw_ore, draw_station_health,
+    draw_time, draw_time_left, draw_tuning_errors, game_over, pause,
+};
+use highscore::HighScores;
+use level::{GoalProgress, Level, LevelGoal, LevelOutcome, LevelScript, Spawner};
 use macroquad::prelude::*;
 use menu::menu;
 use modern::{draw_modern, update_modern};
+use network::WorldView;
+use ore::Ore;
+use rules::ModeRules;
+use score::{add_score, finalize_score, get_score, wave_number};
+use settings::Settings;
+use shop::shop;
 use skins::skins;
 use sound::Sound;
-use spaceship::Spaceship;
+use spaceship::{ShipInput, Spaceship};
+use spectate::{SpectatorServer, SPECTATOR_PORT};
+use station::{Station, StationKind};
+use stats::LifetimeStats;
+use stellarobject::StellarObject;
 
 /// Dessine l'arrière-plan du jeu.
 ///
@@ -46,150 +98,466 @@ fn draw_background(background_texture: &Texture2D) {
     );
 }
 
-/// Boucle principale de jeu pour les modes "classic" et "modern".
+/// Termine la partie : attribue les bonus de fin de partie et ajoute les statistiques
+/// de la session aux statistiques cumulées (sauvegardées sur le disque).
 ///
 /// # Paramètres
-/// - `mode`: &str - Mode de jeu sélectionné ("classic" ou "modern").
+/// - `spaceship`: &mut Spaceship - Le vaisseau du joueur.
+/// - `time`: f64 - Temps de jeu écoulé.
+///
+/// # Retourne
+/// `LifetimeStats` - Les statistiques cumulées mises à jour.
+fn end_session(spaceship: &mut Spaceship, time: f64) -> LifetimeStats {
+    finalize_score(spaceship);
+    spaceship.stats.survival_time = time;
+    let mut lifetime = LifetimeStats::load();
+    lifetime.add_session(&spaceship.stats);
+    lifetime.save();
+    lifetime
+}
+
+/// Boucle principale de jeu pour les modes solo.
+///
+/// # Paramètres
+/// - `mode`: &str - Mode de jeu sélectionné ("classic", "modern", "time_attack", "survival", "zen", "defend", "daily", "custom", "level" ou "campaign").
 /// - `skins`: [&str; 4] - Chemins des fichiers pour les skins (astéroïdes, trous noirs, arrière-plan, vaisseau).
 /// - `start_time`: f64 - Temps de démarrage du jeu.
 /// - `sound`: &mut Sound - Gestionnaire des sons.
-/// - `bind`: &mut Bind - Gestionnaire des commandes utilisateur.
-async fn play(mode: &str, skins: [&str; 4], start_time: f64, sound: &mut Sound, bind: &mut Bind) {
+/// - `bindings`: &mut [Bind; 2] - Gestionnaire des commandes utilisateur (le premier joueur utilise le premier Bind).
+/// - `level`: Option<&Level> - Le niveau joué (modes "level" et "campaign"), qui remplace les apparitions aléatoires.
+/// - `autopilot`: Option<AiSkill> - Le niveau du pilote automatique qui remplace le clavier (mode "Watch AI") ;
+///   la partie n'est alors comptée ni dans les meilleurs scores, ni dans les succès, ni dans les statistiques.
+///   Il en va de même pour un défi quotidien déjà joué (entraînement).
+///
+/// # Retourne
+/// `Option<LevelOutcome>` - Le résultat de la partie, ou `None` si le joueur l'a quittée.
+/// En mode "campaign", l'écran de fin de partie est laissé au débriefing de la mission.
+async fn play(
+    mode: &str,
+    skins: [&str; 4],
+    start_time: f64,
+    sound: &mut Sound,
+    bindings: &mut [Bind; 2],
+    level: Option<&Level>,
+    autopilot: Option<AiSkill>,
+) -> Option<LevelOutcome> {
     // Chargement des textures
     let asteroid_texture = load_texture(skins[0]).await.unwrap();
     asteroid_texture.set_filter(FilterMode::Linear);
     let blackhole_texture = load_texture(skins[1]).await.unwrap();
     blackhole_texture.set_filter(FilterMode::Linear);
-    let background_texture = load_texture(skins[2]).await.unwrap();
+    let background_path = level
+        .and_then(|level| level.background.as_deref())
+        .unwrap_or(skins[2]);
+    let background_texture = match load_texture(background_path).await {
+        Ok(texture) => texture,
+        Err(_) => load_texture(skins[2]).await.unwrap(),
+    };
     background_texture.set_filter(FilterMode::Nearest);
     let spaceship_texture = load_texture(skins[3]).await.unwrap();
     spaceship_texture.set_filter(FilterMode::Linear);
 
+    // Réglages : classe du vaisseau, schéma de contrôle et diffusion aux spectateurs
+    let settings = Settings::load();
+
     // Initialisation des entités
-    let mut blackholes: Vec<BlackHole> = Vec::new();
+    let screen = vec2(screen_width(), screen_height());
+    let mut blackholes: Vec<BlackHole> = level
+        .map(|level| level.place_blackholes(screen))
+        .unwrap_or_default();
     let mut asteroids: Vec<Asteroid> = Vec::new();
-    let mut last_add_asteroid: f64 = 0.0;
     let mut time: f64;
     let mut total_paused_time: f64 = 0.0;
-    let mut spaceship = Spaceship::new();
     let mut missiles: Vec<missile::Missile> = Vec::new();
+    let mut ores: Vec<Ore> = Vec::new();
     let mut last_shoot: f64 = 0.0;
+    let mut boss: Option<Boss> = None;
+    let mut last_boss_wave: u32 = 0;
+    let mut last_shop_wave: u32 = 1;
 
-    // Boucle principale selon le mode
-    match mode {
-        "classic" => {
-            loop {
-                time = get_time() - start_time - total_paused_time;
+    // Règles du mode : apparitions, conditions de victoire et de défaite
+    // Partie personnalisée : règles et bouclier de départ issus de la configuration
+    let custom = (mode == "custom").then(CustomConfig::load);
+    let Some(mut rules) = level
+        .map(Level::rules)
+        .or_else(|| custom.as_ref().map(CustomConfig::rules))
+        .or_else(|| ModeRules::for_mode(mode))
+    else {
+        eprintln!("Unknown mode: {}", mode);
+        return None;
+    };
+    let mut definition = settings.ship_definition();
+    if let Some(custom) = &custom {
+        definition.shield = custom.starting_shield;
+    }
 
-                // Gérer la pause
-                total_paused_time +=
-                    pause(&background_texture, &asteroid_texture, sound, bind).await;
+    // Difficulté : le défi quotidien est toujours joué en 'Normal' (même partie pour tous),
+    // la durée d'une partie personnalisée (ou d'un niveau) n'est pas ajustée
+    let daily = (mode == "daily").then(DailyChallenge::today);
+    let difficulty = if daily.is_some() {
+        Difficulty::Normal
+    } else {
+        settings.difficulty
+    };
+    if custom.is_some() || level.is_some() {
+        rules.difficulty = difficulty;
+    } else {
+        rules = rules.with_difficulty(difficulty);
+    }
 
-                // Quitter si Backspace est pressé
-                if is_key_down(KeyCode::Backspace) {
-                    break;
-                }
+    // Défi quotidien : graine et modificateurs dérivés de la date
+    if let Some(daily) = &daily {
+        daily.apply(&mut rules, &mut definition);
+        rng::set_seed(Some(daily.seed));
+    }
+    let mut spaceship = settings.new_spaceship(&definition);
+    if let Some(start) = level.and_then(|level| level.start) {
+        spaceship.set_motion(start * screen, Vec2::ZERO, 0.0);
+    }
+    // Structure à protéger : capsule d'une mission d'escorte ou station du mode défense
+    let mut station = match level.and_then(|level| level.cargo) {
+        Some((position, speed)) => Some(Station::new(
+            StationKind::CargoPod,
+            position * screen,
+            speed,
+        )),
+        None => rules
+            .station
+            .then(|| Station::outpost(screen * vec2(0.5, 0.3))),
+    };
+    // Apparitions des astéroïdes : aléatoires (lancées vers la station en mode défense),
+    // ou scriptées par le niveau
+    let mut spawner = match (level, &station) {
+        (Some(level), _) => Spawner::Script(LevelScript::new(level)),
+        (None, Some(station)) => Spawner::Toward {
+            last_add: 0.0,
+            target: station.position,
+        },
+        (None, None) => Spawner::Random { last_add: 0.0 },
+    };
+    let mut high_scores = HighScores::load();
 
-                // Mettre à jour les entités et vérifier les conditions de fin
-                if update_classic(
-                    &mut asteroids,
-                    &mut blackholes,
-                    &mut last_add_asteroid,
-                    &mut spaceship,
-                    time,
-                    &mut last_shoot,
-                    &mut missiles,
-                    sound,
-                    bind,
-                )
-                .await
-                {
-                    if game_over(&spaceship, sound, false).await {
-                        break;
-                    } else {
-                        std::process::exit(0);
-                    }
-                }
+    // Directeur adaptatif (sauf pour le défi quotidien, identique pour tous)
+    let mut director = (settings.adaptive_director && daily.is_none()).then(Director::new);
 
-                // Dessiner les éléments
-                draw_classic(
-                    [
-                        &asteroid_texture,
-                        &blackhole_texture,
-                        &background_texture,
-                        &spaceship_texture,
-                    ],
-                    &asteroids,
-                    &blackholes,
-                    &spaceship,
-                    time,
-                    &missiles,
-                );
-                if asteroids.is_empty() && time > 10.0 {
-                    if game_over(&spaceship, sound, true).await {
-                        break;
-                    } else {
-                        std::process::exit(0);
-                    }
-                }
-                next_frame().await;
+    // Succès
+    let mut achievements = Achievements::load();
+    let previous_lifetime = LifetimeStats::load();
+
+    let scheme = settings.control_scheme_for(rules.flavor);
+    let mut pilot = autopilot.map(Autopilot::new);
+    // Partie comptée (scores, succès, statistiques) : ni pilote automatique, ni défi quotidien déjà joué
+    let counted = pilot.is_none() && !daily.as_ref().is_some_and(DailyChallenge::is_played);
+    let mut spectators = if settings.spectator_stream {
+        SpectatorServer::bind(SPECTATOR_PORT)
+            .map_err(|e| eprintln!("Impossible de diffuser la partie: {}", e))
+            .ok()
+    } else {
+        None
+    };
+
+    // Boucle principale
+    let outcome = loop {
+        time = get_time() - start_time - total_paused_time;
+        // Prise en compte des modifications du fichier d'équilibrage
+        tuning::reload_if_changed();
+
+        // Gérer la pause
+        total_paused_time += pause(&background_texture, &asteroid_texture, sound, bindings).await;
+
+        // Quitter si Backspace est pressé
+        if bindings[0].is_down(Action::Quit) {
+            if !counted {
+                break None;
+            }
+            end_session(&mut spaceship, time);
+            high_scores.submit(rules.name, get_score(&spaceship), difficulty);
+            high_scores.save();
+            if let Some(daily) = &daily {
+                daily.record_attempt(get_score(&spaceship));
+            }
+            if let Some(director) = &director {
+                director.save_log(rules.name);
             }
+            break None;
         }
-        "modern" => loop {
-            time = get_time() - start_time - total_paused_time;
 
-            total_paused_time += pause(&background_texture, &asteroid_texture, sound, bind).await;
-
-            if is_key_down(KeyCode::Backspace) {
-                break;
+        // Règles ajustées par le directeur adaptatif
+        let frame_rules = match &mut director {
+            Some(director) => {
+                director.review(time, &spaceship.stats, asteroids.len());
+                director.adjust(&rules)
             }
+            None => rules,
+        };
+
+        // Commandes du vaisseau : clavier du joueur, ou pilote automatique
+        let input = match &mut pilot {
+            Some(pilot) => pilot.decide(
+                &spaceship,
+                &Situation {
+                    asteroids: &asteroids,
+                    blackholes: &blackholes,
+                    asteroid_speed: frame_rules.asteroid_speed(time) as f32,
+                    missile_speed: frame_rules.missile_speed,
+                },
+                scheme,
+            ),
+            None => ShipInput::from_keyboard(&bindings[0], scheme),
+        };
 
-            if update_modern(
+        // Mettre à jour les entités et vérifier les conditions de fin
+        let mut lost = if rules.flavor == "classic" {
+            update_classic(
                 &mut asteroids,
                 &mut blackholes,
-                &mut last_add_asteroid,
+                &mut spawner,
                 &mut spaceship,
                 time,
                 &mut last_shoot,
                 &mut missiles,
+                &mut ores,
                 sound,
-                bind,
+                &input,
+                scheme,
+                &frame_rules,
             )
             .await
-            {
-                if game_over(&spaceship, sound, false).await {
-                    break;
-                } else {
-                    std::process::exit(0);
+        } else {
+            update_modern(
+                &mut asteroids,
+                &mut blackholes,
+                &mut spawner,
+                &mut spaceship,
+                time,
+                &mut last_shoot,
+                &mut missiles,
+                &mut ores,
+                sound,
+                &input,
+                scheme,
+                &frame_rules,
+            )
+            .await
+        };
+
+        // Minerai ramassé, puis boutique d'améliorations au début de chaque vague
+        let wave = wave_number(time);
+        if rules.ore_shop {
+            Ore::update_all(&mut ores, &mut spaceship, time);
+            // Le pilote automatique ne passe pas par la boutique
+            if wave > last_shop_wave && pilot.is_none() {
+                last_shop_wave = wave;
+                if spaceship.ore > 0 {
+                    total_paused_time += shop(
+                        &background_texture,
+                        &asteroid_texture,
+                        sound,
+                        &mut spaceship,
+                        wave,
+                    )
+                    .await;
+                }
+            }
+        }
+
+        // Boss à la fin de chaque série de vagues
+        // Pas de boss dans les niveaux (apparitions écrites à la main)
+        if rules.bosses && boss.is_none() && Boss::is_due(wave, last_boss_wave) {
+            boss = Some(Boss::new(wave, time));
+            last_boss_wave = wave;
+        }
+        if let Some(active) = &mut boss {
+            lost |= active.update(
+                time,
+                vec2(screen_width(), screen_height()),
+                &mut asteroids,
+                &mut blackholes,
+                &mut spaceship,
+                sound,
+            );
+            active.hit_by_missiles(&mut missiles, &mut asteroids, &mut spaceship, sound);
+            if active.is_defeated() {
+                sound.play_sound_effect("win");
+                add_score(&mut spaceship, Boss::DEFEAT_SCORE);
+                boss = None;
+            }
+        }
+        // Mode Zen : le vaisseau réapparaît au lieu d'être détruit
+        if lost && !rules.can_die {
+            spaceship.respawn(vec2(screen_width(), screen_height()) / 2.0, time);
+            lost = false;
+        }
+        if let Some(station) = &mut station {
+            station.update();
+            spawner.set_target(station.position);
+            if station.hit_by_asteroids(&mut asteroids) {
+                sound.play_sound_effect("collision");
+            }
+            lost |= station.is_destroyed();
+        }
+        let field_cleared = boss.is_none() && asteroids.is_empty() && spawner.is_finished();
+        // Objectif du niveau, sinon règles du mode
+        let progress = GoalProgress {
+            time,
+            field_cleared,
+            score: get_score(&spaceship),
+            large_destroyed: spaceship.stats.get_destroyed(AsteroidShape::Large),
+            ship_position: spaceship.get_position() / screen,
+        };
+        let won = !lost
+            && match level {
+                Some(level) => level.goal.is_reached(&progress),
+                None => rules.is_won(time, field_cleared, get_score(&spaceship)),
+            };
+        lost |= !won && rules.is_time_up(time);
+
+        if counted
+            && achievements.evaluate(
+                &GameContext {
+                    mode: rules.flavor,
+                    time,
+                    session: &spaceship.stats,
+                    lifetime: &previous_lifetime,
+                    max_combo: spaceship.score_engine.get_max_combo(),
+                    won,
+                },
+                get_time(),
+            ) > 0
+        {
+            achievements.save();
+        }
+
+        if let Some(spectators) = &mut spectators {
+            spectators.poll();
+            let world = WorldView {
+                time,
+                over: lost || won,
+                won,
+                ships: vec![(0, &spaceship)],
+                asteroids: &asteroids,
+                blackholes: &blackholes,
+                missiles: &missiles,
+            };
+            spectators.broadcast(rules.flavor, &world.encode());
+        }
+
+        if lost || won {
+            let (lifetime, new_record) = if !counted {
+                finalize_score(&mut spaceship);
+                spaceship.stats.survival_time = time;
+                (previous_lifetime.clone(), false)
+            } else {
+                let lifetime = end_session(&mut spaceship, time);
+                let new_record = high_scores.submit(rules.name, get_score(&spaceship), difficulty);
+                high_scores.save();
+                if let Some(daily) = &daily {
+                    daily.record_attempt(get_score(&spaceship));
+                }
+                if let Some(director) = &director {
+                    director.save_log(rules.name);
                 }
+                (lifetime, new_record)
+            };
+            let outcome = LevelOutcome {
+                won,
+                score: get_score(&spaceship),
+                shield_hits: spaceship.stats.shield_hits,
+                time,
+            };
+            if mode == "campaign" {
+                break Some(outcome);
             }
+            if game_over(
+                &spaceship,
+                &lifetime,
+                &high_scores,
+                new_record,
+                &mut achievements,
+                sound,
+                won,
+            )
+            .await
+            {
+                break Some(outcome);
+            } else {
+                std::process::exit(0);
+            }
+        }
 
+        // Dessiner les éléments
+        let textures = [
+            &asteroid_texture,
+            &blackhole_texture,
+            &background_texture,
+            &spaceship_texture,
+        ];
+        if rules.flavor == "classic" {
+            draw_classic(
+                textures,
+                &asteroids,
+                &blackholes,
+                &spaceship,
+                time,
+                &missiles,
+            );
+        } else {
             draw_modern(
-                [
-                    &asteroid_texture,
-                    &blackhole_texture,
-                    &background_texture,
-                    &spaceship_texture,
-                ],
+                textures,
                 &asteroids,
                 &blackholes,
                 &spaceship,
                 time,
                 &missiles,
             );
-            if asteroids.is_empty() && time > 45.0 {
-                if game_over(&spaceship, sound, true).await {
-                    break;
-                } else {
-                    std::process::exit(0);
-                }
+        }
+        if let Some(time_left) = rules.time_left(time) {
+            draw_time_left(time_left);
+        }
+        if rules.ore_shop {
+            for ore in &ores {
+                ore.draw(time);
             }
-            next_frame().await;
-        },
-        _ => {
-            eprintln!("Unknown mode: {}", mode);
+            draw_ore(spaceship.ore);
         }
-    }
+        if let Some(level) = level {
+            if let LevelGoal::Beacon(beacon) = level.goal {
+                draw_beacon(beacon * screen, time);
+            }
+            if let Some(text) = level.goal.progress_text(&progress) {
+                draw_objective(&text);
+            }
+        }
+        if let Some(station) = &station {
+            station.draw();
+            draw_station_health(station);
+        }
+        if let Some(boss) = &boss {
+            boss.draw(&asteroid_texture);
+            draw_boss_health(boss);
+        }
+        if let Some(pilot) = &pilot {
+            draw_autopilot(pilot.get_skill());
+        }
+        if let Some(spectators) = &spectators {
+            draw_text(
+                &format!(
+                    "Streaming on port {} : {} spectator(s)",
+                    spectators.port(),
+                    spectators.viewer_count()
+                ),
+                10.0,
+                screen_height() - 20.0,
+                25.0,
+                YELLOW,
+            );
+        }
+        achievements.draw_toasts(get_time());
+        draw_tuning_errors();
+        next_frame().await;
+    };
+    rng::set_seed(None);
+    outcome
 }
 
 /// Configure les paramètres de la fenêtre du jeu.
@@ -207,5 +575,7 @@ fn configuration() -> Conf {
 /// Fonction principale, point d'entrée du programme.
 #[macroquad::main(configuration)]
 async fn main() {
+    // Valeurs d'équilibrage (le fichier est ensuite surveillé pendant le jeu)
+    tuning::reload_if_changed();
     menu().await;
 }
@@ -0,0 +1,134 @@
+//! # Material Module
+//!
+//! Ce module définit les matériaux des astéroïdes, en plus de leur forme (`AsteroidShape`) :
+//! - `Rock` : détruit par un seul missile.
+//! - `Metallic` : résiste à plusieurs missiles (des fissures apparaissent).
+//! - `Ice` : se brise en de nombreux petits fragments.
+//! - `Explosive` : explose en endommageant les astéroïdes voisins (réaction en chaîne).
+//! - `Crystal` : rapporte des points bonus.
+//!
+//! Chaque matériau a sa teinte et son poids d'apparition selon la vague en cours.
+use crate::asteroid::AsteroidShape;
+use crate::rng::game_rng;
+use ::rand::Rng;
+use macroquad::prelude::*;
+
+/// Matériau d'un astéroïde.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Material {
+    /// Roche (comportement historique).
+    Rock,
+    /// Métal : plusieurs missiles sont nécessaires.
+    Metallic,
+    /// Glace : se brise en de nombreux fragments.
+    Ice,
+    /// Explosif : endommage les astéroïdes voisins.
+    Explosive,
+    /// Cristal : points bonus.
+    Crystal,
+}
+
+impl Material {
+    /// Tous les matériaux (dans l'ordre des poids d'apparition).
+    pub const ALL: [Material; 5] = [
+        Material::Rock,
+        Material::Metallic,
+        Material::Ice,
+        Material::Explosive,
+        Material::Crystal,
+    ];
+    /// Poids d'apparition des matériaux (dans l'ordre de `ALL`) pour les vagues 1, 2, 3, 4 et suivantes.
+    const SPAWN_WEIGHTS: [[u32; 5]; 5] = [
+        [100, 0, 0, 0, 5],
+        [85, 10, 10, 0, 5],
+        [70, 15, 10, 5, 5],
+        [60, 20, 10, 10, 5],
+        [50, 25, 10, 15, 5],
+    ];
+    /// Rayon de l'explosion d'un astéroïde explosif (en plus de la taille des voisins).
+    pub const BLAST_RADIUS: f32 = 120.0;
+    /// Points bonus d'un astéroïde en cristal.
+    const CRYSTAL_BONUS: u32 = 200;
+
+    /// Tire un matériau au hasard selon les poids d'apparition de la vague.
+    ///
+    /// # Arguments
+    /// - `wave` Le numéro de la vague en cours (à partir de 1).
+    ///
+    pub fn random_for_wave(wave: u32) -> Self {
+        let index = (wave.max(1) as usize - 1).min(Self::SPAWN_WEIGHTS.len() - 1);
+        let weights = Self::SPAWN_WEIGHTS[index];
+        let mut roll = game_rng().gen_range(0..weights.iter().sum::<u32>());
+        for (material, weight) in Self::ALL.into_iter().zip(weights) {
+            if roll < weight {
+                return material;
+            }
+            roll -= weight;
+        }
+        Material::Rock
+    }
+
+    /// Teinte appliquée à la texture de l'astéroïde.
+    pub fn tint(self) -> Color {
+        match self {
+            Material::Rock => WHITE,
+            Material::Metallic => Color::new(0.65, 0.7, 0.8, 1.0),
+            Material::Ice => Color::new(0.6, 0.9, 1.0, 0.85),
+            Material::Explosive => Color::new(1.0, 0.45, 0.3, 1.0),
+            Material::Crystal => Color::new(0.85, 0.5, 1.0, 1.0),
+        }
+    }
+
+    /// Nombre de missiles nécessaires pour détruire un astéroïde de ce matériau.
+    ///
+    /// # Arguments
+    /// - `shape` La forme de l'astéroïde.
+    ///
+    pub fn hit_points(self, shape: AsteroidShape) -> u32 {
+        match (self, shape) {
+            (Material::Metallic, AsteroidShape::Large) => 4,
+            (Material::Metallic, AsteroidShape::Medium) => 3,
+            (Material::Metallic, AsteroidShape::Small) => 2,
+            _ => 1,
+        }
+    }
+
+    /// Points bonus gagnés en détruisant un astéroïde de ce matériau.
+    pub fn bonus_score(self) -> u32 {
+        match self {
+            Material::Crystal => Self::CRYSTAL_BONUS,
+            _ => 0,
+        }
+    }
+
+    /// Matériau des fragments d'un astéroïde scindé.
+    /// Le métal et la glace gardent leur matériau, les autres fragments sont de la roche.
+    pub fn fragment(self) -> Self {
+        match self {
+            Material::Metallic | Material::Ice => self,
+            _ => Material::Rock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_wave_spawns_only_rock_or_crystal() {
+        for _ in 0..200 {
+            let material = Material::random_for_wave(1);
+            assert!(material == Material::Rock || material == Material::Crystal);
+        }
+    }
+
+    #[test]
+    fn test_material_properties() {
+        assert_eq!(Material::Rock.hit_points(AsteroidShape::Large), 1);
+        assert!(Material::Metallic.hit_points(AsteroidShape::Small) > 1);
+        assert_eq!(Material::Crystal.bonus_score(), 200);
+        assert_eq!(Material::Explosive.fragment(), Material::Rock);
+        assert_eq!(Material::Metallic.fragment(), Material::Metallic);
+    }
+}
@@ -1,19 +1,113 @@
 use super::play;
 use super::skins;
+use crate::achievement::Achievements;
 use crate::asteroid::Asteroid;
+use crate::autopilot::autopilot_menu;
 use crate::bind::Bind;
+use crate::campaign::campaign_menu;
+use crate::coop::play_coop;
+use crate::custom::custom_game_menu;
+use crate::daily::daily_menu;
+use crate::editor::level_editor;
+use crate::gui::draw_tuning_errors;
+use crate::level::level_menu;
+use crate::lobby::lobby_menu;
 use crate::option::option_menu;
+use crate::rules::choose_mode;
+use crate::settings::Settings;
+use crate::shipclass::ship_picker;
 use crate::sound::Sound;
-use crate::utils::background_asteroids;
+use crate::spectate::spectate;
+use crate::stats::statistics_menu;
+use crate::tuning;
+use crate::utils::{background_asteroids, choose_image_from_folder, scroll_offset};
+use crate::versus::{choose_kills_to_win, play_versus};
 use macroquad::prelude::*;
 
+/// Entrées du menu principal, dans l'ordre d'affichage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuEntry {
+    Classic,
+    Modern,
+    MoreModes,
+    Daily,
+    Custom,
+    Campaign,
+    Levels,
+    LevelEditor,
+    Difficulty,
+    Coop,
+    Versus,
+    Multiplayer,
+    Spectate,
+    WatchAi,
+    Skins,
+    Ship,
+    Statistics,
+    Options,
+    Leave,
+}
+
+impl MenuEntry {
+    /// Toutes les entrées, dans l'ordre d'affichage.
+    const ALL: [MenuEntry; 19] = [
+        MenuEntry::Classic,
+        MenuEntry::Modern,
+        MenuEntry::MoreModes,
+        MenuEntry::Daily,
+        MenuEntry::Custom,
+        MenuEntry::Campaign,
+        MenuEntry::Levels,
+        MenuEntry::LevelEditor,
+        MenuEntry::Difficulty,
+        MenuEntry::Coop,
+        MenuEntry::Versus,
+        MenuEntry::Multiplayer,
+        MenuEntry::Spectate,
+        MenuEntry::WatchAi,
+        MenuEntry::Skins,
+        MenuEntry::Ship,
+        MenuEntry::Statistics,
+        MenuEntry::Options,
+        MenuEntry::Leave,
+    ];
+
+    /// Libellé de l'entrée.
+    fn label(self) -> &'static str {
+        match self {
+            MenuEntry::Classic => "Classic Mode",
+            MenuEntry::Modern => "Modern Mode",
+            MenuEntry::MoreModes => "More Modes",
+            MenuEntry::Daily => "Daily Challenge",
+            MenuEntry::Custom => "Custom Game",
+            MenuEntry::Campaign => "Campaign",
+            MenuEntry::Levels => "Levels",
+            MenuEntry::LevelEditor => "Level Editor",
+            MenuEntry::Difficulty => "Difficulty",
+            MenuEntry::Coop => "Co-op Mode",
+            MenuEntry::Versus => "Versus Mode",
+            MenuEntry::Multiplayer => "Multiplayer",
+            MenuEntry::Spectate => "Spectate",
+            MenuEntry::WatchAi => "Watch AI",
+            MenuEntry::Skins => "Skins",
+            MenuEntry::Ship => "Ship",
+            MenuEntry::Statistics => "Statistics",
+            MenuEntry::Options => "Options",
+            MenuEntry::Leave => "Leave",
+        }
+    }
+}
+
 /// Menu principal du jeu.
-/// Permet le lancement des deux modes de jeu, accès aux options (binding, sons), accès aux choix des skins.
+/// Permet le lancement des modes de jeu, le choix de la difficulté, accès aux options (binding, sons), accès aux choix des skins et de la classe du vaisseau.
 pub async fn menu() {
     // Indice de l'option sélectionnée
-    let mut actual_binding = Bind::new();
+    let mut bindings = [Bind::new(), Bind::new_player_two()];
     let mut selected_index = 0;
-    let options = ["Classic Mode", "Modern Mode", "Skins", "Options", "Leave"];
+    // Première option affichée lorsque la liste défile
+    let mut first_visible = 0;
+    let mut difficulty = Settings::load().difficulty;
+    let options = MenuEntry::ALL;
     let mut asteroid_skin = "assets/pictures/asteroids/asteroid.png";
     let mut blackhole_skin = "assets/pictures/blackholes/blackhole.png";
     let mut spaceship_skin = "assets/pictures/ships/spaceship.png";
@@ -32,6 +126,8 @@ pub async fn menu() {
     sound.play_background_music();
     sound.play_sound_effect("select_menu");
     loop {
+        // Prise en compte des modifications du fichier d'équilibrage
+        tuning::reload_if_changed();
         clear_background(BLACK);
         background_asteroids(&background_texture, &asteroid_texture, &mut asteroids).await;
 
@@ -45,13 +141,38 @@ pub async fn menu() {
             WHITE,
         );
 
-        // Affichage des options
-        for (i, &option) in options.iter().enumerate() {
+        // Affichage des options (la liste défile si elle ne tient pas à l'écran)
+        let visible = (((screen_height() - 280.0) / 50.0) as usize).clamp(1, options.len());
+        first_visible = scroll_offset(selected_index, first_visible, visible, options.len());
+        if first_visible > 0 {
+            draw_text("...", screen_width() / 2.0 - 100.0, 250.0, 40.0, GRAY);
+        }
+        if first_visible + visible < options.len() {
+            draw_text(
+                "...",
+                screen_width() / 2.0 - 100.0,
+                260.0 + visible as f32 * 50.0,
+                40.0,
+                GRAY,
+            );
+        }
+        for (row, (i, &entry)) in options
+            .iter()
+            .enumerate()
+            .skip(first_visible)
+            .take(visible)
+            .enumerate()
+        {
             let color = if i == selected_index { YELLOW } else { WHITE };
+            let label = if entry == MenuEntry::Difficulty {
+                format!("{} : {}", entry.label(), difficulty.name())
+            } else {
+                entry.label().to_string()
+            };
             draw_text(
-                option,
+                &label,
                 screen_width() / 2.0 - 100.0,
-                350.0 + i as f32 * 50.0,
+                280.0 + row as f32 * 50.0,
                 40.0,
                 color,
             );
@@ -77,8 +198,8 @@ pub async fn menu() {
 
         // Actions pour chaque option avec Entrée
         if is_key_pressed(KeyCode::Enter) {
-            match selected_index {
-                0 => {
+            match options[selected_index] {
+                MenuEntry::Classic => {
                     play(
                         "classic",
                         [
@@ -89,11 +210,13 @@ pub async fn menu() {
                         ],
                         get_time(),
                         &mut sound,
-                        &mut actual_binding,
+                        &mut bindings,
+                        None,
+                        None,
                     )
                     .await;
                 }
-                1 => {
+                MenuEntry::Modern => {
                     play(
                         "modern",
                         [
@@ -104,11 +227,268 @@ pub async fn menu() {
                         ],
                         get_time(),
                         &mut sound,
-                        &mut actual_binding,
+                        &mut bindings,
+                        None,
+                        None,
+                    )
+                    .await;
+                }
+                MenuEntry::MoreModes => {
+                    if let Some(mode) = choose_mode(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await
+                    {
+                        play(
+                            mode,
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                            None,
+                            None,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Daily => {
+                    let start = daily_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await;
+                    if start {
+                        play(
+                            "daily",
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                            None,
+                            None,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Custom => {
+                    let start = custom_game_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await;
+                    if start {
+                        play(
+                            "custom",
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                            None,
+                            None,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Campaign => {
+                    campaign_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &mut sound,
+                        &mut bindings,
+                        [
+                            asteroid_skin,
+                            blackhole_skin,
+                            background_skin,
+                            spaceship_skin,
+                        ],
                     )
                     .await;
                 }
-                2 => {
+                MenuEntry::Levels => {
+                    if let Some((_, level)) = level_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await
+                    {
+                        play(
+                            "level",
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                            Some(&level),
+                            None,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::LevelEditor => {
+                    level_editor(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &mut sound,
+                        &mut bindings,
+                        [
+                            asteroid_skin,
+                            blackhole_skin,
+                            background_skin,
+                            spaceship_skin,
+                        ],
+                    )
+                    .await;
+                }
+                MenuEntry::Difficulty => {
+                    sound.play_sound_effect("select_menu");
+                    let mut settings = Settings::load();
+                    settings.difficulty = settings.difficulty.next();
+                    settings.save();
+                    difficulty = settings.difficulty;
+                }
+                MenuEntry::Coop => {
+                    let player_two_skin = choose_image_from_folder(
+                        "assets/pictures/ships",
+                        &sound,
+                        &Achievements::load(),
+                    )
+                    .await;
+                    if let Some(player_two_skin) = player_two_skin {
+                        play_coop(
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            &player_two_skin,
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Versus => {
+                    let player_two_skin = choose_image_from_folder(
+                        "assets/pictures/ships",
+                        &sound,
+                        &Achievements::load(),
+                    )
+                    .await;
+                    let kills_to_win = choose_kills_to_win(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await;
+                    if let (Some(player_two_skin), Some(kills_to_win)) =
+                        (player_two_skin, kills_to_win)
+                    {
+                        play_versus(
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            &player_two_skin,
+                            kills_to_win,
+                            &mut sound,
+                            &mut bindings,
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Multiplayer => {
+                    lobby_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                        &bindings[0],
+                        [
+                            asteroid_skin,
+                            blackhole_skin,
+                            background_skin,
+                            spaceship_skin,
+                        ],
+                    )
+                    .await;
+                }
+                MenuEntry::Spectate => {
+                    spectate(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                        [
+                            asteroid_skin,
+                            blackhole_skin,
+                            background_skin,
+                            spaceship_skin,
+                        ],
+                    )
+                    .await;
+                }
+                MenuEntry::WatchAi => {
+                    if let Some((skill, mode)) = autopilot_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await
+                    {
+                        play(
+                            mode,
+                            [
+                                asteroid_skin,
+                                blackhole_skin,
+                                background_skin,
+                                spaceship_skin,
+                            ],
+                            get_time(),
+                            &mut sound,
+                            &mut bindings,
+                            None,
+                            Some(skill),
+                        )
+                        .await;
+                    }
+                }
+                MenuEntry::Skins => {
                     skin_return = skins(
                         &background_texture,
                         &asteroid_texture,
@@ -130,25 +510,49 @@ pub async fn menu() {
                     blackhole_skin = &skin_return.2;
                     spaceship_skin = &skin_return.3;
                 }
-                3 => {
+                MenuEntry::Ship => {
+                    let mut settings = Settings::load();
+                    if let Some(ship_class) = ship_picker(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                        &settings.ship_definition().name,
+                    )
+                    .await
+                    {
+                        settings.ship_class = ship_class;
+                        settings.save();
+                    }
+                }
+                MenuEntry::Statistics => {
+                    statistics_menu(
+                        &background_texture,
+                        &asteroid_texture,
+                        &mut asteroids,
+                        &sound,
+                    )
+                    .await;
+                }
+                MenuEntry::Options => {
                     option_menu(
                         &background_texture,
                         &asteroid_texture,
                         &mut asteroids,
                         &mut sound,
-                        &mut actual_binding,
+                        &mut bindings,
                         get_time(),
                     )
                     .await;
                 }
-                4 => {
+                MenuEntry::Leave => {
                     println!("Leave selected");
                     return; // Quitter le jeu
                 }
-                _ => {}
             }
         }
 
+        draw_tuning_errors();
         next_frame().await;
     }
 }
@@ -1,11 +1,12 @@
+use crate::bind::{Action, Bind};
+use crate::shield::EnergyShield;
+use crate::shipclass::Weapon;
 use crate::sound::Sound;
+use crate::tuning::tuning;
 use macroquad::prelude::*;
 
 use crate::{
-    asteroid::Asteroid,
-    impl_stellar_object,
-    score::{add_score, increase_score},
-    spaceship::Spaceship,
+    asteroid::Asteroid, impl_stellar_object, score::add_score, spaceship::Spaceship,
     stellarobject::StellarObject,
 };
 
@@ -15,6 +16,10 @@ pub struct Missile {
     pub speed: Vec2,
     pub size: f32,
     pub thickness: f32,
+    /// Indice du joueur ayant tiré le missile.
+    pub owner: usize,
+    /// Distance parcourue par le missile (pour limiter la portée des missiles qui traversent les bords).
+    travelled: f32,
 }
 
 /// Implémentation du missile.
@@ -26,13 +31,16 @@ impl Missile {
     /// - `speed` La vitesse du missile.
     /// - `size` La taille du missile.
     /// - `thickness` L'épaisseur du missile (ligne).
+    /// - `owner` L'indice du joueur ayant tiré le missile.
     ///
-    pub fn new(position: Vec2, speed: Vec2, size: f32, thickness: f32) -> Self {
+    pub fn new(position: Vec2, speed: Vec2, size: f32, thickness: f32, owner: usize) -> Self {
         Self {
             position,
             speed,
             size,
             thickness,
+            owner,
+            travelled: 0.0,
         }
     }
 
@@ -48,6 +56,15 @@ impl Missile {
     /// Affichage du missile.
     /// Le missile est dessiné sous forme de ligne.
     pub fn draw_missile(&self) {
+        self.draw_missile_with_color(WHITE);
+    }
+
+    /// Affichage du missile avec une couleur donnée (pour distinguer les joueurs).
+    ///
+    /// # Arguments
+    /// - `color` La couleur du missile.
+    ///
+    pub fn draw_missile_with_color(&self, color: Color) {
         let pos = self.get_end_position();
         draw_line(
             self.position.x,
@@ -55,13 +72,14 @@ impl Missile {
             pos[0],
             pos[1],
             self.thickness,
-            WHITE,
+            color,
         );
     }
 
     /// Mise à jour de la position du missile.
     pub fn move_missile(&mut self) {
         self.position += self.speed;
+        self.travelled += self.speed.length();
     }
 
     /// Vérifie si le missile est en collision avec un astéroid.
@@ -72,9 +90,20 @@ impl Missile {
     /// # Returns
     /// - `true` si le missile est en collision avec l'astéroid.
     pub fn is_collide_asteroid(&self, asteroid: &Asteroid) -> bool {
+        self.is_collide_circle(asteroid.get_position(), asteroid.get_size())
+    }
+
+    /// Vérifie si le missile (segment) est en collision avec un cercle.
+    ///
+    /// # Arguments
+    /// - `c` Le centre du cercle.
+    /// - `radius` Le rayon du cercle.
+    ///
+    /// # Returns
+    /// - `true` si le missile est en collision avec le cercle.
+    pub fn is_collide_circle(&self, c: Vec2, radius: f32) -> bool {
         let a: Vec2 = self.position;
         let b: Vec2 = self.get_end_position();
-        let c: Vec2 = asteroid.get_position();
 
         // Vecteurs ab et ac
         let ab = (b.x - a.x, b.y - a.y);
@@ -92,106 +121,169 @@ impl Missile {
         let distance = ((closest_point.x - c.x).powi(2) + (closest_point.y - c.y).powi(2)).sqrt();
 
         // Check if the distance is less than the radius of the circle
-        distance <= asteroid.get_size()
+        distance <= radius
     }
 
-    /// Gestions des tirs de missiles en mode 'classic'.
+    /// Gestions des tirs de missiles.
+    /// Les tirs sont effectués avec les entrées de l'action 'Fire' ('Espace' par défaut).
     ///
     /// # Arguments
-    /// - `key` La touche 'Space' (espace).
+    /// - `keybinding` Le Bind du joueur.
+    /// - `spaceship` Le vaisseau du joueur (sa classe fixe la cadence de tir).
     /// - `last_shoot` Le temps du dernier tir.
     /// - `time` Le temps actuel.
     ///
     /// # Returns
     /// - `true` si le vaisseau tire un missile
     ///
-    pub fn do_i_shoot_classic(key: KeyCode, last_shoot: f64, time: f64) -> bool {
-        is_key_down(key) && time - last_shoot > 0.2
+    pub fn do_i_shoot(
+        keybinding: &Bind,
+        spaceship: &Spaceship,
+        last_shoot: f64,
+        time: f64,
+    ) -> bool {
+        keybinding.is_down(Action::Fire) && Self::is_ready(spaceship, last_shoot, time)
     }
 
-    /// Gestions des tirs de missiles en mode 'modern'.
-    /// Les tirs sont effectués avec les clics de la souris.
+    /// Vérifie si l'arme du vaisseau est rechargée (délai entre deux tirs écoulé).
     ///
     /// # Arguments
+    /// - `spaceship` Le vaisseau (sa classe fixe la cadence de tir).
     /// - `last_shoot` Le temps du dernier tir.
     /// - `time` Le temps actuel.
     ///
-    pub fn do_i_shoot_modern(last_shoot: f64, time: f64) -> bool {
-        (is_mouse_button_down(MouseButton::Left)
-            || is_mouse_button_down(MouseButton::Middle)
-            || is_mouse_button_down(MouseButton::Right))
-            && time - last_shoot > 0.2
+    pub fn is_ready(spaceship: &Spaceship, last_shoot: f64, time: f64) -> bool {
+        time - last_shoot > spaceship.get_definition().fire_delay
     }
 
-    /// Créer un nouveau missile en mode 'classic'.
-    /// A pour origine l'orientation du vaisseau spatial (gérée par les touches directionnelles).
+    /// Créer un nouveau missile.
+    /// A pour origine l'orientation du vaisseau spatial (selon le schéma de contrôle choisi).
     ///
     /// # Arguments
     /// - `spaceship` Le vaisseau spatial.
     /// - `factor` Facteur de vitesse du missile.
+    /// - `owner` L'indice du joueur qui tire.
     ///
-    pub fn new_missile_classic(spaceship: &Spaceship, factor: f32) -> Self {
-        let source = spaceship.get_position();
-        let speed: Vec2 = Vec2::new(
-            spaceship.get_rotation().sin(),
-            -spaceship.get_rotation().cos(),
-        ) * factor;
-
-        if speed == Vec2::new(0.0, 0.0) {
-            let rotation_vec = Vec2::new(
-                spaceship.get_rotation().sin(),
-                -spaceship.get_rotation().cos(),
-            );
-            Missile::new(source, rotation_vec * factor, 5.0, 2.0)
-        } else {
-            Missile::new(source, speed, 5.0, 2.0)
-        }
+    pub fn new_missile(spaceship: &Spaceship, factor: f32, owner: usize) -> Self {
+        Self::new_missile_at(spaceship, spaceship.get_position(), 0.0, factor, owner)
     }
 
-    /// Créer un nouveau missile en mode 'modern'.
-    /// A pour origine l'orientation du vaisseau spatial (gérée par le curseur de la souris).
+    /// Créer un missile décalé par rapport au vaisseau spatial.
     ///
     /// # Arguments
     /// - `spaceship` Le vaisseau spatial.
+    /// - `position` La position de départ du missile.
+    /// - `angle` L'écart (en radians) avec l'orientation du vaisseau.
     /// - `factor` Facteur de vitesse du missile.
+    /// - `owner` L'indice du joueur qui tire.
+    ///
+    fn new_missile_at(
+        spaceship: &Spaceship,
+        position: Vec2,
+        angle: f32,
+        factor: f32,
+        owner: usize,
+    ) -> Self {
+        let rotation = spaceship.get_rotation() + angle;
+        let speed = Vec2::new(rotation.sin(), -rotation.cos()) * factor;
+        let tuning = tuning();
+        Self::new(
+            position,
+            speed,
+            tuning.missile_length,
+            tuning.missile_thickness,
+            owner,
+        )
+    }
+
+    /// Tir de l'arme du vaisseau spatial (selon sa classe).
+    ///
+    /// # Arguments
+    /// - `spaceship` Le vaisseau spatial.
+    /// - `factor` Facteur de vitesse des missiles.
+    /// - `owner` L'indice du joueur qui tire.
+    ///
+    /// # Returns
+    /// - `Vec<Missile>` Les missiles tirés.
     ///
-    pub fn new_missile_modern(spaceship: &Spaceship, factor: f32) -> Self {
-        let source = spaceship.get_position();
-        let speed = spaceship.angle_from_mouse() * factor;
-        Self::new(source, speed, 5.0, 2.0)
+    pub fn fire(spaceship: &Spaceship, factor: f32, owner: usize) -> Vec<Self> {
+        let factor = factor * spaceship.get_upgrades().missile_speed_factor();
+        match spaceship.get_definition().weapon {
+            Weapon::Single => vec![Self::new_missile(spaceship, factor, owner)],
+            Weapon::Twin => {
+                let rotation = spaceship.get_rotation();
+                let side = Vec2::new(rotation.cos(), rotation.sin()) * spaceship.get_size() * 0.5;
+                [-1.0, 1.0]
+                    .iter()
+                    .map(|offset| {
+                        let position = spaceship.get_position() + side * *offset;
+                        Self::new_missile_at(spaceship, position, 0.0, factor, owner)
+                    })
+                    .collect()
+            }
+            Weapon::Spread => {
+                let spread = tuning().missile_spread_angle;
+                [-spread, 0.0, spread]
+                    .iter()
+                    .map(|angle| {
+                        Self::new_missile_at(
+                            spaceship,
+                            spaceship.get_position(),
+                            *angle,
+                            factor,
+                            owner,
+                        )
+                    })
+                    .collect()
+            }
+        }
     }
 
     /// Gestion des collisions entre les missiles et les astéroids.
     /// Permet de vérifier si un missile est entré en collision avec un astéroid.
-    /// Si c'est le cas, le missile est supprimé, l'astéroid est scindé en deux (si sa taille le lui permet) et le score est augmenté.
+    /// Si c'est le cas, le missile est supprimé et l'astéroid encaisse l'impact. S'il est détruit,
+    /// il est scindé (si sa taille le lui permet), peut exploser (voir `Asteroid::destroy`)
+    /// et le score du joueur ayant tiré le missile est augmenté.
     ///
     /// # Arguments
     /// - `missiles` Les missiles.
     /// - `asteroids` Les astéroids.
-    /// - `spaceship` Le vaisseau spatial.
+    /// - `spaceships` Les vaisseaux des joueurs (indexés par `owner`).
     /// - `sound` Les sons du jeu.
+    /// - `time` Le temps de jeu (pour les combos).
+    ///
+    /// # Returns
+    /// - `Vec<Asteroid>` Les astéroids détruits.
     ///
     pub fn what_collide_missile(
         missiles: &mut Vec<Missile>,
         asteroids: &mut Vec<Asteroid>,
-        spaceship: &mut Spaceship,
+        spaceships: &mut [Spaceship],
         sound: &Sound,
-    ) {
+        time: f64,
+    ) -> Vec<Asteroid> {
+        let mut destroyed = Vec::new();
         let mut i = 0;
 
         while i < missiles.len() {
             let mut j = 0;
             while j < asteroids.len() {
                 if missiles[i].is_collide_asteroid(&asteroids[j]) {
-                    sound.play_sound_effect("boom");
-                    let new_asteroids = asteroids[j].split();
-
-                    add_score(spaceship, increase_score(&asteroids[j]));
-
-                    asteroids.swap_remove(j);
+                    let owner = missiles[i].owner;
                     missiles.swap_remove(i);
 
-                    asteroids.extend(new_asteroids);
+                    if asteroids[j].hit() {
+                        sound.play_sound_effect("boom");
+                        let spaceship = &mut spaceships[owner];
+                        for asteroid in Asteroid::destroy(asteroids, j) {
+                            spaceship.stats.record_destroyed(&asteroid);
+                            let points = spaceship.score_engine.register_kill(&asteroid, time);
+                            add_score(spaceship, points);
+                            destroyed.push(asteroid);
+                        }
+                    } else {
+                        sound.play_sound_effect("collision");
+                    }
 
                     i = i.saturating_sub(1);
                     break;
@@ -200,6 +292,51 @@ impl Missile {
             }
             i += 1;
         }
+        destroyed
+    }
+
+    /// Gestion des collisions entre les missiles et les vaisseaux (mode 'versus').
+    /// Un missile touchant un autre vaisseau que celui qui l'a tiré est supprimé et retire un bouclier.
+    ///
+    /// # Arguments
+    /// - `missiles` Les missiles.
+    /// - `spaceships` Les vaisseaux des joueurs (indexés par `owner`).
+    /// - `sound` Les sons du jeu.
+    /// - `time` Le temps de jeu.
+    ///
+    /// # Returns
+    /// - `Vec<(usize, usize)>` Les éliminations (tireur, victime) provoquées par les missiles.
+    ///
+    pub fn what_collide_spaceship(
+        missiles: &mut Vec<Missile>,
+        spaceships: &mut [Spaceship],
+        sound: &Sound,
+        time: f64,
+    ) -> Vec<(usize, usize)> {
+        let mut kills = Vec::new();
+        let mut i = 0;
+        while i < missiles.len() {
+            let owner = missiles[i].owner;
+            let victim = spaceships
+                .iter()
+                .enumerate()
+                .position(|(index, spaceship)| {
+                    index != owner
+                        && spaceship.shield > 0
+                        && missiles[i]
+                            .is_collide_circle(spaceship.get_position(), spaceship.get_size())
+                });
+            match victim {
+                Some(victim) => {
+                    missiles.swap_remove(i);
+                    if spaceships[victim].check_shield(EnergyShield::MISSILE_DAMAGE, time, sound) {
+                        kills.push((owner, victim));
+                    }
+                }
+                None => i += 1,
+            }
+        }
+        kills
     }
 
     /// Suppression des missiles dès lorsqu'ils quittent l'écran.
@@ -221,24 +358,41 @@ impl Missile {
             i += 1;
         }
     }
+
+    /// Fait réapparaître les missiles sortis de l'écran sur le bord opposé
+    /// et supprime ceux qui ont dépassé leur portée.
+    ///
+    /// # Arguments
+    /// - `missiles` Les missiles.
+    ///
+    pub fn wrap_missiles(missiles: &mut Vec<Self>) {
+        let range = tuning().missile_wrap_range;
+        missiles.retain(|missile| missile.travelled < range);
+        for missile in missiles {
+            missile.position = Self::bound_pos(missile.position);
+        }
+    }
 }
 impl_stellar_object!(Missile);
 
 #[cfg(test)]
 mod tests {
     use crate::asteroid::AsteroidShape;
+    use crate::shipclass::ShipDefinition;
 
     use super::*;
 
     #[test]
     fn test_missile_new() {
-        let missile: Missile = Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0);
+        let missile: Missile =
+            Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0, 0);
         assert_eq!(missile.get_position(), Vec2::new(100.0, 100.0));
     }
 
     #[test]
     fn test_collide() {
-        let missile: Missile = Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0);
+        let missile: Missile =
+            Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0, 0);
         let asteroid: Asteroid = Asteroid::new(
             Vec2::new(100.0, 100.0),
             Vec2::new(1.0, 1.0),
@@ -250,7 +404,36 @@ mod tests {
 
     #[test]
     fn test_get_end_position() {
-        let missile: Missile = Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0);
+        let missile: Missile =
+            Missile::new(Vec2::new(100.0, 100.0), Vec2::new(1.0, 1.0), 5.0, 2.0, 0);
         assert_eq!(missile.get_end_position(), Vec2::new(105.0, 105.0));
     }
+
+    #[test]
+    fn test_fire_follows_ship_weapon() {
+        for (weapon, count) in [(Weapon::Single, 1), (Weapon::Twin, 2), (Weapon::Spread, 3)] {
+            let definition = ShipDefinition {
+                weapon,
+                ..ShipDefinition::default()
+            };
+            let missiles = Missile::fire(&Spaceship::new(&definition), 5.0, 1);
+            assert_eq!(missiles.len(), count);
+            assert!(missiles.iter().all(|missile| missile.owner == 1));
+        }
+    }
+
+    #[test]
+    fn test_wrapped_missiles_have_limited_range() {
+        let mut missiles = vec![Missile::new(Vec2::ZERO, Vec2::new(10.0, 0.0), 5.0, 2.0, 0)];
+        for _ in 0..100 {
+            missiles[0].move_missile();
+        }
+        Missile::wrap_missiles(&mut missiles);
+        assert_eq!(missiles.len(), 1);
+        for _ in 0..100 {
+            missiles[0].move_missile();
+        }
+        Missile::wrap_missiles(&mut missiles);
+        assert!(missiles.is_empty());
+    }
 }
@@ -0,0 +1,1000 @@
+//! # Network Module
+//!
+//! Ce module gère le multijoueur en réseau local (UDP).
+//!
+//! Un joueur héberge la partie : son instance simule le monde (astéroïds, trous noirs,
+//! missiles) et fait autorité. Les clients envoient leurs commandes à chaque frame et
+//! reçoivent l'état du monde sous forme d'instantanés complets ou de deltas calculés
+//! par rapport au dernier instantané confirmé. Le vaisseau du client est prédit
+//! localement, puis corrigé à la réception de chaque instantané.
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::blackhole::BlackHole;
+use crate::control::ControlScheme;
+use crate::material::Material;
+use crate::missile::Missile;
+use crate::shipclass::ShipDefinition;
+use crate::spaceship::{ShipInput, Spaceship};
+use crate::stellarobject::StellarObject;
+use macroquad::prelude::*;
+use std::collections::VecDeque;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+/// Port utilisé par défaut pour héberger une partie.
+pub const DEFAULT_PORT: u16 = 7878;
+/// Nombre maximal de joueurs (hôte compris).
+pub const MAX_PLAYERS: usize = 4;
+/// Délai sans nouvelles au-delà duquel un pair est considéré comme déconnecté.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+/// Schéma de contrôle des parties en réseau (hôte, clients et prédiction).
+/// Les commandes transmises ne contiennent pas le point visé (voir `ShipInput::to_bits`) :
+/// le schéma `TwinStick`, qui s'oriente vers la souris, n'est donc pas utilisable en réseau.
+pub const NET_SCHEME: ControlScheme = ControlScheme::Tank;
+
+/// Version du protocole (refus des clients incompatibles).
+const PROTOCOL_VERSION: u8 = 2;
+/// Taille maximale d'un datagramme reçu.
+const MAX_DATAGRAM: usize = 65_507;
+/// Nombre d'instantanés conservés pour le calcul des deltas.
+const HISTORY_LEN: usize = 32;
+/// Nombre maximal de commandes en attente de confirmation côté client.
+const MAX_PENDING_INPUTS: usize = 120;
+/// Nombre de commandes renvoyées dans chaque paquet (redondance contre les pertes).
+const REDUNDANT_INPUTS: usize = 8;
+/// Nombre maximal de commandes en file côté hôte pour un client.
+const MAX_QUEUED_INPUTS: usize = 10;
+/// Délai entre deux demandes de connexion.
+const JOIN_RETRY: Duration = Duration::from_millis(500);
+/// Délai maximal sans envoi avant un signe de vie (salon d'attente).
+const KEEPALIVE: Duration = Duration::from_secs(1);
+
+/// Client -> hôte : demande de connexion.
+const MSG_JOIN: u8 = 1;
+/// Hôte -> client : connexion acceptée (avec l'indice du joueur).
+const MSG_WELCOME: u8 = 2;
+/// Hôte -> client : connexion refusée (partie pleine ou version différente).
+const MSG_REFUSED: u8 = 3;
+/// Client -> hôte : commandes du joueur.
+const MSG_INPUT: u8 = 4;
+/// Hôte -> client : instantané complet du monde.
+const MSG_SNAPSHOT: u8 = 5;
+/// Hôte -> client : delta du monde par rapport à un instantané confirmé.
+const MSG_DELTA: u8 = 6;
+/// Dans les deux sens : départ de la partie.
+const MSG_LEAVE: u8 = 7;
+/// Dans les deux sens : signe de vie (rien d'autre à envoyer, par exemple dans le salon).
+const MSG_PING: u8 = 8;
+
+/// Ajoute un `u32` (petit-boutiste) à un tampon.
+fn put_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Ajoute un `f32` (petit-boutiste) à un tampon.
+fn put_f32(buffer: &mut Vec<u8>, value: f32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Ajoute un vecteur (deux `f32`) à un tampon.
+fn put_vec2(buffer: &mut Vec<u8>, value: Vec2) {
+    put_f32(buffer, value.x);
+    put_f32(buffer, value.y);
+}
+
+/// Lecteur séquentiel d'un tampon binaire.
+/// Chaque lecture renvoie `None` si le tampon est trop court (paquet tronqué).
+pub struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Créer un lecteur au début d'un tampon.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Lit `count` octets.
+    fn bytes(&mut self, count: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position + count)?;
+        self.position += count;
+        Some(bytes)
+    }
+
+    /// Lit un octet.
+    pub fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    /// Lit un `u16` petit-boutiste.
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes(self.bytes(2)?.try_into().ok()?))
+    }
+
+    /// Lit un `u32` petit-boutiste.
+    pub fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    /// Lit un `f32` petit-boutiste.
+    pub fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    /// Lit un vecteur (deux `f32`).
+    pub fn vec2(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    /// Renvoie le reste du tampon.
+    pub fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.data[self.position.min(self.data.len())..];
+        self.position = self.data.len();
+        rest
+    }
+}
+
+/// Code d'une forme d'astéroïd sur le réseau.
+fn shape_to_u8(shape: AsteroidShape) -> u8 {
+    match shape {
+        AsteroidShape::Small => 0,
+        AsteroidShape::Medium => 1,
+        AsteroidShape::Large => 2,
+    }
+}
+
+/// Code réseau d'un matériau d'astéroïd (indice dans `Material::ALL`).
+fn material_to_u8(material: Material) -> u8 {
+    Material::ALL
+        .iter()
+        .position(|&m| m == material)
+        .unwrap_or(0) as u8
+}
+
+/// Forme d'astéroïd correspondant à un code réseau.
+fn shape_from_u8(code: u8) -> Option<AsteroidShape> {
+    match code {
+        0 => Some(AsteroidShape::Small),
+        1 => Some(AsteroidShape::Medium),
+        2 => Some(AsteroidShape::Large),
+        _ => None,
+    }
+}
+
+/// Vue sur l'état du monde à encoder (côté hôte, sans copie).
+pub struct WorldView<'a> {
+    /// Le temps de jeu.
+    pub time: f64,
+    /// Vrai si la partie est terminée.
+    pub over: bool,
+    /// Vrai si les joueurs ont gagné (partie terminée).
+    pub won: bool,
+    /// Les vaisseaux en jeu, avec l'indice de leur joueur.
+    pub ships: Vec<(usize, &'a Spaceship)>,
+    /// Les astéroids.
+    pub asteroids: &'a [Asteroid],
+    /// Les trous noirs.
+    pub blackholes: &'a [BlackHole],
+    /// Les missiles.
+    pub missiles: &'a [Missile],
+}
+
+impl WorldView<'_> {
+    /// Encode l'état du monde en binaire.
+    ///
+    /// # Returns
+    /// - `Vec<u8>` L'instantané encodé.
+    ///
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&self.time.to_le_bytes());
+        buffer.push(self.over as u8 | (self.won as u8) << 1);
+
+        buffer.push(self.ships.len() as u8);
+        for (player_id, ship) in &self.ships {
+            buffer.push(*player_id as u8);
+            put_vec2(&mut buffer, ship.get_position());
+            put_vec2(&mut buffer, ship.get_speed());
+            put_f32(&mut buffer, ship.get_rotation());
+            buffer.push(ship.shield.min(u8::MAX as u32) as u8);
+            put_u32(&mut buffer, ship.score);
+        }
+
+        put_u32(&mut buffer, self.asteroids.len() as u32);
+        for asteroid in self.asteroids {
+            put_vec2(&mut buffer, asteroid.position);
+            put_vec2(&mut buffer, asteroid.speed);
+            buffer.push(shape_to_u8(asteroid.shape));
+            put_f32(&mut buffer, asteroid.size);
+            put_f32(&mut buffer, asteroid.rotation);
+            buffer.push(material_to_u8(asteroid.material));
+            buffer.push(asteroid.hit_points.min(u8::MAX as u32) as u8);
+        }
+
+        put_u32(&mut buffer, self.blackholes.len() as u32);
+        for blackhole in self.blackholes {
+            put_vec2(&mut buffer, blackhole.position);
+            put_f32(&mut buffer, blackhole.rotation);
+        }
+
+        put_u32(&mut buffer, self.missiles.len() as u32);
+        for missile in self.missiles {
+            put_vec2(&mut buffer, missile.position);
+            put_vec2(&mut buffer, missile.speed);
+            buffer.push(missile.owner as u8);
+        }
+        buffer
+    }
+}
+
+/// État du monde décodé (côté client).
+pub struct WorldSnapshot {
+    /// Le temps de jeu.
+    pub time: f64,
+    /// Vrai si la partie est terminée.
+    pub over: bool,
+    /// Vrai si les joueurs ont gagné (partie terminée).
+    pub won: bool,
+    /// Les vaisseaux en jeu, avec l'indice de leur joueur.
+    pub ships: Vec<(usize, Spaceship)>,
+    /// Les astéroids.
+    pub asteroids: Vec<Asteroid>,
+    /// Les trous noirs.
+    pub blackholes: Vec<BlackHole>,
+    /// Les missiles.
+    pub missiles: Vec<Missile>,
+}
+
+impl WorldSnapshot {
+    /// Décode un instantané encodé avec `WorldView::encode`.
+    ///
+    /// # Arguments
+    /// - `data` L'instantané encodé.
+    ///
+    /// # Returns
+    /// - `Some(WorldSnapshot)` L'état du monde.
+    /// - `None` si l'instantané est invalide.
+    ///
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::new(data);
+        let time = f64::from_le_bytes(reader.bytes(8)?.try_into().ok()?);
+        let flags = reader.u8()?;
+
+        let ship_count = reader.u8()?;
+        let mut ships = Vec::with_capacity(ship_count as usize);
+        for _ in 0..ship_count {
+            let player_id = reader.u8()? as usize;
+            let mut ship = Spaceship::new(&ShipDefinition::default());
+            ship.set_motion(reader.vec2()?, reader.vec2()?, reader.f32()?);
+            ship.shield = reader.u8()? as u32;
+            ship.score = reader.u32()?;
+            ships.push((player_id, ship));
+        }
+
+        let mut asteroids = Vec::new();
+        for _ in 0..reader.u32()? {
+            let position = reader.vec2()?;
+            let speed = reader.vec2()?;
+            let shape = shape_from_u8(reader.u8()?)?;
+            let mut asteroid = Asteroid::new(position, speed, shape, reader.f32()?);
+            asteroid.set_rotation(reader.f32()?);
+            asteroid.set_material(*Material::ALL.get(reader.u8()? as usize)?);
+            asteroid.hit_points = reader.u8()? as u32;
+            asteroids.push(asteroid);
+        }
+
+        let mut blackholes = Vec::new();
+        for _ in 0..reader.u32()? {
+            let mut blackhole = BlackHole::new(reader.vec2()?);
+            blackhole.rotation = reader.f32()?;
+            blackholes.push(blackhole);
+        }
+
+        let mut missiles = Vec::new();
+        for _ in 0..reader.u32()? {
+            let position = reader.vec2()?;
+            let speed = reader.vec2()?;
+            let owner = reader.u8()? as usize;
+            missiles.push(Missile::new(position, speed, 5.0, 2.0, owner));
+        }
+
+        Some(Self {
+            time,
+            over: flags & 1 != 0,
+            won: flags & 2 != 0,
+            ships,
+            asteroids,
+            blackholes,
+            missiles,
+        })
+    }
+
+    /// Vaisseau d'un joueur dans l'instantané.
+    ///
+    /// # Arguments
+    /// - `player_id` L'indice du joueur.
+    ///
+    pub fn get_ship(&self, player_id: usize) -> Option<&Spaceship> {
+        self.ships
+            .iter()
+            .find(|(id, _)| *id == player_id)
+            .map(|(_, ship)| ship)
+    }
+}
+
+/// Calcule le delta entre deux instantanés encodés.
+/// Le delta est une suite de blocs `(octets identiques à sauter, octets modifiés, octets)`,
+/// précédée de la taille du nouvel instantané.
+///
+/// # Arguments
+/// - `base` L'instantané de référence (confirmé par le client).
+/// - `current` Le nouvel instantané.
+///
+/// # Returns
+/// - `Vec<u8>` Le delta encodé.
+///
+pub fn encode_delta(base: &[u8], current: &[u8]) -> Vec<u8> {
+    let mut delta = Vec::new();
+    put_u32(&mut delta, current.len() as u32);
+    let same = |i: usize| base.get(i) == Some(&current[i]);
+    let mut i = 0;
+    while i < current.len() {
+        let start = i;
+        while i < current.len() && same(i) && i - start < u16::MAX as usize {
+            i += 1;
+        }
+        let skip = i - start;
+        let literal_start = i;
+        while i < current.len() && !same(i) && i - literal_start < u16::MAX as usize {
+            i += 1;
+        }
+        delta.extend_from_slice(&(skip as u16).to_le_bytes());
+        delta.extend_from_slice(&((i - literal_start) as u16).to_le_bytes());
+        delta.extend_from_slice(&current[literal_start..i]);
+    }
+    delta
+}
+
+/// Reconstruit un instantané à partir de sa référence et d'un delta.
+///
+/// # Arguments
+/// - `base` L'instantané de référence.
+/// - `delta` Le delta calculé par `encode_delta`.
+///
+/// # Returns
+/// - `Some(Vec<u8>)` Le nouvel instantané.
+/// - `None` si le delta est invalide (y compris une taille annoncée plus grande qu'un datagramme).
+///
+pub fn decode_delta(base: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let mut reader = Reader::new(delta);
+    let length = reader.u32()? as usize;
+    // Un instantané complet tient dans un datagramme : refuser avant d'allouer
+    if length > MAX_DATAGRAM {
+        return None;
+    }
+    let mut current = Vec::with_capacity(length);
+    while current.len() < length {
+        let skip = reader.u16()? as usize;
+        let literal = reader.u16()? as usize;
+        current.extend_from_slice(base.get(current.len()..current.len() + skip)?);
+        current.extend_from_slice(reader.bytes(literal)?);
+    }
+    (current.len() == length).then_some(current)
+}
+
+/// Recale le vaisseau prédit sur l'état de l'hôte, puis rejoue les commandes
+/// que l'hôte n'a pas encore traitées.
+///
+/// # Arguments
+/// - `predicted` Le vaisseau prédit localement.
+/// - `authoritative` Le vaisseau tel que simulé par l'hôte.
+/// - `pending` Les commandes envoyées mais pas encore traitées par l'hôte.
+///
+pub fn reconcile<'a>(
+    predicted: &mut Spaceship,
+    authoritative: &Spaceship,
+    pending: impl Iterator<Item = &'a ShipInput>,
+) {
+    predicted.set_motion(
+        authoritative.get_position(),
+        authoritative.get_speed(),
+        authoritative.get_rotation(),
+    );
+    predicted.shield = authoritative.shield;
+    predicted.score = authoritative.score;
+    for input in pending {
+        predicted.apply_input(NET_SCHEME, input);
+    }
+}
+
+/// Résout une adresse saisie par le joueur (`ip:port`, ou `ip` pour le port par défaut).
+///
+/// # Arguments
+/// - `address` L'adresse saisie.
+/// - `default_port` Le port utilisé si l'adresse n'en précise pas.
+///
+pub fn resolve_address(address: &str, default_port: u16) -> io::Result<SocketAddr> {
+    let address = address.trim();
+    if address.contains(':') {
+        address.to_socket_addrs()?
+    } else {
+        (address, default_port).to_socket_addrs()?
+    }
+    .next()
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "adresse invalide"))
+}
+
+/// Client connecté à l'hôte.
+struct RemoteClient {
+    address: SocketAddr,
+    player_id: usize,
+    last_input_seq: u32,
+    inputs: VecDeque<ShipInput>,
+    acked_snapshot: Option<u32>,
+    last_seen: Instant,
+}
+
+/// Hôte d'une partie en réseau.
+pub struct NetHost {
+    socket: UdpSocket,
+    clients: Vec<RemoteClient>,
+    snapshot_id: u32,
+    history: VecDeque<(u32, Vec<u8>)>,
+    last_sent: Instant,
+}
+
+impl NetHost {
+    /// Ouvre le port d'écoute de l'hôte.
+    ///
+    /// # Arguments
+    /// - `port` Le port UDP (0 pour un port libre quelconque).
+    ///
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", port))?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            clients: Vec::new(),
+            snapshot_id: 0,
+            history: VecDeque::new(),
+            last_sent: Instant::now(),
+        })
+    }
+
+    /// Port d'écoute effectif de l'hôte.
+    pub fn port(&self) -> u16 {
+        self.socket.local_addr().map(|a| a.port()).unwrap_or(0)
+    }
+
+    /// Indices et adresses des joueurs distants connectés.
+    pub fn players(&self) -> Vec<(usize, SocketAddr)> {
+        self.clients
+            .iter()
+            .map(|client| (client.player_id, client.address))
+            .collect()
+    }
+
+    /// Vérifie si un joueur distant est connecté.
+    pub fn is_connected(&self, player_id: usize) -> bool {
+        self.clients.iter().any(|c| c.player_id == player_id)
+    }
+
+    /// Premier indice de joueur libre (l'indice 0 est celui de l'hôte).
+    fn free_player_id(&self) -> Option<usize> {
+        (1..MAX_PLAYERS).find(|&id| !self.is_connected(id))
+    }
+
+    /// Traite les paquets reçus (connexions, commandes, départs),
+    /// retire les clients silencieux depuis trop longtemps et envoie un signe de vie
+    /// aux clients si rien ne leur a été envoyé récemment.
+    pub fn poll(&mut self) {
+        let mut buffer = vec![0; MAX_DATAGRAM];
+        while let Ok((size, address)) = self.socket.recv_from(&mut buffer) {
+            self.handle(&buffer[..size], address);
+            if let Some(client) = self.clients.iter_mut().find(|c| c.address == address) {
+                client.last_seen = Instant::now();
+            }
+        }
+        self.clients.retain(|c| c.last_seen.elapsed() < TIMEOUT);
+        if self.last_sent.elapsed() > KEEPALIVE {
+            for client in &self.clients {
+                let _ = self.socket.send_to(&[MSG_PING], client.address);
+            }
+            self.last_sent = Instant::now();
+        }
+    }
+
+    /// Traite un paquet reçu d'un client.
+    fn handle(&mut self, packet: &[u8], address: SocketAddr) {
+        let mut reader = Reader::new(packet);
+        match reader.u8() {
+            Some(MSG_JOIN) => {
+                let known = self.clients.iter().find(|c| c.address == address);
+                let player_id = match known {
+                    Some(client) => Some(client.player_id),
+                    None if reader.u8() == Some(PROTOCOL_VERSION) => self.free_player_id(),
+                    None => None,
+                };
+                match player_id {
+                    Some(player_id) => {
+                        if known.is_none() {
+                            self.clients.push(RemoteClient {
+                                address,
+                                player_id,
+                                last_input_seq: 0,
+                                inputs: VecDeque::new(),
+                                acked_snapshot: None,
+                                last_seen: Instant::now(),
+                            });
+                        }
+                        let _ = self
+                            .socket
+                            .send_to(&[MSG_WELCOME, player_id as u8], address);
+                    }
+                    None => {
+                        let _ = self.socket.send_to(&[MSG_REFUSED], address);
+                    }
+                }
+            }
+            Some(MSG_INPUT) => {
+                let Some(client) = self.clients.iter_mut().find(|c| c.address == address) else {
+                    return;
+                };
+                if let Some(ack) = reader.u32() {
+                    client.acked_snapshot = client.acked_snapshot.max(Some(ack));
+                }
+                let count = reader.u8().unwrap_or(0);
+                for _ in 0..count {
+                    let (Some(seq), Some(bits)) = (reader.u32(), reader.u8()) else {
+                        break;
+                    };
+                    if seq > client.last_input_seq {
+                        client.last_input_seq = seq;
+                        client.inputs.push_back(ShipInput::from_bits(bits));
+                    }
+                }
+                while client.inputs.len() > MAX_QUEUED_INPUTS {
+                    client.inputs.pop_front();
+                }
+            }
+            Some(MSG_LEAVE) => self.clients.retain(|c| c.address != address),
+            _ => {}
+        }
+    }
+
+    /// Récupère les commandes reçues d'un joueur depuis la dernière frame.
+    ///
+    /// # Arguments
+    /// - `player_id` L'indice du joueur.
+    ///
+    pub fn take_inputs(&mut self, player_id: usize) -> Vec<ShipInput> {
+        self.clients
+            .iter_mut()
+            .find(|c| c.player_id == player_id)
+            .map(|c| c.inputs.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Envoie l'état du monde à tous les clients.
+    /// Un client ayant confirmé un instantané encore en mémoire reçoit un delta,
+    /// les autres reçoivent l'instantané complet.
+    ///
+    /// # Arguments
+    /// - `world` L'instantané encodé (voir `WorldView::encode`).
+    ///
+    pub fn broadcast(&mut self, world: &[u8]) {
+        self.snapshot_id += 1;
+        for client in &self.clients {
+            let base = client
+                .acked_snapshot
+                .and_then(|ack| self.history.iter().find(|(id, _)| *id == ack));
+            let mut packet = Vec::with_capacity(world.len() + 16);
+            match base {
+                Some((base_id, base)) => {
+                    packet.push(MSG_DELTA);
+                    put_u32(&mut packet, self.snapshot_id);
+                    put_u32(&mut packet, *base_id);
+                    put_u32(&mut packet, client.last_input_seq);
+                    packet.extend_from_slice(&encode_delta(base, world));
+                }
+                None => {
+                    packet.push(MSG_SNAPSHOT);
+                    put_u32(&mut packet, self.snapshot_id);
+                    put_u32(&mut packet, client.last_input_seq);
+                    packet.extend_from_slice(world);
+                }
+            }
+            let _ = self.socket.send_to(&packet, client.address);
+        }
+        self.history.push_back((self.snapshot_id, world.to_vec()));
+        if self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.last_sent = Instant::now();
+    }
+
+    /// Prévient les clients que l'hôte quitte la partie.
+    pub fn close(&self) {
+        for client in &self.clients {
+            let _ = self.socket.send_to(&[MSG_LEAVE], client.address);
+        }
+    }
+}
+
+/// Évènements reçus par un client.
+pub enum ClientEvent {
+    /// Connexion acceptée (voir `NetClient::get_player_id`).
+    Welcome,
+    /// Connexion refusée par l'hôte.
+    Refused,
+    /// Nouvel état du monde.
+    Snapshot(WorldSnapshot),
+    /// L'hôte a quitté la partie.
+    HostLeft,
+}
+
+/// Client d'une partie en réseau.
+pub struct NetClient {
+    socket: UdpSocket,
+    host: SocketAddr,
+    player_id: Option<usize>,
+    next_input_seq: u32,
+    pending: VecDeque<(u32, ShipInput)>,
+    history: VecDeque<(u32, Vec<u8>)>,
+    last_join: Instant,
+    last_heard: Instant,
+    last_sent: Instant,
+}
+
+impl NetClient {
+    /// Se connecte à un hôte (envoie la demande de connexion).
+    ///
+    /// # Arguments
+    /// - `address` L'adresse de l'hôte (`ip:port`, ou `ip` pour le port par défaut).
+    ///
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let host = resolve_address(address, DEFAULT_PORT)?;
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.set_nonblocking(true)?;
+        socket.send_to(&[MSG_JOIN, PROTOCOL_VERSION], host)?;
+        Ok(Self {
+            socket,
+            host,
+            player_id: None,
+            next_input_seq: 1,
+            pending: VecDeque::new(),
+            history: VecDeque::new(),
+            last_join: Instant::now(),
+            last_heard: Instant::now(),
+            last_sent: Instant::now(),
+        })
+    }
+
+    /// Indice du joueur attribué par l'hôte (une fois la connexion acceptée).
+    pub fn get_player_id(&self) -> Option<usize> {
+        self.player_id
+    }
+
+    /// Vérifie si l'hôte n'a plus donné de nouvelles depuis trop longtemps.
+    pub fn is_timed_out(&self) -> bool {
+        self.last_heard.elapsed() > TIMEOUT
+    }
+
+    /// Commandes envoyées mais pas encore traitées par l'hôte.
+    pub fn pending_inputs(&self) -> impl Iterator<Item = &ShipInput> {
+        self.pending.iter().map(|(_, input)| input)
+    }
+
+    /// Traite les paquets reçus de l'hôte.
+    /// Seul le plus récent des instantanés reçus est renvoyé.
+    /// Une fois connecté, envoie un signe de vie à l'hôte si rien ne lui a été envoyé récemment.
+    ///
+    /// # Returns
+    /// - `Vec<ClientEvent>` Les évènements reçus.
+    ///
+    pub fn poll(&mut self) -> Vec<ClientEvent> {
+        if self.player_id.is_none() && self.last_join.elapsed() > JOIN_RETRY {
+            let _ = self
+                .socket
+                .send_to(&[MSG_JOIN, PROTOCOL_VERSION], self.host);
+            self.last_join = Instant::now();
+        }
+        if self.player_id.is_some() && self.last_sent.elapsed() > KEEPALIVE {
+            let _ = self.socket.send_to(&[MSG_PING], self.host);
+            self.last_sent = Instant::now();
+        }
+
+        let mut events = Vec::new();
+        let mut latest: Option<(u32, u32)> = None;
+        let mut buffer = vec![0; MAX_DATAGRAM];
+        while let Ok((size, address)) = self.socket.recv_from(&mut buffer) {
+            if address != self.host {
+                continue;
+            }
+            self.last_heard = Instant::now();
+            let mut reader = Reader::new(&buffer[..size]);
+            match reader.u8() {
+                Some(MSG_WELCOME) => {
+                    if let (None, Some(player_id)) = (self.player_id, reader.u8()) {
+                        self.player_id = Some(player_id as usize);
+                        events.push(ClientEvent::Welcome);
+                    }
+                }
+                Some(MSG_REFUSED) => events.push(ClientEvent::Refused),
+                Some(MSG_LEAVE) => events.push(ClientEvent::HostLeft),
+                Some(kind @ (MSG_SNAPSHOT | MSG_DELTA)) => {
+                    if let Some((id, last_input)) = self.receive_world(kind, &mut reader) {
+                        if latest.is_none_or(|(latest_id, _)| id > latest_id) {
+                            latest = Some((id, last_input));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some((id, last_input)) = latest {
+            self.pending.retain(|(seq, _)| *seq > last_input);
+            let world = self.history.iter().find(|(i, _)| *i == id);
+            if let Some(snapshot) = world.and_then(|(_, data)| WorldSnapshot::decode(data)) {
+                events.push(ClientEvent::Snapshot(snapshot));
+            }
+        }
+        events
+    }
+
+    /// Reconstruit un instantané (complet ou delta) et le garde en mémoire.
+    ///
+    /// # Returns
+    /// - `Some((id, last_input))` L'identifiant de l'instantané et la dernière commande traitée.
+    ///
+    fn receive_world(&mut self, kind: u8, reader: &mut Reader) -> Option<(u32, u32)> {
+        let id = reader.u32()?;
+        let world = if kind == MSG_DELTA {
+            let base_id = reader.u32()?;
+            let last_input = reader.u32()?;
+            let (_, base) = self.history.iter().find(|(i, _)| *i == base_id)?;
+            (decode_delta(base, reader.rest())?, last_input)
+        } else {
+            let last_input = reader.u32()?;
+            (reader.rest().to_vec(), last_input)
+        };
+        if self.history.iter().any(|(i, _)| *i >= id) {
+            return None;
+        }
+        self.history.push_back((id, world.0));
+        if self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+        }
+        Some((id, world.1))
+    }
+
+    /// Envoie les commandes de la frame à l'hôte.
+    /// Les dernières commandes non confirmées sont renvoyées avec, en cas de perte.
+    ///
+    /// # Arguments
+    /// - `input` Les commandes du joueur.
+    ///
+    pub fn send_input(&mut self, input: ShipInput) {
+        self.pending.push_back((self.next_input_seq, input));
+        self.next_input_seq += 1;
+        while self.pending.len() > MAX_PENDING_INPUTS {
+            self.pending.pop_front();
+        }
+
+        let ack = self.history.back().map_or(0, |(id, _)| *id);
+        let count = self.pending.len().min(REDUNDANT_INPUTS);
+        let mut packet = vec![MSG_INPUT];
+        put_u32(&mut packet, ack);
+        packet.push(count as u8);
+        for (seq, input) in self.pending.iter().skip(self.pending.len() - count) {
+            put_u32(&mut packet, *seq);
+            packet.push(input.to_bits());
+        }
+        let _ = self.socket.send_to(&packet, self.host);
+        self.last_sent = Instant::now();
+    }
+
+    /// Prévient l'hôte que le client quitte la partie.
+    pub fn leave(&self) {
+        let _ = self.socket.send_to(&[MSG_LEAVE], self.host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    /// Attend qu'une condition soit vraie (boucle locale, quelques millisecondes).
+    fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+        for _ in 0..200 {
+            if condition() {
+                return true;
+            }
+            sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn test_world_round_trip_and_delta() {
+        let ship = Spaceship::new(&ShipDefinition::default());
+        let asteroids = vec![Asteroid::new(
+            vec2(10.0, 20.0),
+            vec2(1.0, -1.0),
+            AsteroidShape::Large,
+            70.0,
+        )];
+        let blackholes = vec![BlackHole::new(vec2(300.0, 200.0))];
+        let missiles = vec![Missile::new(vec2(5.0, 5.0), vec2(0.0, -5.0), 5.0, 2.0, 1)];
+        let view = WorldView {
+            time: 12.5,
+            over: false,
+            won: false,
+            ships: vec![(0, &ship)],
+            asteroids: &asteroids,
+            blackholes: &blackholes,
+            missiles: &missiles,
+        };
+        let base = view.encode();
+
+        let world = WorldSnapshot::decode(&base).unwrap();
+        assert_eq!(world.time, 12.5);
+        assert_eq!(
+            world.get_ship(0).unwrap().get_position(),
+            ship.get_position()
+        );
+        assert_eq!(world.asteroids[0].position, vec2(10.0, 20.0));
+        assert_eq!(world.blackholes[0].position, vec2(300.0, 200.0));
+        assert_eq!(world.missiles[0].owner, 1);
+
+        let current = WorldView {
+            time: 12.6,
+            missiles: &[],
+            ..view
+        }
+        .encode();
+        let delta = encode_delta(&base, &current);
+        assert!(delta.len() < current.len());
+        assert_eq!(decode_delta(&base, &delta).unwrap(), current);
+        assert_eq!(
+            decode_delta(&current, &encode_delta(&current, &base)).unwrap(),
+            base
+        );
+    }
+
+    #[test]
+    fn test_decode_delta_rejects_oversized_length() {
+        let mut delta = Vec::new();
+        put_u32(&mut delta, u32::MAX);
+        assert_eq!(decode_delta(&[], &delta), None);
+
+        let mut delta = Vec::new();
+        put_u32(&mut delta, MAX_DATAGRAM as u32 + 1);
+        delta.extend_from_slice(&0u16.to_le_bytes());
+        delta.extend_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(decode_delta(&[], &delta), None);
+    }
+
+    #[test]
+    fn test_net_scheme_does_not_need_the_aim() {
+        let input = ShipInput {
+            up: true,
+            right: true,
+            fire: true,
+            aim: vec2(640.0, 120.0),
+            ..Default::default()
+        };
+        let mut local = Spaceship::new(&ShipDefinition::default());
+        let mut remote = Spaceship::new(&ShipDefinition::default());
+        local.apply_input(NET_SCHEME, &input);
+        remote.apply_input(NET_SCHEME, &ShipInput::from_bits(input.to_bits()));
+        assert_eq!(local.get_position(), remote.get_position());
+        assert_eq!(local.get_rotation(), remote.get_rotation());
+    }
+
+    #[test]
+    fn test_reconcile_replays_pending_inputs() {
+        let inputs = [ShipInput {
+            up: true,
+            ..Default::default()
+        }; 6];
+        let mut host_ship = Spaceship::new(&ShipDefinition::default());
+        for input in &inputs[..2] {
+            host_ship.apply_input(NET_SCHEME, input);
+        }
+        let mut expected = Spaceship::new(&ShipDefinition::default());
+        for input in &inputs {
+            expected.apply_input(NET_SCHEME, input);
+        }
+
+        let mut predicted = Spaceship::new(&ShipDefinition::default());
+        reconcile(&mut predicted, &host_ship, inputs[2..].iter());
+        assert_eq!(predicted.get_position(), expected.get_position());
+    }
+
+    #[test]
+    fn test_host_and_client_over_loopback() {
+        let mut host = NetHost::bind(0).unwrap();
+        let mut client = NetClient::connect(&format!("127.0.0.1:{}", host.port())).unwrap();
+
+        assert!(wait_for(|| {
+            host.poll();
+            client.poll();
+            client.get_player_id().is_some()
+        }));
+        assert_eq!(client.get_player_id(), Some(1));
+        assert!(host.is_connected(1));
+
+        // Salon d'attente : les signes de vie maintiennent la connexion dans les deux sens.
+        let silence = Instant::now() - Duration::from_secs(4);
+        host.clients[0].last_seen = silence;
+        client.last_heard = silence;
+        host.last_sent = silence;
+        client.last_sent = silence;
+        assert!(wait_for(|| {
+            host.poll();
+            client.poll();
+            host.clients[0].last_seen > silence && client.last_heard > silence
+        }));
+        assert!(!client.is_timed_out());
+
+        let input = ShipInput {
+            left: true,
+            ..Default::default()
+        };
+        client.send_input(input);
+        assert!(wait_for(|| {
+            host.poll();
+            host.clients[0].last_input_seq == 1
+        }));
+        assert_eq!(host.take_inputs(1), vec![input]);
+
+        let ship = Spaceship::new(&ShipDefinition::default());
+        let view = WorldView {
+            time: 1.0,
+            over: false,
+            won: false,
+            ships: vec![(1, &ship)],
+            asteroids: &[],
+            blackholes: &[],
+            missiles: &[],
+        };
+        host.broadcast(&view.encode());
+        let mut received = None;
+        assert!(wait_for(|| {
+            received = client.poll().into_iter().find_map(|event| match event {
+                ClientEvent::Snapshot(world) => Some(world),
+                _ => None,
+            });
+            received.is_some()
+        }));
+        assert_eq!(received.unwrap().time, 1.0);
+        assert_eq!(client.pending_inputs().count(), 0);
+
+        // Le client confirme l'instantané : l'hôte envoie ensuite un delta.
+        client.send_input(ShipInput::default());
+        assert!(wait_for(|| {
+            host.poll();
+            host.clients[0].acked_snapshot == Some(1)
+        }));
+        host.broadcast(&WorldView { time: 2.0, ..view }.encode());
+        let mut received = None;
+        assert!(wait_for(|| {
+            received = client.poll().into_iter().find_map(|event| match event {
+                ClientEvent::Snapshot(world) => Some(world),
+                _ => None,
+            });
+            received.is_some()
+        }));
+        assert_eq!(received.unwrap().time, 2.0);
+
+        client.leave();
+        assert!(wait_for(|| {
+            host.poll();
+            !host.is_connected(1)
+        }));
+    }
+}
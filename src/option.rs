@@ -1,18 +1,21 @@
 use crate::asteroid::Asteroid;
 use crate::bind::{binding, Bind};
+use crate::control::ControlScheme;
+use crate::settings::Settings;
 use crate::sound::{setup_sound, Sound};
+use crate::spectate::SPECTATOR_PORT;
 use crate::utils::background_asteroids;
 use macroquad::prelude::*;
 
 /// Menu des options.
-/// Permet d'accèder aux réglages du binding des touches et du niveau du son.
+/// Permet d'accèder aux réglages du binding des touches, du niveau du son, du schéma de contrôle, de la diffusion aux spectateurs, du bouclier et du directeur adaptatif.
 ///
 /// # Arguments
 /// - `background_texture` La texture de l'arrière-plan.
 /// - `asteroid_texture` La texture des astéroids.
 /// - `asteroids` Les astéroids.
 /// - `sound` Les sons du jeu.
-/// - `actual_binding` Le Bind actuel.
+/// - `bindings` Les Binds actuels des deux joueurs.
 /// - `last_press` Le temps de la dernière pression de touche (pour éviter les pressions multiples).
 ///
 pub async fn option_menu(
@@ -20,11 +23,21 @@ pub async fn option_menu(
     asteroid_texture: &Texture2D,
     asteroids: &mut Vec<Asteroid>,
     sound: &mut Sound,
-    actual_binding: &mut Bind,
+    bindings: &mut [Bind; 2],
     last_press: f64,
 ) {
     let mut selected_index = 0;
-    let options = ["Binding", "Sound", "Back"];
+    let options = [
+        "Binding",
+        "Binding P2",
+        "Sound",
+        "Controls",
+        "Spectator stream",
+        "Shield",
+        "Adaptive difficulty",
+        "Back",
+    ];
+    let mut settings = Settings::load();
 
     loop {
         clear_background(BLACK);
@@ -41,6 +54,36 @@ pub async fn option_menu(
 
         for (i, &option) in options.iter().enumerate() {
             let color = if i == selected_index { YELLOW } else { WHITE };
+            let label = if option == "Spectator stream" {
+                let state = if settings.spectator_stream {
+                    format!("On (port {})", SPECTATOR_PORT)
+                } else {
+                    "Off".to_string()
+                };
+                format!("{} : {}", option, state)
+            } else if option == "Shield" {
+                let shield = if settings.energy_shield {
+                    "Energy"
+                } else {
+                    "Pips"
+                };
+                format!("{} : {}", option, shield)
+            } else if option == "Adaptive difficulty" {
+                let state = if settings.adaptive_director {
+                    "On"
+                } else {
+                    "Off"
+                };
+                format!("{} : {}", option, state)
+            } else if option == "Controls" {
+                let scheme = settings
+                    .control_scheme
+                    .map_or("Mode default", ControlScheme::name);
+                format!("{} : {}", option, scheme)
+            } else {
+                option.to_string()
+            };
+            let option = label.as_str();
             let dimension = measure_text(option, None, 40, 1.0);
             if option == "Back" {
                 draw_text(
@@ -81,19 +124,57 @@ pub async fn option_menu(
             if is_key_pressed(KeyCode::Enter) {
                 match selected_index {
                     0 => {
+                        let [player_one, player_two] = bindings;
                         binding(
                             background_texture,
                             asteroid_texture,
                             asteroids,
                             sound,
-                            actual_binding,
+                            player_one,
+                            player_two,
+                            "Binding",
+                            Bind::new,
                         )
                         .await;
                     }
                     1 => {
-                        setup_sound(sound, background_texture, asteroid_texture, asteroids).await;
+                        let [player_one, player_two] = bindings;
+                        binding(
+                            background_texture,
+                            asteroid_texture,
+                            asteroids,
+                            sound,
+                            player_two,
+                            player_one,
+                            "Binding P2",
+                            Bind::new_player_two,
+                        )
+                        .await;
                     }
                     2 => {
+                        setup_sound(sound, background_texture, asteroid_texture, asteroids).await;
+                    }
+                    3 => {
+                        sound.play_sound_effect("select_menu");
+                        settings.next_control_scheme();
+                        settings.save();
+                    }
+                    4 => {
+                        sound.play_sound_effect("select_menu");
+                        settings.spectator_stream = !settings.spectator_stream;
+                        settings.save();
+                    }
+                    5 => {
+                        sound.play_sound_effect("select_menu");
+                        settings.energy_shield = !settings.energy_shield;
+                        settings.save();
+                    }
+                    6 => {
+                        sound.play_sound_effect("select_menu");
+                        settings.adaptive_director = !settings.adaptive_director;
+                        settings.save();
+                    }
+                    7 => {
                         sound.play_sound_effect("select_menu");
                         return;
                     }
@@ -0,0 +1,167 @@
+//! # Ore Module
+//!
+//! Ce module gère le minerai laissé par les astéroïdes détruits (modes avec boutique,
+//! voir `ModeRules::ore_shop`). Le minerai dérive lentement et disparaît au bout de quelques secondes ;
+//! le vaisseau le ramasse en passant dessus, ou l'attire à lui grâce à l'aimant (amélioration de la boutique).
+//! Le minerai ramassé est dépensé dans la boutique entre deux vagues (voir le module `shop`).
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::impl_stellar_object;
+use crate::rng::game_rng;
+use crate::spaceship::Spaceship;
+use crate::stellarobject::StellarObject;
+use ::rand::Rng;
+use macroquad::prelude::*;
+
+/// Minerai laissé par un astéroïde.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ore {
+    pub position: Vec2,
+    pub speed: Vec2,
+    pub size: f32,
+    /// Quantité de minerai.
+    amount: u32,
+    /// Temps de l'apparition, en secondes.
+    drop_time: f64,
+}
+
+impl Ore {
+    /// Taille (rayon) du minerai.
+    const SIZE: f32 = 8.0;
+    /// Durée (en secondes) avant la disparition du minerai.
+    const LIFETIME: f64 = 10.0;
+    /// Part de la vitesse de l'astéroïde conservée par le minerai.
+    const DRIFT: f32 = 0.3;
+    /// Vitesse (pixels par frame) du minerai attiré par l'aimant.
+    const MAGNET_SPEED: f32 = 5.0;
+
+    /// Créer du minerai.
+    ///
+    /// # Arguments
+    /// - `position` La position.
+    /// - `speed` La vitesse de dérive.
+    /// - `amount` La quantité de minerai.
+    /// - `drop_time` Le temps de l'apparition.
+    ///
+    pub fn new(position: Vec2, speed: Vec2, amount: u32, drop_time: f64) -> Self {
+        Self {
+            position,
+            speed,
+            size: Self::SIZE,
+            amount,
+            drop_time,
+        }
+    }
+
+    /// Minerai laissé (ou non, selon la chance) par un astéroïde détruit :
+    /// les gros astéroïdes en laissent plus souvent, et davantage.
+    ///
+    /// # Arguments
+    /// - `asteroid` L'astéroïde détruit.
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn drop_from(asteroid: &Asteroid, time: f64) -> Option<Self> {
+        let (chance, amount) = match asteroid.get_shape() {
+            AsteroidShape::Small => (0.15, 1),
+            AsteroidShape::Medium => (0.3, 2),
+            AsteroidShape::Large => (0.5, 3),
+        };
+        game_rng().gen_bool(chance).then(|| {
+            Self::new(
+                asteroid.get_position(),
+                asteroid.get_speed() * Self::DRIFT,
+                amount,
+                time,
+            )
+        })
+    }
+
+    /// Déplace le minerai, ramasse celui que le vaisseau touche et retire celui qui a disparu.
+    ///
+    /// # Arguments
+    /// - `ores` Le minerai présent sur le terrain.
+    /// - `spaceship` Le vaisseau du joueur (position, aimant et réserve de minerai).
+    /// - `time` Le temps de jeu.
+    ///
+    /// # Returns
+    /// - `u32` La quantité de minerai ramassée.
+    ///
+    pub fn update_all(ores: &mut Vec<Ore>, spaceship: &mut Spaceship, time: f64) -> u32 {
+        let ship = spaceship.get_position();
+        let magnet_range = spaceship.get_upgrades().magnet_range();
+        let reach = spaceship.get_size() + Self::SIZE;
+        let mut collected = 0;
+        ores.retain_mut(|ore| {
+            let distance = ore.position.distance(ship);
+            if distance <= reach {
+                collected += ore.amount;
+                return false;
+            }
+            ore.position = if distance <= reach + magnet_range {
+                ore.position + (ship - ore.position).normalize_or_zero() * Self::MAGNET_SPEED
+            } else {
+                Self::bound_pos(ore.position + ore.speed)
+            };
+            time - ore.drop_time < Self::LIFETIME
+        });
+        spaceship.ore += collected;
+        collected
+    }
+
+    /// Dessine le minerai (losange clignotant avant sa disparition).
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn draw(&self, time: f64) {
+        let remaining = Self::LIFETIME - (time - self.drop_time);
+        if remaining < 3.0 && ((time * 8.0) as u64).is_multiple_of(2) {
+            return;
+        }
+        let Vec2 { x, y } = self.position;
+        let color = Color::new(0.3, 0.9, 1.0, 1.0);
+        draw_triangle(
+            vec2(x, y - self.size),
+            vec2(x - self.size, y),
+            vec2(x + self.size, y),
+            color,
+        );
+        draw_triangle(
+            vec2(x, y + self.size),
+            vec2(x - self.size, y),
+            vec2(x + self.size, y),
+            color,
+        );
+    }
+}
+
+impl_stellar_object!(Ore);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shipclass::ShipDefinition;
+    use crate::shop::Upgrade;
+
+    #[test]
+    fn test_ore_is_collected_attracted_or_lost() {
+        let mut spaceship = Spaceship::new(&ShipDefinition::default());
+        let ship = spaceship.get_position();
+        let mut ores = vec![
+            Ore::new(ship + vec2(10.0, 0.0), Vec2::ZERO, 2, 0.0),
+            Ore::new(ship + vec2(60.0, 0.0), Vec2::ZERO, 3, 0.0),
+            Ore::new(ship + vec2(300.0, 0.0), vec2(1.0, 0.0), 1, 0.0),
+        ];
+        assert_eq!(Ore::update_all(&mut ores, &mut spaceship, 1.0), 2);
+        assert_eq!(spaceship.ore, 2);
+        assert_eq!(ores.len(), 2);
+        assert_eq!(ores[0].position, ship + vec2(60.0, 0.0));
+        assert_eq!(ores[1].position, ship + vec2(301.0, 0.0));
+
+        spaceship.apply_upgrade(Upgrade::MagnetRange);
+        Ore::update_all(&mut ores, &mut spaceship, 2.0);
+        assert_eq!(ores[0].position, ship + vec2(55.0, 0.0));
+
+        assert_eq!(Ore::update_all(&mut ores, &mut spaceship, 20.0), 0);
+        assert!(ores.is_empty());
+    }
+}
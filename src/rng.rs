@@ -0,0 +1,76 @@
+//! # Rng Module
+//!
+//! Ce module fournit le générateur aléatoire utilisé par le jeu (astéroïdes, matériaux, boss).
+//!
+//! Par défaut, `game_rng()` utilise le générateur du thread (`thread_rng`). Une graine peut être
+//! fixée avec `set_seed` (défi quotidien) : toutes les parties jouées avec la même graine
+//! tirent alors la même suite de nombres.
+use ::rand::rngs::StdRng;
+use ::rand::{thread_rng, Error, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Générateur à graine fixe, s'il est activé.
+    static SEEDED: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Fixe (ou retire) la graine du générateur du jeu.
+///
+/// # Arguments
+/// - `seed` La graine à utiliser, ou `None` pour revenir au générateur du thread.
+///
+pub fn set_seed(seed: Option<u64>) {
+    SEEDED.with(|seeded| *seeded.borrow_mut() = seed.map(StdRng::seed_from_u64));
+}
+
+/// Générateur aléatoire du jeu (à graine fixe si `set_seed` a été appelé).
+pub fn game_rng() -> GameRng {
+    GameRng
+}
+
+/// Générateur aléatoire du jeu, voir `game_rng`.
+pub struct GameRng;
+
+impl GameRng {
+    /// Appelle `f` avec le générateur à graine fixe, ou avec le générateur du thread.
+    fn with<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        SEEDED.with(|seeded| match seeded.borrow_mut().as_mut() {
+            Some(rng) => f(rng),
+            None => f(&mut thread_rng()),
+        })
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        Self::with(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Self::with(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Self::with(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Self::with(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rand::Rng;
+
+    #[test]
+    fn test_same_seed_gives_same_sequence() {
+        set_seed(Some(42));
+        let first: Vec<u32> = (0..5).map(|_| game_rng().gen_range(0..1000)).collect();
+        set_seed(Some(42));
+        let second: Vec<u32> = (0..5).map(|_| game_rng().gen_range(0..1000)).collect();
+        set_seed(None);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,434 @@
+//! # Rules Module
+//!
+//! Ce module définit les règles de chaque mode de jeu solo (`ModeRules`) :
+//! apparition des astéroïdes, accélération de la partie et conditions de victoire ou de défaite.
+//!
+//! - `classic` / `modern` : les astéroïdes cessent d'apparaître après 45 s, la partie est gagnée une fois le terrain nettoyé.
+//! - `time_attack` : marquer un maximum de points avant la fin du temps imparti.
+//! - `survival` : les astéroïdes apparaissent sans fin et la difficulté augmente indéfiniment.
+//! - `zen` : pas de mort (le vaisseau réapparaît), pour s'entraîner.
+//! - `defend` : protéger une station spatiale vers laquelle les astéroïdes sont lancés ;
+//!   la partie se termine lorsqu'elle est détruite (voir le module `station`).
+//! - `daily` : défi quotidien chronométré (voir le module `daily`).
+//!
+//! Dans les modes `time_attack`, `survival` et `defend`, les astéroïdes détruits laissent
+//! du minerai, dépensé dans une boutique d'améliorations entre deux vagues (voir le module `shop`),
+//! et un boss apparaît toutes les quelques vagues (voir le module `boss`).
+use crate::asteroid::Asteroid;
+use crate::difficulty::Difficulty;
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, handle_enter_press, sigmoid_range_secs, sigmoid_speed};
+use macroquad::prelude::*;
+use std::time::Instant;
+
+/// Règles d'un mode de jeu solo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModeRules {
+    /// Identifiant du mode (utilisé pour les meilleurs scores).
+    pub name: &'static str,
+    /// Nom affiché du mode.
+    pub title: &'static str,
+    /// Variante de jeu utilisée pour la mise à jour et l'affichage ('classic' ou 'modern').
+    pub flavor: &'static str,
+    /// Temps (en secondes) après lequel les astéroïdes cessent d'apparaître (`None` : jamais).
+    pub spawn_until: Option<f64>,
+    /// La partie est gagnée lorsque le terrain est nettoyé après ce temps (en secondes).
+    pub clear_after: Option<f64>,
+    /// Durée de la partie (en secondes) : la partie se termine à la fin du temps imparti.
+    pub time_limit: Option<f64>,
+    /// La difficulté augmente indéfiniment (au lieu de plafonner).
+    pub endless_ramp: bool,
+    /// Le vaisseau peut être détruit (sinon il réapparaît).
+    pub can_die: bool,
+    /// Facteur appliqué à la vitesse des astéroïdes.
+    pub speed_factor: f64,
+    /// Facteur appliqué au délai entre deux apparitions d'astéroïdes.
+    pub spawn_factor: f64,
+    /// Les collisions entre astéroïdes forment des trous noirs.
+    pub black_holes: bool,
+    /// Les missiles traversent les bords de l'écran (au lieu de disparaître).
+    pub missile_wrap: bool,
+    /// Vitesse des missiles.
+    pub missile_speed: f32,
+    /// La partie est gagnée lorsque ce score est atteint.
+    pub score_target: Option<u32>,
+    /// Niveau de difficulté (courbes d'apparition et de vitesse, trous noirs).
+    pub difficulty: Difficulty,
+    /// Décalage de la répartition des tailles des nouveaux astéroïdes (voir `Asteroid::new_random_biased`).
+    pub size_bias: f32,
+    /// Une station spatiale à défendre est placée sur le terrain et les astéroïdes sont lancés vers elle.
+    pub station: bool,
+    /// Les astéroïdes détruits laissent du minerai, dépensé dans la boutique entre deux vagues.
+    pub ore_shop: bool,
+    /// Un boss apparaît toutes les quelques vagues (voir le module `boss`).
+    pub bosses: bool,
+}
+
+impl ModeRules {
+    /// Tous les modes de jeu solo.
+    pub const ALL: [ModeRules; 7] = [
+        ModeRules {
+            name: "classic",
+            title: "Classic",
+            flavor: "classic",
+            spawn_until: Some(45.0),
+            clear_after: Some(10.0),
+            time_limit: None,
+            endless_ramp: false,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: false,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: false,
+            bosses: false,
+        },
+        ModeRules {
+            name: "modern",
+            title: "Modern",
+            flavor: "modern",
+            spawn_until: Some(45.0),
+            clear_after: Some(45.0),
+            time_limit: None,
+            endless_ramp: false,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: true,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: false,
+            bosses: false,
+        },
+        ModeRules {
+            name: "time_attack",
+            title: "Time Attack",
+            flavor: "modern",
+            spawn_until: None,
+            clear_after: None,
+            time_limit: Some(180.0),
+            endless_ramp: false,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: true,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: true,
+            bosses: true,
+        },
+        ModeRules {
+            name: "survival",
+            title: "Survival",
+            flavor: "modern",
+            spawn_until: None,
+            clear_after: None,
+            time_limit: None,
+            endless_ramp: true,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: true,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: true,
+            bosses: true,
+        },
+        ModeRules {
+            name: "zen",
+            title: "Endless Zen",
+            flavor: "classic",
+            spawn_until: None,
+            clear_after: None,
+            time_limit: None,
+            endless_ramp: false,
+            can_die: false,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: false,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: false,
+            bosses: false,
+        },
+        ModeRules {
+            name: "defend",
+            title: "Station Defense",
+            flavor: "modern",
+            spawn_until: None,
+            clear_after: None,
+            time_limit: None,
+            endless_ramp: true,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: false,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: true,
+            ore_shop: true,
+            bosses: true,
+        },
+        ModeRules {
+            name: "daily",
+            title: "Daily Challenge",
+            flavor: "modern",
+            spawn_until: None,
+            clear_after: None,
+            time_limit: Some(120.0),
+            endless_ramp: false,
+            can_die: true,
+            speed_factor: 1.0,
+            spawn_factor: 1.0,
+            black_holes: true,
+            missile_wrap: false,
+            missile_speed: 5.0,
+            score_target: None,
+            difficulty: Difficulty::Normal,
+            size_bias: 0.0,
+            station: false,
+            ore_shop: false,
+            bosses: false,
+        },
+    ];
+
+    /// Règles d'un mode de jeu.
+    ///
+    /// # Arguments
+    /// - `name` L'identifiant du mode.
+    ///
+    pub fn for_mode(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rules| rules.name == name)
+    }
+
+    /// Applique un niveau de difficulté : les courbes suivent le niveau choisi
+    /// et la durée des apparitions (et du nettoyage du terrain) est ajustée.
+    ///
+    /// # Arguments
+    /// - `difficulty` Le niveau de difficulté.
+    ///
+    pub fn with_difficulty(self, difficulty: Difficulty) -> Self {
+        let factor = difficulty.spawn_time_factor();
+        Self {
+            spawn_until: self.spawn_until.map(|until| until * factor),
+            clear_after: self.clear_after.map(|after| after * factor),
+            difficulty,
+            ..self
+        }
+    }
+
+    /// Vérifie si de nouveaux astéroïdes apparaissent encore.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn spawns(&self, time: f64) -> bool {
+        self.spawn_until.is_none_or(|until| time < until)
+    }
+
+    /// Délai minimal (en secondes) entre deux apparitions d'astéroïdes.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn spawn_interval(&self, time: f64) -> f64 {
+        let interval = if self.endless_ramp {
+            (0.9_f64.powf(time / 15.0)).max(0.1)
+        } else {
+            sigmoid_range_secs(time, 22.5, self.difficulty)
+        };
+        interval * self.spawn_factor
+    }
+
+    /// Facteur de vitesse des astéroïdes.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn asteroid_speed(&self, time: f64) -> f64 {
+        let speed = if self.endless_ramp {
+            sigmoid_speed(time, self.flavor, self.difficulty) + time / 120.0
+        } else {
+            sigmoid_speed(time, self.flavor, self.difficulty)
+        };
+        speed * self.speed_factor
+    }
+
+    /// Vérifie si la partie est gagnée : score atteint s'il y a un objectif de score,
+    /// sinon fin du temps imparti ou terrain nettoyé.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `field_cleared` Aucun astéroïde (ni boss) ne reste sur le terrain.
+    /// - `score` Le score du joueur.
+    ///
+    pub fn is_won(&self, time: f64, field_cleared: bool, score: u32) -> bool {
+        match self.score_target {
+            Some(target) => score >= target,
+            None => {
+                self.is_time_up(time)
+                    || self
+                        .clear_after
+                        .is_some_and(|after| field_cleared && time > after)
+            }
+        }
+    }
+
+    /// Vérifie si le temps imparti est écoulé (la partie se termine).
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn is_time_up(&self, time: f64) -> bool {
+        self.time_limit.is_some_and(|limit| time >= limit)
+    }
+
+    /// Temps restant (en secondes) avant la fin de la partie, s'il y a une limite.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    ///
+    pub fn time_left(&self, time: f64) -> Option<f64> {
+        self.time_limit.map(|limit| (limit - time).max(0.0))
+    }
+}
+
+/// Menu de sélection des modes supplémentaires (Time Attack, Survival, Endless Zen, Station Defense).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some(&str)` L'identifiant du mode choisi.
+/// - `None` si le joueur revient en arrière.
+///
+pub async fn choose_mode(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<&'static str> {
+    let modes: Vec<ModeRules> = ModeRules::ALL
+        .into_iter()
+        .filter(|rules| !matches!(rules.name, "classic" | "modern" | "daily"))
+        .collect();
+    let mut selected_index = 0;
+    sound.play_sound_effect("select_menu");
+
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("More Modes", None, 50, 1.0);
+        draw_text(
+            "More Modes",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+
+        for (i, rules) in modes.iter().enumerate() {
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            draw_text(
+                rules.title,
+                screen_width() / 2.0 - 100.0,
+                350.0 + i as f32 * 50.0,
+                40.0,
+                color,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % modes.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                modes.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            return Some(modes[selected_index].name);
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_outcomes() {
+        let time_attack = ModeRules::for_mode("time_attack").unwrap();
+        assert!(!time_attack.is_won(100.0, true, 0));
+        assert!(time_attack.is_won(180.0, false, 0));
+        assert_eq!(time_attack.time_left(150.0), Some(30.0));
+
+        let classic = ModeRules::for_mode("classic").unwrap();
+        assert!(classic.is_won(11.0, true, 0));
+        assert!(!classic.spawns(50.0));
+
+        let survival = ModeRules::for_mode("survival").unwrap();
+        assert!(!survival.is_won(10_000.0, true, 0));
+        assert!(survival.spawns(10_000.0));
+        assert!(survival.asteroid_speed(600.0) > survival.asteroid_speed(300.0) + 1.0);
+        assert!(survival.spawn_interval(300.0) < survival.spawn_interval(60.0));
+
+        assert!(!ModeRules::for_mode("zen").unwrap().can_die);
+        assert!(!classic.ore_shop && !ModeRules::for_mode("modern").unwrap().ore_shop);
+        assert!(!classic.bosses && !ModeRules::for_mode("modern").unwrap().bosses);
+        assert!(survival.bosses);
+
+        let defend = ModeRules::for_mode("defend").unwrap();
+        assert!(defend.station);
+        assert!(!defend.is_won(10_000.0, true, 0));
+
+        let hard = ModeRules::for_mode("modern")
+            .unwrap()
+            .with_difficulty(Difficulty::Hard);
+        assert!(hard.spawns(50.0));
+        assert!(!hard.spawns(61.0));
+        assert!(
+            hard.asteroid_speed(90.0) > ModeRules::for_mode("modern").unwrap().asteroid_speed(90.0)
+        );
+        assert!(ModeRules::for_mode("unknown").is_none());
+    }
+}
@@ -0,0 +1,88 @@
+//! # Save Module
+//!
+//! Ce module gère les fichiers de sauvegarde du jeu (statistiques, progression...).
+//! Les fichiers sont au format texte `clé=valeur`, une entrée par ligne, et sont
+//! rangés dans le dossier `saves/`.
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Dossier contenant les sauvegardes.
+pub const SAVE_DIR: &str = "saves";
+
+/// Chemin complet d'un fichier de sauvegarde.
+///
+/// # Arguments
+/// - `file_name` Le nom du fichier (dans le dossier de sauvegarde).
+///
+pub fn save_path(file_name: &str) -> PathBuf {
+    PathBuf::from(SAVE_DIR).join(file_name)
+}
+
+/// Lit le contenu `clé=valeur` d'une chaîne.
+/// Les lignes vides, les commentaires (`#`) et les lignes sans `=` sont ignorés.
+///
+/// # Arguments
+/// - `content` Le contenu à lire.
+///
+/// # Returns
+/// - `HashMap<String, String>` Les couples clé/valeur lus.
+///
+pub fn parse_key_values(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Lit un fichier de sauvegarde `clé=valeur`.
+/// Un fichier absent ou illisible donne une sauvegarde vide.
+///
+/// # Arguments
+/// - `file_name` Le nom du fichier (dans le dossier de sauvegarde).
+///
+pub fn read_key_values(file_name: &str) -> HashMap<String, String> {
+    fs::read_to_string(save_path(file_name))
+        .map(|content| parse_key_values(&content))
+        .unwrap_or_default()
+}
+
+/// Écrit un fichier de sauvegarde `clé=valeur` (le dossier est créé si besoin).
+///
+/// # Arguments
+/// - `file_name` Le nom du fichier (dans le dossier de sauvegarde).
+/// - `entries` Les couples clé/valeur à écrire, dans l'ordre.
+///
+pub fn write_key_values(file_name: &str, entries: &[(String, String)]) {
+    let content: String = entries
+        .iter()
+        .map(|(key, value)| format!("{}={}\n", key, value))
+        .collect();
+    if let Err(e) =
+        fs::create_dir_all(SAVE_DIR).and_then(|_| fs::write(save_path(file_name), content))
+    {
+        eprintln!(
+            "Erreur lors de l'écriture de la sauvegarde '{}': {}",
+            file_name, e
+        );
+    }
+}
+
+/// Lit une valeur numérique, ou la valeur par défaut si elle est absente ou invalide.
+///
+/// # Arguments
+/// - `values` Les couples clé/valeur lus.
+/// - `key` La clé recherchée.
+///
+pub fn get_or_default<T: std::str::FromStr + Default>(
+    values: &HashMap<String, String>,
+    key: &str,
+) -> T {
+    values
+        .get(key)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
@@ -1,5 +1,243 @@
 use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::stats::SessionStats;
+use crate::tuning::tuning;
 use crate::Spaceship;
+use macroquad::prelude::*;
+
+/// Numéro de la vague en cours (la première vague porte le numéro 1).
+/// La durée d'une vague est une valeur d'équilibrage (`wave_duration`).
+///
+/// # Arguments
+/// - `time` Le temps de jeu.
+///
+pub fn wave_number(time: f64) -> u32 {
+    (time.max(0.0) / tuning().wave_duration) as u32 + 1
+}
+
+/// Texte flottant affiché lors d'un gain de points.
+pub struct ScorePopup {
+    /// Position du texte (`None` pour un bonus affiché sous le score).
+    pub position: Option<Vec2>,
+    /// Texte affiché.
+    pub text: String,
+    /// Temps d'apparition du texte.
+    pub birth_time: f64,
+}
+
+/// Détail des points gagnés pendant la partie.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScoreBreakdown {
+    /// Points de base (25/50/100 selon la taille des astéroïds).
+    pub base: u32,
+    /// Points supplémentaires obtenus grâce au multiplicateur.
+    pub combo: u32,
+    /// Bonus de précision (attribué en fin de partie).
+    pub accuracy: u32,
+    /// Bonus des vagues terminées sans dégâts.
+    pub no_damage: u32,
+    /// Bonus des terrains nettoyés rapidement.
+    pub quick_clear: u32,
+}
+
+/// Moteur de score : combos, multiplicateur et bonus.
+///
+/// Chaque destruction enchaînée dans la fenêtre de combo augmente le multiplicateur,
+/// qui redescend progressivement vers 1 lorsque le joueur cesse de détruire des astéroïds.
+pub struct ScoreEngine {
+    /// Nombre de destructions enchaînées.
+    combo: u32,
+    /// Meilleur combo de la partie.
+    max_combo: u32,
+    /// Multiplicateur de score actuel.
+    multiplier: f32,
+    /// Temps de la dernière destruction.
+    last_kill_time: f64,
+    /// Temps de la dernière mise à jour (pour la décroissance du multiplicateur).
+    last_update_time: f64,
+    /// Vague en cours.
+    current_wave: u32,
+    /// Vrai si le vaisseau a été touché pendant la vague en cours.
+    damaged_this_wave: bool,
+    /// Nombre de destructions pendant la vague en cours.
+    kills_this_wave: u32,
+    /// Temps depuis lequel le terrain contient des astéroïds (`None` si le terrain est vide).
+    populated_since: Option<f64>,
+    /// Vrai si le bonus de précision a déjà été attribué.
+    finalized: bool,
+    /// Détail des points gagnés.
+    breakdown: ScoreBreakdown,
+    /// Textes flottants à afficher.
+    pub popups: Vec<ScorePopup>,
+}
+
+impl ScoreEngine {
+    /// Créer un nouveau moteur de score.
+    pub fn new() -> Self {
+        Self {
+            combo: 0,
+            max_combo: 0,
+            multiplier: 1.0,
+            last_kill_time: f64::NEG_INFINITY,
+            last_update_time: 0.0,
+            current_wave: 1,
+            damaged_this_wave: false,
+            kills_this_wave: 0,
+            populated_since: None,
+            finalized: false,
+            breakdown: ScoreBreakdown::default(),
+            popups: Vec::new(),
+        }
+    }
+
+    /// Getter du combo en cours.
+    pub fn get_combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Getter du meilleur combo de la partie.
+    pub fn get_max_combo(&self) -> u32 {
+        self.max_combo
+    }
+
+    /// Getter du multiplicateur actuel.
+    pub fn get_multiplier(&self) -> f32 {
+        self.multiplier
+    }
+
+    /// Getter du détail des points.
+    pub fn get_breakdown(&self) -> ScoreBreakdown {
+        self.breakdown
+    }
+
+    /// Enregistre une perte de bouclier (annule le bonus de la vague en cours).
+    pub fn register_damage(&mut self) {
+        self.damaged_this_wave = true;
+    }
+
+    /// Enregistre la destruction d'un astéroïd et calcule les points gagnés.
+    ///
+    /// # Arguments
+    /// - `asteroid` L'astéroïd détruit.
+    /// - `time` Le temps de jeu.
+    ///
+    /// # Returns
+    /// - `u32` Points gagnés (multiplicateur compris).
+    ///
+    pub fn register_kill(&mut self, asteroid: &Asteroid, time: f64) -> u32 {
+        let tuning = tuning();
+        if time - self.last_kill_time <= tuning.combo_window {
+            self.combo += 1;
+            self.multiplier = (self.multiplier + tuning.multiplier_step).min(tuning.max_multiplier);
+        } else {
+            self.combo = 1;
+        }
+        self.max_combo = self.max_combo.max(self.combo);
+        self.last_kill_time = time;
+        self.kills_this_wave += 1;
+
+        let base = increase_score(asteroid);
+        let total = (base as f32 * self.multiplier).round() as u32;
+        self.breakdown.base += base;
+        self.breakdown.combo += total - base;
+
+        let text = if self.multiplier > 1.0 {
+            format!("+{} x{:.2}", total, self.multiplier)
+        } else {
+            format!("+{}", total)
+        };
+        self.popups.push(ScorePopup {
+            position: Some(asteroid.position),
+            text,
+            birth_time: time,
+        });
+        total
+    }
+
+    /// Mise à jour du moteur de score, à appeler à chaque frame.
+    /// Fait décroître le multiplicateur, supprime les textes expirés et attribue les bonus de vague.
+    ///
+    /// # Arguments
+    /// - `time` Le temps de jeu.
+    /// - `field_empty` Vrai s'il ne reste aucun astéroïd sur le terrain.
+    ///
+    /// # Returns
+    /// - `u32` Points bonus gagnés pendant cette frame.
+    ///
+    pub fn update(&mut self, time: f64, field_empty: bool) -> u32 {
+        let dt = (time - self.last_update_time).max(0.0) as f32;
+        self.last_update_time = time;
+        let tuning = tuning();
+
+        if time - self.last_kill_time > tuning.combo_window {
+            self.combo = 0;
+            self.multiplier = (self.multiplier - tuning.multiplier_decay * dt).max(1.0);
+        }
+
+        self.popups
+            .retain(|popup| time - popup.birth_time < tuning.popup_duration);
+
+        let mut bonus = 0;
+
+        let wave = wave_number(time);
+        if wave > self.current_wave {
+            if !self.damaged_this_wave && self.kills_this_wave > 0 {
+                bonus += tuning.no_damage_bonus;
+                self.breakdown.no_damage += tuning.no_damage_bonus;
+                self.push_bonus_popup(format!("No damage wave +{}", tuning.no_damage_bonus), time);
+            }
+            self.current_wave = wave;
+            self.damaged_this_wave = false;
+            self.kills_this_wave = 0;
+        }
+
+        match (self.populated_since, field_empty) {
+            (None, false) => self.populated_since = Some(time),
+            (Some(since), true) => {
+                self.populated_since = None;
+                let remaining = tuning.quick_clear_window - (time - since);
+                if remaining > 0.0 {
+                    let quick = (remaining * tuning.quick_clear_points).round() as u32;
+                    bonus += quick;
+                    self.breakdown.quick_clear += quick;
+                    self.push_bonus_popup(format!("Field cleared +{}", quick), time);
+                }
+            }
+            _ => {}
+        }
+        bonus
+    }
+
+    /// Attribue le bonus de précision en fin de partie (une seule fois).
+    ///
+    /// # Arguments
+    /// - `stats` Les statistiques de la partie (tirs et touches).
+    ///
+    /// # Returns
+    /// - `u32` Points du bonus de précision.
+    ///
+    pub fn finalize(&mut self, stats: &SessionStats) -> u32 {
+        if self.finalized {
+            return 0;
+        }
+        self.finalized = true;
+        let tuning = tuning();
+        if stats.shots_fired < tuning.accuracy_min_shots {
+            return 0;
+        }
+        let bonus = (stats.accuracy() * tuning.accuracy_bonus_max).round() as u32;
+        self.breakdown.accuracy = bonus;
+        bonus
+    }
+
+    /// Ajoute un texte de bonus affiché sous le score.
+    fn push_bonus_popup(&mut self, text: String, time: f64) {
+        self.popups.push(ScorePopup {
+            position: None,
+            text,
+            birth_time: time,
+        });
+    }
+}
 
 // Getter de la valeur du score du vaisseau spatial.
 //
@@ -23,7 +261,17 @@ pub fn add_score(spaceship: &mut Spaceship, score: u32) {
     spaceship.score += score;
 }
 
-// Augmente le score du vaisseau spatial en fonction de la taille de l'astéroid détruit.
+// Ajoute le bonus de fin de partie (précision) au score du vaisseau spatial.
+//
+// # Arguments
+// - `spaceship` - Référence du vaisseau spatial.
+//
+pub fn finalize_score(spaceship: &mut Spaceship) {
+    let bonus = spaceship.score_engine.finalize(&spaceship.stats);
+    add_score(spaceship, bonus);
+}
+
+// Augmente le score du vaisseau spatial en fonction de la taille (et du matériau) de l'astéroid détruit.
 //
 // # Arguments
 // - `asteroid` - Référence de l'astéroid détruit.
@@ -32,11 +280,76 @@ pub fn add_score(spaceship: &mut Spaceship, score: u32) {
 // - `u32` Score à ajouter.
 //
 pub fn increase_score(asteroid: &Asteroid) -> u32 {
-    let mut score = 0;
+    let tuning = tuning();
+    let mut score = asteroid.get_material().bonus_score();
     match asteroid.get_shape() {
-        AsteroidShape::Large => score += 25,
-        AsteroidShape::Medium => score += 50,
-        AsteroidShape::Small => score += 100,
+        AsteroidShape::Large => score += tuning.score_large,
+        AsteroidShape::Medium => score += tuning.score_medium,
+        AsteroidShape::Small => score += tuning.score_small,
     }
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_asteroid() -> Asteroid {
+        Asteroid::new(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 0.0),
+            AsteroidShape::Small,
+            20.0,
+        )
+    }
+
+    #[test]
+    fn test_combo_chain_increases_multiplier() {
+        let mut engine = ScoreEngine::new();
+        let asteroid = small_asteroid();
+        assert_eq!(engine.register_kill(&asteroid, 1.0), 100);
+        assert_eq!(engine.register_kill(&asteroid, 2.0), 125);
+        assert_eq!(engine.register_kill(&asteroid, 3.0), 150);
+        assert_eq!(engine.get_combo(), 3);
+        assert_eq!(engine.get_breakdown().combo, 75);
+    }
+
+    #[test]
+    fn test_combo_resets_and_multiplier_decays() {
+        let mut engine = ScoreEngine::new();
+        let asteroid = small_asteroid();
+        engine.register_kill(&asteroid, 1.0);
+        engine.register_kill(&asteroid, 2.0);
+        engine.update(2.0, false);
+        engine.update(5.0, false);
+        assert_eq!(engine.get_combo(), 0);
+        assert_eq!(engine.get_multiplier(), 1.0);
+        assert_eq!(engine.get_max_combo(), 2);
+    }
+
+    #[test]
+    fn test_no_damage_wave_bonus() {
+        let wave_duration = tuning().wave_duration;
+        let mut engine = ScoreEngine::new();
+        engine.register_kill(&small_asteroid(), 1.0);
+        assert_eq!(engine.update(wave_duration + 0.1, false), 250);
+
+        engine.register_kill(&small_asteroid(), wave_duration + 1.0);
+        engine.register_damage();
+        assert_eq!(engine.update(2.0 * wave_duration + 0.1, false), 0);
+    }
+
+    #[test]
+    fn test_accuracy_bonus_given_once() {
+        let mut engine = ScoreEngine::new();
+        let mut stats = SessionStats::new();
+        stats.shots_fired = 10;
+        for i in 0..5 {
+            let asteroid = small_asteroid();
+            stats.record_destroyed(&asteroid);
+            engine.register_kill(&asteroid, i as f64 * 10.0);
+        }
+        assert_eq!(engine.finalize(&stats), 500);
+        assert_eq!(engine.finalize(&stats), 0);
+    }
+}
@@ -0,0 +1,145 @@
+//! # Settings Module
+//!
+//! Ce module gère les réglages du jeu choisis dans le menu des options
+//! (sauvegardés sur le disque, au format `clé=valeur`).
+use crate::control::ControlScheme;
+use crate::difficulty::Difficulty;
+use crate::save::{read_key_values, write_key_values};
+use crate::shipclass::ShipDefinition;
+use crate::spaceship::Spaceship;
+use std::collections::HashMap;
+
+/// Réglages du jeu.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Settings {
+    /// Diffuser les parties en cours pour les spectateurs (TCP).
+    pub spectator_stream: bool,
+    /// Schéma de contrôle du vaisseau (`None` : schéma par défaut du mode de jeu).
+    pub control_scheme: Option<ControlScheme>,
+    /// Nom de la classe du vaisseau du joueur (vide : classe par défaut).
+    pub ship_class: String,
+    /// Bouclier d'énergie régénérant au lieu du bouclier par points.
+    pub energy_shield: bool,
+    /// Niveau de difficulté des modes solo et coopératif.
+    pub difficulty: Difficulty,
+    /// Directeur adaptatif : la difficulté s'ajuste à la performance du joueur.
+    pub adaptive_director: bool,
+}
+
+impl Settings {
+    /// Nom du fichier de sauvegarde des réglages.
+    const FILE_NAME: &'static str = "settings.txt";
+
+    /// Charge les réglages depuis le disque.
+    pub fn load() -> Self {
+        Self::from_key_values(&read_key_values(Self::FILE_NAME))
+    }
+
+    /// Schéma de contrôle à utiliser dans un mode de jeu.
+    ///
+    /// # Arguments
+    /// - `mode` Le mode de jeu ('classic' ou 'modern').
+    ///
+    pub fn control_scheme_for(&self, mode: &str) -> ControlScheme {
+        self.control_scheme
+            .unwrap_or_else(|| ControlScheme::default_for(mode))
+    }
+
+    /// Classe du vaisseau choisie par le joueur.
+    pub fn ship_definition(&self) -> ShipDefinition {
+        ShipDefinition::load(&self.ship_class)
+    }
+
+    /// Crée le vaisseau d'un joueur avec le bouclier choisi dans les réglages.
+    ///
+    /// # Arguments
+    /// - `definition` La classe du vaisseau.
+    ///
+    pub fn new_spaceship(&self, definition: &ShipDefinition) -> Spaceship {
+        let mut spaceship = Spaceship::new(definition);
+        if self.energy_shield {
+            spaceship.enable_energy_shield();
+        }
+        spaceship
+    }
+
+    /// Passe au schéma de contrôle suivant (le schéma par défaut du mode, puis chaque schéma).
+    pub fn next_control_scheme(&mut self) {
+        self.control_scheme = match self.control_scheme {
+            None => Some(ControlScheme::ALL[0]),
+            Some(scheme) => ControlScheme::ALL
+                .iter()
+                .position(|&s| s == scheme)
+                .and_then(|i| ControlScheme::ALL.get(i + 1))
+                .copied(),
+        };
+    }
+
+    /// Sauvegarde les réglages sur le disque.
+    pub fn save(&self) {
+        write_key_values(Self::FILE_NAME, &self.to_key_values());
+    }
+
+    /// Construit les réglages à partir d'un fichier `clé=valeur`.
+    fn from_key_values(values: &HashMap<String, String>) -> Self {
+        Self {
+            spectator_stream: values.get("spectator_stream").is_some_and(|v| v == "true"),
+            control_scheme: values
+                .get("control_scheme")
+                .and_then(|name| ControlScheme::from_name(name)),
+            ship_class: values.get("ship_class").cloned().unwrap_or_default(),
+            energy_shield: values.get("energy_shield").is_some_and(|v| v == "true"),
+            difficulty: values
+                .get("difficulty")
+                .and_then(|name| Difficulty::from_name(name))
+                .unwrap_or_default(),
+            adaptive_director: values.get("adaptive_director").is_some_and(|v| v == "true"),
+        }
+    }
+
+    /// Convertit les réglages en couples `clé=valeur`.
+    fn to_key_values(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                "spectator_stream".to_string(),
+                self.spectator_stream.to_string(),
+            ),
+            (
+                "control_scheme".to_string(),
+                self.control_scheme
+                    .map_or("default", ControlScheme::name)
+                    .to_string(),
+            ),
+            ("ship_class".to_string(), self.ship_class.clone()),
+            ("energy_shield".to_string(), self.energy_shield.to_string()),
+            ("difficulty".to_string(), self.difficulty.name().to_string()),
+            (
+                "adaptive_director".to_string(),
+                self.adaptive_director.to_string(),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = Settings {
+            spectator_stream: true,
+            control_scheme: Some(ControlScheme::KeyboardAim),
+            ship_class: "Heavy".to_string(),
+            energy_shield: true,
+            difficulty: Difficulty::Hard,
+            adaptive_director: true,
+        };
+        let values: HashMap<String, String> = settings.to_key_values().into_iter().collect();
+        assert_eq!(Settings::from_key_values(&values), settings);
+        assert_eq!(
+            Settings::from_key_values(&HashMap::new()),
+            Settings::default()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! # Shield Module
+//!
+//! Ce module gère le bouclier d'énergie (option des réglages), alternative au bouclier par points.
+//!
+//! - Chaque impact retire de l'énergie, proportionnellement à la taille de l'astéroïde et à la vitesse relative.
+//! - Le bouclier se régénère après un délai sans impact.
+//! - Lorsque l'énergie est épuisée, le bouclier se brise : le vaisseau est vulnérable
+//!   (un nouvel impact le détruit) jusqu'à la fin du temps de recharge.
+//!
+//! L'énergie est calculée à partir du temps du dernier impact : aucune mise à jour par frame n'est nécessaire.
+
+/// Bouclier d'énergie d'un vaisseau.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyShield {
+    /// Énergie maximale.
+    capacity: f32,
+    /// Énergie restante juste après le dernier impact.
+    energy: f32,
+    /// Temps du dernier impact, en secondes.
+    last_hit_time: f64,
+    /// Le bouclier a été brisé par le dernier impact.
+    broken: bool,
+}
+
+impl EnergyShield {
+    /// Énergie par point de bouclier de la classe du vaisseau.
+    pub const ENERGY_PER_PIP: f32 = 100.0;
+    /// Dégâts infligés par un missile.
+    pub const MISSILE_DAMAGE: f32 = 60.0;
+    /// Délai minimal (en secondes) entre deux impacts comptabilisés.
+    pub const HIT_GRACE: f64 = 0.5;
+    /// Facteur des dégâts d'un astéroïde (taille × vitesse relative).
+    const DAMAGE_FACTOR: f32 = 0.5;
+    /// Dégâts minimaux d'un impact d'astéroïde.
+    const MIN_DAMAGE: f32 = 10.0;
+    /// Délai (en secondes) sans impact avant le début de la régénération.
+    const REGEN_DELAY: f64 = 3.0;
+    /// Part de l'énergie maximale régénérée par seconde.
+    const REGEN_RATE: f32 = 0.25;
+    /// Durée (en secondes) pendant laquelle un bouclier brisé laisse le vaisseau vulnérable.
+    const BREAK_COOLDOWN: f64 = 4.0;
+
+    /// Créer un bouclier chargé.
+    ///
+    /// # Arguments
+    /// - `capacity` L'énergie maximale.
+    ///
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            capacity,
+            energy: capacity,
+            last_hit_time: f64::NEG_INFINITY,
+            broken: false,
+        }
+    }
+
+    /// Dégâts d'un impact d'astéroïde.
+    ///
+    /// # Arguments
+    /// - `size` La taille de l'astéroïde.
+    /// - `relative_speed` La vitesse de l'astéroïde par rapport au vaisseau.
+    ///
+    pub fn impact_damage(size: f32, relative_speed: f32) -> f32 {
+        (size * relative_speed * Self::DAMAGE_FACTOR).max(Self::MIN_DAMAGE)
+    }
+
+    /// Énergie restante à un instant donné (régénération comprise).
+    ///
+    /// # Arguments
+    /// - `time` Le temps actuel (en secondes).
+    ///
+    pub fn energy(&self, time: f64) -> f32 {
+        let delay = if self.broken {
+            Self::BREAK_COOLDOWN
+        } else {
+            Self::REGEN_DELAY
+        };
+        let idle = (time - self.last_hit_time - delay) as f32;
+        if idle <= 0.0 {
+            self.energy
+        } else {
+            (self.energy + idle * Self::REGEN_RATE * self.capacity).min(self.capacity)
+        }
+    }
+
+    /// Part de l'énergie restante (entre 0 et 1).
+    ///
+    /// # Arguments
+    /// - `time` Le temps actuel (en secondes).
+    ///
+    pub fn ratio(&self, time: f64) -> f32 {
+        self.energy(time) / self.capacity
+    }
+
+    /// Vérifie si le bouclier est brisé (vaisseau vulnérable).
+    ///
+    /// # Arguments
+    /// - `time` Le temps actuel (en secondes).
+    ///
+    pub fn is_broken(&self, time: f64) -> bool {
+        self.broken && time - self.last_hit_time < Self::BREAK_COOLDOWN
+    }
+
+    /// Applique un impact au bouclier.
+    ///
+    /// # Arguments
+    /// - `damage` L'énergie retirée par l'impact.
+    /// - `time` Le temps actuel (en secondes).
+    ///
+    /// # Returns
+    /// - `true` si le bouclier était brisé : le vaisseau est détruit.
+    /// - `false` sinon.
+    ///
+    pub fn hit(&mut self, damage: f32, time: f64) -> bool {
+        if self.is_broken(time) {
+            return true;
+        }
+        self.energy = (self.energy(time) - damage).max(0.0);
+        self.broken = self.energy == 0.0;
+        self.last_hit_time = time;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_and_regeneration() {
+        let mut shield = EnergyShield::new(300.0);
+        assert!(!shield.hit(EnergyShield::impact_damage(50.0, 4.0), 10.0));
+        assert_eq!(shield.energy(10.0), 200.0);
+        assert_eq!(shield.energy(12.0), 200.0);
+        assert_eq!(shield.energy(14.0), 275.0);
+        assert_eq!(shield.energy(30.0), 300.0);
+        assert!(EnergyShield::impact_damage(70.0, 4.0) > EnergyShield::impact_damage(20.0, 4.0));
+    }
+
+    #[test]
+    fn test_broken_shield_is_vulnerable() {
+        let mut shield = EnergyShield::new(100.0);
+        assert!(!shield.hit(150.0, 1.0));
+        assert!(shield.is_broken(2.0));
+        assert!(shield.hit(10.0, 2.0));
+        assert!(!shield.is_broken(5.0));
+        assert_eq!(shield.energy(5.0), 0.0);
+        assert!(shield.energy(6.0) > 0.0);
+    }
+}
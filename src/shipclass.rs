@@ -0,0 +1,356 @@
+//! # Ship Class Module
+//!
+//! Ce module définit les classes de vaisseaux : chaque classe a ses propres caractéristiques
+//! (accélération, frottement, vitesse de rotation, taille, bouclier, cadence de tir et arme).
+//!
+//! Les classes sont lues depuis les fichiers `*.ship` du dossier `assets/ships`, au format `clé=valeur` :
+//!
+//! ```text
+//! name=Interceptor
+//! acceleration=0.24
+//! drag=0.015
+//! turn_rate=0.07
+//! size=16
+//! shield=2
+//! fire_delay=0.15
+//! weapon=single
+//! ```
+use crate::asteroid::Asteroid;
+use crate::save::parse_key_values;
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, handle_enter_press};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+/// Arme par défaut d'un vaisseau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weapon {
+    /// Un missile droit devant.
+    Single,
+    /// Deux missiles parallèles.
+    Twin,
+    /// Trois missiles en éventail.
+    Spread,
+}
+
+impl Weapon {
+    /// Toutes les armes.
+    pub const ALL: [Weapon; 3] = [Weapon::Single, Weapon::Twin, Weapon::Spread];
+
+    /// Nom de l'arme (pour l'affichage et les fichiers de définition).
+    pub fn name(self) -> &'static str {
+        match self {
+            Weapon::Single => "single",
+            Weapon::Twin => "twin",
+            Weapon::Spread => "spread",
+        }
+    }
+
+    /// Arme correspondant à un nom (voir `name`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|weapon| weapon.name() == name)
+    }
+}
+
+/// Caractéristiques d'une classe de vaisseau.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipDefinition {
+    /// Nom de la classe.
+    pub name: String,
+    /// Accélération appliquée à chaque frame de poussée.
+    pub acceleration: f32,
+    /// Part de la vitesse perdue à chaque frame (entre 0 et 1).
+    pub drag: f32,
+    /// Vitesse de rotation (radians par frame).
+    pub turn_rate: f32,
+    /// Rayon de la hitbox du vaisseau.
+    pub size: f32,
+    /// Bouclier maximal (nombre de vies).
+    pub shield: u32,
+    /// Délai minimal (en secondes) entre deux tirs.
+    pub fire_delay: f64,
+    /// Arme par défaut.
+    pub weapon: Weapon,
+}
+
+impl Default for ShipDefinition {
+    /// Classe équilibrée, reprenant les caractéristiques historiques du vaisseau.
+    fn default() -> Self {
+        Self {
+            name: "Balanced".to_string(),
+            acceleration: 0.18,
+            drag: 0.01,
+            turn_rate: 0.05,
+            size: 20.0,
+            shield: 3,
+            fire_delay: 0.2,
+            weapon: Weapon::Single,
+        }
+    }
+}
+
+impl ShipDefinition {
+    /// Dossier contenant les définitions des classes.
+    const FOLDER: &'static str = "assets/ships";
+
+    /// Lit une définition au format `clé=valeur`.
+    /// Les clés absentes prennent la valeur de la classe par défaut.
+    ///
+    /// # Arguments
+    /// - `content` Le contenu du fichier de définition.
+    ///
+    /// # Returns
+    /// - `Ok(ShipDefinition)` La définition lue.
+    /// - `Err(String)` Un message décrivant la première valeur invalide.
+    ///
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let values = parse_key_values(content);
+        let default = Self::default();
+        let weapon = match values.get("weapon") {
+            Some(name) => {
+                Weapon::from_name(name).ok_or_else(|| format!("unknown weapon '{}'", name))?
+            }
+            None => default.weapon,
+        };
+        let definition = Self {
+            name: values.get("name").cloned().unwrap_or(default.name),
+            acceleration: parse_value(&values, "acceleration", default.acceleration)?,
+            drag: parse_value(&values, "drag", default.drag)?,
+            turn_rate: parse_value(&values, "turn_rate", default.turn_rate)?,
+            size: parse_value(&values, "size", default.size)?,
+            shield: parse_value(&values, "shield", default.shield)?,
+            fire_delay: parse_value(&values, "fire_delay", default.fire_delay)?,
+            weapon,
+        };
+        if !(0.0..1.0).contains(&definition.drag) {
+            return Err("'drag' must be between 0 and 1".to_string());
+        }
+        if definition.acceleration <= 0.0
+            || definition.turn_rate <= 0.0
+            || definition.size <= 0.0
+            || definition.fire_delay <= 0.0
+        {
+            return Err(
+                "'acceleration', 'turn_rate', 'size' and 'fire_delay' must be positive".to_string(),
+            );
+        }
+        if definition.shield == 0 {
+            return Err("'shield' must be at least 1".to_string());
+        }
+        Ok(definition)
+    }
+
+    /// Charge toutes les classes du dossier `assets/ships`, triées par nom.
+    /// Les fichiers invalides sont ignorés (avec un message d'erreur) ;
+    /// la classe par défaut est utilisée si aucune classe n'est disponible.
+    pub fn load_all() -> Vec<Self> {
+        let mut definitions: Vec<Self> = fs::read_dir(Self::FOLDER)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "ship"))
+                    .filter_map(|path| {
+                        let content = fs::read_to_string(&path).ok()?;
+                        Self::parse(&content)
+                            .map_err(|e| eprintln!("Classe invalide '{}': {}", path.display(), e))
+                            .ok()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if definitions.is_empty() {
+            definitions.push(Self::default());
+        }
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        definitions
+    }
+
+    /// Charge la classe portant un nom donné
+    /// (ou la classe par défaut, ou la première classe disponible).
+    ///
+    /// # Arguments
+    /// - `name` Le nom de la classe.
+    ///
+    pub fn load(name: &str) -> Self {
+        let mut definitions = Self::load_all();
+        let default_name = Self::default().name;
+        let index = definitions
+            .iter()
+            .position(|definition| definition.name == name)
+            .or_else(|| {
+                definitions
+                    .iter()
+                    .position(|definition| definition.name == default_name)
+            })
+            .unwrap_or(0);
+        definitions.swap_remove(index)
+    }
+
+    /// Lignes décrivant les caractéristiques de la classe (pour le sélecteur de vaisseau).
+    pub fn describe(&self) -> Vec<String> {
+        vec![
+            format!("Acceleration : {:.2}", self.acceleration),
+            format!("Drag : {:.3}", self.drag),
+            format!("Turn rate : {:.3}", self.turn_rate),
+            format!("Hitbox : {:.0}", self.size),
+            format!("Shield : {}", self.shield),
+            format!("Fire rate : {:.1} shots/s", 1.0 / self.fire_delay),
+            format!("Weapon : {}", self.weapon.name()),
+        ]
+    }
+}
+
+/// Lit une valeur numérique d'une définition, ou la valeur par défaut si la clé est absente.
+///
+/// # Arguments
+/// - `values` Les couples clé/valeur lus.
+/// - `key` La clé recherchée.
+/// - `default` La valeur par défaut.
+///
+fn parse_value<T: std::str::FromStr>(
+    values: &HashMap<String, String>,
+    key: &str,
+    default: T,
+) -> Result<T, String> {
+    match values.get(key) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value '{}' for '{}'", value, key)),
+        None => Ok(default),
+    }
+}
+
+/// Sélecteur de vaisseau.
+/// Affiche les classes disponibles et les caractéristiques de la classe sélectionnée.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `current` Le nom de la classe actuellement choisie.
+///
+/// # Returns
+/// - `Some(String)` Le nom de la classe choisie.
+/// - `None` si le joueur revient en arrière.
+///
+pub async fn ship_picker(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    current: &str,
+) -> Option<String> {
+    let definitions = ShipDefinition::load_all();
+    let mut selected_index = definitions
+        .iter()
+        .position(|definition| definition.name == current)
+        .unwrap_or(0);
+
+    sound.play_sound_effect("select_menu");
+
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Ship", None, 50, 1.0);
+        draw_text(
+            "Ship",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+
+        for (i, definition) in definitions.iter().enumerate() {
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            let label = if definition.name == current {
+                format!("{} (current)", definition.name)
+            } else {
+                definition.name.clone()
+            };
+            draw_text(
+                &label,
+                screen_width() / 2.0 - 350.0,
+                350.0 + i as f32 * 50.0,
+                40.0,
+                color,
+            );
+        }
+        for (i, line) in definitions[selected_index].describe().iter().enumerate() {
+            draw_text(
+                line,
+                screen_width() / 2.0 + 50.0,
+                350.0 + i as f32 * 40.0,
+                30.0,
+                WHITE,
+            );
+        }
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % definitions.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                definitions.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            return Some(definitions[selected_index].name.clone());
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_definition() {
+        let definition = ShipDefinition::parse(
+            "# Vaisseau lourd\nname=Heavy\nacceleration=0.12\nshield=5\nweapon=spread\n",
+        )
+        .unwrap();
+        assert_eq!(definition.name, "Heavy");
+        assert_eq!(definition.acceleration, 0.12);
+        assert_eq!(definition.shield, 5);
+        assert_eq!(definition.weapon, Weapon::Spread);
+        assert_eq!(definition.size, ShipDefinition::default().size);
+        assert_eq!(
+            ShipDefinition::parse("").unwrap(),
+            ShipDefinition::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_values() {
+        assert!(ShipDefinition::parse("acceleration=fast").is_err());
+        assert!(ShipDefinition::parse("weapon=laser").is_err());
+        assert!(ShipDefinition::parse("drag=1.5").is_err());
+        assert!(ShipDefinition::parse("shield=0").is_err());
+    }
+
+    #[test]
+    fn test_bundled_definitions_are_valid() {
+        let paths = fs::read_dir(ShipDefinition::FOLDER).unwrap();
+        for path in paths.flatten().map(|entry| entry.path()) {
+            let content = fs::read_to_string(&path).unwrap();
+            assert!(ShipDefinition::parse(&content).is_ok(), "{:?}", path);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! # Shop Module
+//!
+//! Ce module gère les améliorations du vaisseau achetées entre deux vagues
+//! (modes avec boutique, voir `ModeRules::ore_shop`), en dépensant le minerai
+//! ramassé (voir le module `ore`) et des points de score.
+//!
+//! Les améliorations modifient la classe du vaisseau (poussée, cadence de tir, bouclier)
+//! ou ses tirs et son aimant (vitesse des missiles, portée de l'aimant) ;
+//! elles sont conservées jusqu'à la fin de la partie.
+use crate::shipclass::ShipDefinition;
+use crate::sound::Sound;
+use crate::spaceship::Spaceship;
+use crate::utils::background_asteroids;
+use macroquad::prelude::*;
+
+/// Amélioration du vaisseau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upgrade {
+    /// Accélération du vaisseau.
+    Thrust,
+    /// Cadence de tir.
+    FireRate,
+    /// Bouclier maximal.
+    ShieldCapacity,
+    /// Vitesse des missiles.
+    MissileSpeed,
+    /// Portée de l'aimant à minerai.
+    MagnetRange,
+}
+
+impl Upgrade {
+    /// Toutes les améliorations (dans l'ordre de la boutique).
+    pub const ALL: [Upgrade; 5] = [
+        Upgrade::Thrust,
+        Upgrade::FireRate,
+        Upgrade::ShieldCapacity,
+        Upgrade::MissileSpeed,
+        Upgrade::MagnetRange,
+    ];
+    /// Niveau maximal d'une amélioration.
+    pub const MAX_LEVEL: u32 = 5;
+    /// Facteur appliqué à l'accélération par niveau de poussée.
+    const THRUST_FACTOR: f32 = 1.15;
+    /// Facteur appliqué au délai entre deux tirs par niveau de cadence.
+    const FIRE_DELAY_FACTOR: f64 = 0.85;
+    /// Gain de vitesse des missiles par niveau.
+    const MISSILE_SPEED_STEP: f32 = 0.15;
+    /// Portée de l'aimant (en pixels) par niveau.
+    const MAGNET_RANGE_STEP: f32 = 40.0;
+
+    /// Nom affiché de l'amélioration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Upgrade::Thrust => "Thrust",
+            Upgrade::FireRate => "Fire rate",
+            Upgrade::ShieldCapacity => "Shield capacity",
+            Upgrade::MissileSpeed => "Missile speed",
+            Upgrade::MagnetRange => "Magnet range",
+        }
+    }
+
+    /// Indice de l'amélioration dans `Upgrade::ALL`.
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&u| u == self).unwrap_or(0)
+    }
+
+    /// Coût du prochain niveau : (minerai, points de score).
+    ///
+    /// # Arguments
+    /// - `level` Le niveau actuel de l'amélioration.
+    ///
+    pub fn cost(self, level: u32) -> (u32, u32) {
+        (3 + 2 * level, 150 * (level + 1))
+    }
+
+    /// Applique un niveau de l'amélioration à la classe du vaisseau
+    /// (sans effet pour la vitesse des missiles et l'aimant, voir `Upgrades`).
+    ///
+    /// # Arguments
+    /// - `definition` La classe du vaisseau.
+    ///
+    pub fn improve(self, definition: &mut ShipDefinition) {
+        match self {
+            Upgrade::Thrust => definition.acceleration *= Self::THRUST_FACTOR,
+            Upgrade::FireRate => definition.fire_delay *= Self::FIRE_DELAY_FACTOR,
+            Upgrade::ShieldCapacity => definition.shield += 1,
+            Upgrade::MissileSpeed | Upgrade::MagnetRange => {}
+        }
+    }
+}
+
+/// Niveaux des améliorations achetées pendant la partie.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Upgrades {
+    /// Niveau de chaque amélioration (dans l'ordre de `Upgrade::ALL`).
+    levels: [u32; 5],
+}
+
+impl Upgrades {
+    /// Niveau d'une amélioration.
+    ///
+    /// # Arguments
+    /// - `upgrade` L'amélioration.
+    ///
+    pub fn level(&self, upgrade: Upgrade) -> u32 {
+        self.levels[upgrade.index()]
+    }
+
+    /// Augmente le niveau d'une amélioration.
+    ///
+    /// # Arguments
+    /// - `upgrade` L'amélioration.
+    ///
+    pub fn raise(&mut self, upgrade: Upgrade) {
+        self.levels[upgrade.index()] += 1;
+    }
+
+    /// Facteur appliqué à la vitesse des missiles.
+    pub fn missile_speed_factor(&self) -> f32 {
+        1.0 + Upgrade::MISSILE_SPEED_STEP * self.level(Upgrade::MissileSpeed) as f32
+    }
+
+    /// Portée (en pixels) à laquelle le minerai est attiré par le vaisseau.
+    pub fn magnet_range(&self) -> f32 {
+        Upgrade::MAGNET_RANGE_STEP * self.level(Upgrade::MagnetRange) as f32
+    }
+}
+
+/// Achète le prochain niveau d'une amélioration.
+///
+/// # Arguments
+/// - `spaceship` Le vaisseau du joueur (minerai, score et caractéristiques).
+/// - `upgrade` L'amélioration.
+///
+/// # Returns
+/// - `Ok(())` si l'amélioration est achetée.
+/// - `Err(String)` La raison du refus (niveau maximal, minerai ou score insuffisant).
+///
+pub fn buy(spaceship: &mut Spaceship, upgrade: Upgrade) -> Result<(), String> {
+    let level = spaceship.get_upgrades().level(upgrade);
+    if level >= Upgrade::MAX_LEVEL {
+        return Err(format!(
+            "{} is already at its maximum level",
+            upgrade.name()
+        ));
+    }
+    let (ore, score) = upgrade.cost(level);
+    if spaceship.ore < ore {
+        return Err(format!("Not enough ore ({} needed)", ore));
+    }
+    if spaceship.score < score {
+        return Err(format!("Not enough score ({} needed)", score));
+    }
+    spaceship.ore -= ore;
+    spaceship.score -= score;
+    spaceship.apply_upgrade(upgrade);
+    Ok(())
+}
+
+/// Boutique entre deux vagues : le joueur dépense son minerai et son score en améliorations.
+/// Entrée : acheter l'amélioration choisie ; Retour arrière : reprendre la partie.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `sound` Les sons du jeu.
+/// - `spaceship` Le vaisseau du joueur.
+/// - `wave` Le numéro de la vague qui commence.
+///
+/// # Returns
+/// Le temps passé dans la boutique (pour ne pas le prendre en compte comme 'temps de jeu').
+///
+pub async fn shop(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    sound: &Sound,
+    spaceship: &mut Spaceship,
+    wave: u32,
+) -> f64 {
+    let start = get_time();
+    let mut selected_index = 0;
+    let mut status = String::new();
+    sound.play_sound_effect("select_menu");
+
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, &mut vec![]).await;
+
+        let title = format!("Shop - wave {}", wave);
+        let dimension = measure_text(&title, None, 50, 1.0);
+        draw_text(
+            &title,
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        let wallet = format!("Ore : {}   Score : {}", spaceship.ore, spaceship.score);
+        let dimension = measure_text(&wallet, None, 35, 1.0);
+        draw_text(
+            &wallet,
+            (screen_width() - dimension.width) / 2.0,
+            260.0,
+            35.0,
+            YELLOW,
+        );
+
+        for (i, upgrade) in Upgrade::ALL.iter().enumerate() {
+            let level = spaceship.get_upgrades().level(*upgrade);
+            let label = if level >= Upgrade::MAX_LEVEL {
+                format!(
+                    "{} [{}/{}] : max",
+                    upgrade.name(),
+                    level,
+                    Upgrade::MAX_LEVEL
+                )
+            } else {
+                let (ore, score) = upgrade.cost(level);
+                format!(
+                    "{} [{}/{}] : {} ore + {} pts",
+                    upgrade.name(),
+                    level,
+                    Upgrade::MAX_LEVEL,
+                    ore,
+                    score
+                )
+            };
+            let color = if i == selected_index { YELLOW } else { WHITE };
+            draw_text(
+                &label,
+                screen_width() / 2.0 - 250.0,
+                350.0 + i as f32 * 50.0,
+                40.0,
+                color,
+            );
+        }
+        draw_text(
+            &status,
+            screen_width() / 2.0 - 250.0,
+            350.0 + Upgrade::ALL.len() as f32 * 50.0 + 30.0,
+            30.0,
+            GRAY,
+        );
+        let help = "Enter : buy   Backspace : next wave";
+        let dimension = measure_text(help, None, 30, 1.0);
+        draw_text(
+            help,
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 100.0,
+            30.0,
+            GRAY,
+        );
+
+        if is_key_pressed(KeyCode::Down) {
+            selected_index = (selected_index + 1) % Upgrade::ALL.len();
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Up) {
+            selected_index = if selected_index == 0 {
+                Upgrade::ALL.len() - 1
+            } else {
+                selected_index - 1
+            };
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Enter) {
+            let upgrade = Upgrade::ALL[selected_index];
+            status = match buy(spaceship, upgrade) {
+                Ok(()) => {
+                    sound.play_sound_effect("win");
+                    format!("{} upgraded", upgrade.name())
+                }
+                Err(reason) => reason,
+            };
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return get_time() - start;
+        }
+
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_upgrades() {
+        let mut spaceship = Spaceship::new(&ShipDefinition::default());
+        assert!(buy(&mut spaceship, Upgrade::Thrust).is_err());
+
+        spaceship.ore = 20;
+        spaceship.score = 1000;
+        buy(&mut spaceship, Upgrade::Thrust).unwrap();
+        assert_eq!(spaceship.ore, 17);
+        assert_eq!(spaceship.score, 850);
+        assert!(spaceship.get_definition().acceleration > ShipDefinition::default().acceleration);
+
+        buy(&mut spaceship, Upgrade::ShieldCapacity).unwrap();
+        assert_eq!(spaceship.shield, 4);
+        assert_eq!(spaceship.get_definition().shield, 4);
+
+        buy(&mut spaceship, Upgrade::MagnetRange).unwrap();
+        assert_eq!(spaceship.get_upgrades().magnet_range(), 40.0);
+        assert_eq!(spaceship.get_upgrades().level(Upgrade::MagnetRange), 1);
+        assert_eq!(spaceship.ore, 11);
+        buy(&mut spaceship, Upgrade::MissileSpeed).unwrap();
+        assert_eq!(spaceship.get_upgrades().missile_speed_factor(), 1.15);
+
+        spaceship.score = 100;
+        assert_eq!(
+            buy(&mut spaceship, Upgrade::FireRate),
+            Err("Not enough score (150 needed)".to_string())
+        );
+    }
+}
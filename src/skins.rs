@@ -1,3 +1,4 @@
+use crate::achievement::{achievements_menu, Achievements};
 use crate::{asteroid::Asteroid, import::import_file, sound::Sound};
 
 use super::utils::*;
@@ -6,6 +7,7 @@ use std::time::Instant;
 
 /// Menu de sélection des skins.
 /// Permet de choisir les skins des astéroids, du background, des trous noirs et du vaisseau.
+/// Certains skins ne sont disponibles qu'après avoir débloqué le succès correspondant.
 ///
 /// # Arguments
 /// - `background_texture` La texture du background.
@@ -29,9 +31,11 @@ pub async fn skins(
 
     let mut selected_index = 0;
     let mut options = get_top_level_directory_names("assets/pictures/");
+    options.push("Achievements".to_string());
     options.push("Import".to_string());
     options.push("Back".to_string());
     let mut last_enter_press = Instant::now();
+    let achievements = Achievements::load();
 
     sound.play_sound_effect("select_menu");
 
@@ -92,28 +96,42 @@ pub async fn skins(
         if handle_enter_press(&mut last_enter_press) {
             match selected_index {
                 0 => {
-                    skins.0 = choose_image_from_folder("assets/pictures/asteroids", sound)
-                        .await
-                        .unwrap();
+                    skins.0 =
+                        choose_image_from_folder("assets/pictures/asteroids", sound, &achievements)
+                            .await
+                            .unwrap();
                     asteroid_texture = load_texture(&skins.0).await.unwrap();
                 }
                 1 => {
-                    skins.1 = choose_image_from_folder("assets/pictures/backgrounds", sound)
-                        .await
-                        .unwrap();
+                    skins.1 = choose_image_from_folder(
+                        "assets/pictures/backgrounds",
+                        sound,
+                        &achievements,
+                    )
+                    .await
+                    .unwrap();
                     background_texture = load_texture(&skins.1).await.unwrap();
                 }
                 2 => {
-                    skins.2 = choose_image_from_folder("assets/pictures/blackholes", sound)
-                        .await
-                        .unwrap();
+                    skins.2 = choose_image_from_folder(
+                        "assets/pictures/blackholes",
+                        sound,
+                        &achievements,
+                    )
+                    .await
+                    .unwrap();
                 }
                 3 => {
-                    skins.3 = choose_image_from_folder("assets/pictures/ships", sound)
-                        .await
-                        .unwrap();
+                    skins.3 =
+                        choose_image_from_folder("assets/pictures/ships", sound, &achievements)
+                            .await
+                            .unwrap();
                 }
                 4 => {
+                    achievements_menu(&background_texture, &asteroid_texture, asteroids, sound)
+                        .await;
+                }
+                5 => {
                     import_file(
                         get_time(),
                         sound,
@@ -123,7 +141,7 @@ pub async fn skins(
                     )
                     .await;
                 }
-                5 => {
+                6 => {
                     return skins.clone();
                 }
                 _ => {}
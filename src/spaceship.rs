@@ -1,12 +1,99 @@
-use crate::bind::Bind;
+use crate::bind::{Action, Bind};
 use crate::blackhole::BlackHole;
+use crate::control::ControlScheme;
 use crate::impl_stellar_object;
+use crate::score::ScoreEngine;
+use crate::shield::EnergyShield;
+use crate::shipclass::ShipDefinition;
+use crate::shop::{Upgrade, Upgrades};
 use crate::sound::Sound;
+use crate::stats::SessionStats;
 use crate::stellarobject::StellarObject;
+use crate::tuning::tuning;
 use crate::utils::has_even_decimal_part;
 use crate::Asteroid;
 use macroquad::prelude::*;
 
+/// Commandes appliquées au vaisseau spatial pendant une frame.
+///
+/// Les commandes proviennent du clavier du joueur local ou du réseau (joueur distant).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ShipInput {
+    /// Accélérer.
+    pub up: bool,
+    /// Reculer.
+    pub down: bool,
+    /// Tourner à gauche.
+    pub left: bool,
+    /// Tourner à droite.
+    pub right: bool,
+    /// Pas de côté vers la gauche.
+    pub strafe_left: bool,
+    /// Pas de côté vers la droite.
+    pub strafe_right: bool,
+    /// Tirer.
+    pub fire: bool,
+    /// Point visé (curseur de la souris).
+    pub aim: Vec2,
+}
+
+impl ShipInput {
+    /// Lit les commandes au clavier et à la souris selon le Bind du joueur.
+    /// Avec un schéma visé à la souris, les boutons de la souris tirent aussi.
+    ///
+    /// # Arguments
+    ///
+    /// - `keybinding`: Les touches associées au contrôle du vaisseau.
+    /// - `scheme`: Le schéma de contrôle choisi.
+    pub fn from_keyboard(keybinding: &Bind, scheme: ControlScheme) -> Self {
+        Self {
+            up: keybinding.is_down(Action::Thrust),
+            down: keybinding.is_down(Action::Reverse),
+            left: keybinding.is_down(Action::RotateLeft),
+            right: keybinding.is_down(Action::RotateRight),
+            strafe_left: keybinding.is_down(Action::StrafeLeft),
+            strafe_right: keybinding.is_down(Action::StrafeRight),
+            fire: keybinding.is_down(Action::Fire)
+                || (scheme.fires_with_mouse()
+                    && [MouseButton::Left, MouseButton::Middle, MouseButton::Right]
+                        .into_iter()
+                        .any(is_mouse_button_down)),
+            aim: mouse_position().into(),
+        }
+    }
+
+    /// Encode les commandes sur un octet (un bit par commande, sans le point visé :
+    /// les parties en réseau utilisent un schéma sans souris, voir `network::NET_SCHEME`).
+    pub fn to_bits(self) -> u8 {
+        [
+            self.up,
+            self.down,
+            self.left,
+            self.right,
+            self.fire,
+            self.strafe_left,
+            self.strafe_right,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, &pressed)| bits | ((pressed as u8) << i))
+    }
+
+    /// Décode les commandes depuis un octet (voir `to_bits`) ; le point visé reste nul.
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            up: bits & 1 != 0,
+            down: bits & 2 != 0,
+            left: bits & 4 != 0,
+            right: bits & 8 != 0,
+            fire: bits & 16 != 0,
+            strafe_left: bits & 32 != 0,
+            strafe_right: bits & 64 != 0,
+            aim: Vec2::ZERO,
+        }
+    }
+}
+
 /// Représente le vaisseau spatial contrôlé par le joueur.
 ///
 /// Le vaisseau spatial peut se déplacer, entrer en collision avec des objets
@@ -21,34 +108,43 @@ pub struct Spaceship {
     size: f32,
     /// Rotation actuelle du vaisseau spatial, en radians.
     rotation: f32,
-    /// Bouclier du vaisseau (nombre de vies restantes).
+    /// Bouclier du vaisseau (nombre de vies restantes ; 1 tant que le bouclier d'énergie tient).
     pub shield: u32,
     /// Temps de la dernière collision, en secondes.
     last_collision_time: f64,
     /// Score actuel du joueur.
     pub score: u32,
+    /// Moteur de score (combos, multiplicateur et bonus).
+    pub score_engine: ScoreEngine,
+    /// Statistiques de la partie en cours.
+    pub stats: SessionStats,
+    /// Classe du vaisseau (caractéristiques de maniabilité, bouclier et arme).
+    definition: ShipDefinition,
+    /// Bouclier d'énergie (`None` : bouclier par points).
+    energy_shield: Option<EnergyShield>,
+    /// Minerai ramassé (dépensé dans la boutique).
+    pub ore: u32,
+    /// Améliorations achetées pendant la partie.
+    upgrades: Upgrades,
 }
 
 impl Spaceship {
-    /// Taille constante du vaisseau spatial.
-    pub const SPACE_SHIP_SIZE: f32 = 20.0;
-    /// Accélération du vaisseau spatial.
-    const SPACE_SHIP_ACCELERATION: f32 = 0.18;
-    /// Décélération appliquée à chaque mise à jour.
-    const SPACE_SHIP_DECELERATION: f32 = 0.99;
-
-    /// Crée une nouvelle instance de `Spaceship` avec des paramètres par défaut.
+    /// Crée une nouvelle instance de `Spaceship` d'une classe donnée.
+    ///
+    /// # Arguments
+    ///
+    /// - `definition`: La classe du vaisseau.
     ///
     /// # Retourne
     ///
-    /// Un vaisseau spatial initialisé au centre de l'écran avec un bouclier de 3.
+    /// Un vaisseau spatial initialisé au centre de l'écran avec le bouclier maximal de sa classe.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// let spaceship = Spaceship::new();
+    /// let spaceship = Spaceship::new(&ShipDefinition::default());
     /// ```
-    pub fn new() -> Self {
+    pub fn new(definition: &ShipDefinition) -> Self {
         Self {
             position: if cfg!(test) {
                 Vec2::new(220.0, 330.0)
@@ -56,11 +152,74 @@ impl Spaceship {
                 Self::starting_pos()
             }, // Pour éviter l'erreur 'THREAD_ID.is_some()' lors de l'éxécution de tests.
             speed: Vec2::new(0.0, 0.0),
-            size: Self::SPACE_SHIP_SIZE,
+            size: definition.size,
             rotation: 0.0,
-            shield: 3,
+            shield: definition.shield,
             last_collision_time: -2.0,
             score: 0,
+            score_engine: ScoreEngine::new(),
+            stats: SessionStats::new(),
+            definition: definition.clone(),
+            energy_shield: None,
+            ore: 0,
+            upgrades: Upgrades::default(),
+        }
+    }
+    /// Fait réapparaître le vaisseau spatial avec un bouclier complet.
+    /// Le vaisseau est invulnérable pendant 2 secondes après sa réapparition.
+    ///
+    /// # Arguments
+    ///
+    /// - `position`: La position de réapparition.
+    /// - `current_time`: Le temps actuel (en secondes).
+    pub fn respawn(&mut self, position: Vec2, current_time: f64) {
+        self.position = position;
+        self.speed = Vec2::new(0.0, 0.0);
+        self.rotation = 0.0;
+        self.shield = self.definition.shield;
+        self.last_collision_time = current_time;
+        if self.energy_shield.is_some() {
+            self.enable_energy_shield();
+        }
+    }
+    /// Remplace le bouclier par points par un bouclier d'énergie chargé
+    /// (capacité proportionnelle au bouclier de la classe du vaisseau).
+    pub fn enable_energy_shield(&mut self) {
+        self.energy_shield = Some(EnergyShield::new(
+            self.definition.shield as f32 * EnergyShield::ENERGY_PER_PIP,
+        ));
+        self.shield = 1;
+    }
+    /// Retourne le bouclier d'énergie du vaisseau, s'il est activé.
+    pub fn get_energy_shield(&self) -> Option<&EnergyShield> {
+        self.energy_shield.as_ref()
+    }
+    /// Retourne les améliorations achetées pendant la partie.
+    pub fn get_upgrades(&self) -> &Upgrades {
+        &self.upgrades
+    }
+    /// Applique une amélioration achetée dans la boutique (pour le reste de la partie).
+    /// Un bouclier supplémentaire est immédiatement disponible (le bouclier d'énergie est rechargé).
+    ///
+    /// # Arguments
+    ///
+    /// - `upgrade`: L'amélioration.
+    pub fn apply_upgrade(&mut self, upgrade: Upgrade) {
+        upgrade.improve(&mut self.definition);
+        self.upgrades.raise(upgrade);
+        if upgrade == Upgrade::ShieldCapacity {
+            if self.energy_shield.is_some() {
+                self.enable_energy_shield();
+            } else {
+                self.shield += 1;
+            }
+        }
+    }
+    /// Durée (en secondes) pendant laquelle un nouvel impact est ignoré après une collision.
+    fn grace_period(&self) -> f64 {
+        match self.energy_shield {
+            Some(_) => EnergyShield::HIT_GRACE,
+            None => tuning().spaceship_grace_period,
         }
     }
     /// Retourne la position de départ du vaisseau spatial (centre de l'écran).
@@ -77,6 +236,18 @@ impl Spaceship {
     pub fn get_rotation(&self) -> f32 {
         self.rotation
     }
+    /// Retourne la classe du vaisseau spatial.
+    pub fn get_definition(&self) -> &ShipDefinition {
+        &self.definition
+    }
+    /// Comptabilise des missiles tirés (statistiques de la partie, qui servent aussi au bonus de précision).
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: Le nombre de missiles tirés.
+    pub fn register_shots(&mut self, count: usize) {
+        self.stats.shots_fired += count as u32;
+    }
     /// Vérifie si le vaisseau spatial est en collision avec un astéroïde.
     ///
     /// # Arguments
@@ -100,22 +271,53 @@ impl Spaceship {
     ///
     /// # Retourne
     ///
-    /// - `true` si une collision est détectée.
-    /// - `false` sinon.
+    /// - `Some(&Asteroid)` le premier astéroïde en collision avec le vaisseau.
+    /// - `None` sinon.
     ///
     /// # Note
     ///
     /// Cette méthode est asynchrone pour être utilisée dans des boucles de jeu.
-    pub async fn spaceship_collision(&self, asteroids: &mut [Asteroid]) -> bool {
+    pub async fn spaceship_collision<'a>(
+        &self,
+        asteroids: &'a mut [Asteroid],
+    ) -> Option<&'a Asteroid> {
         // Check if the spaceship collides, repeated at each frame
         let mut i = 0;
         while i < asteroids.len() {
             if self.collision(&asteroids[i]) {
-                return true;
+                return Some(&asteroids[i]);
             }
             i += 1;
         }
-        false
+        None
+    }
+    /// Vérifie les collisions avec une liste d'astéroïdes et met à jour le bouclier en cas d'impact.
+    /// Les dégâts (bouclier d'énergie) dépendent de la taille de l'astéroïde et de la vitesse relative.
+    ///
+    /// # Arguments
+    ///
+    /// - `asteroids`: Une liste mutable d'astéroïdes.
+    /// - `current_time`: Le temps actuel (en secondes).
+    /// - `sound`: Une référence à l'objet `Sound` pour jouer un effet sonore.
+    ///
+    /// # Retourne
+    ///
+    /// - `true` si le vaisseau est détruit.
+    /// - `false` sinon.
+    pub async fn asteroid_impact(
+        &mut self,
+        asteroids: &mut [Asteroid],
+        current_time: f64,
+        sound: &Sound,
+    ) -> bool {
+        let damage = match self.spaceship_collision(asteroids).await {
+            Some(asteroid) => EnergyShield::impact_damage(
+                asteroid.get_size(),
+                (asteroid.get_speed() - self.speed).length(),
+            ),
+            None => return false,
+        };
+        self.check_shield(damage, current_time, sound)
     }
     /// Vérifie si le vaisseau spatial est en collision avec un trou noir.
     ///
@@ -158,9 +360,11 @@ impl Spaceship {
         false
     }
     /// Vérifie et met à jour l'état du bouclier après une collision.
+    /// Le bouclier par points perd un point ; le bouclier d'énergie perd `damage`.
     ///
     /// # Arguments
     ///
+    /// - `damage`: Les dégâts de l'impact (bouclier d'énergie).
     /// - `current_time`: Le temps actuel (en secondes).
     /// - `sound`: Une référence à l'objet `Sound` pour jouer un effet sonore.
     ///
@@ -168,11 +372,20 @@ impl Spaceship {
     ///
     /// - `true` si le bouclier est épuisé.
     /// - `false` sinon.
-    pub fn check_shield(&mut self, current_time: f64, sound: &Sound) -> bool {
-        if current_time - self.last_collision_time >= 2.0 {
+    pub fn check_shield(&mut self, damage: f32, current_time: f64, sound: &Sound) -> bool {
+        if current_time - self.last_collision_time >= self.grace_period() {
             sound.play_sound_effect("collision");
-            self.shield -= 1;
+            match &mut self.energy_shield {
+                Some(energy_shield) => {
+                    if energy_shield.hit(damage, current_time) {
+                        self.shield = 0;
+                    }
+                }
+                None => self.shield -= 1,
+            }
             self.last_collision_time = current_time;
+            self.score_engine.register_damage();
+            self.stats.shield_hits += 1;
 
             if self.shield == 0 {
                 return true;
@@ -180,122 +393,74 @@ impl Spaceship {
         }
         false
     }
-    /// Met à jour la position du vaisseau spatial en mode classique.
+    /// Met à jour la position du vaisseau spatial selon les touches du joueur.
     ///
     /// # Arguments
     ///
+    /// - `scheme`: Le schéma de contrôle choisi.
     /// - `keybinding`: Les touches associées au contrôle du vaisseau.
-    pub fn update_position_classic(&mut self, keybinding: &Bind) {
-        self.speed += match (
-            is_key_down(keybinding.get_up()),
-            is_key_down(keybinding.get_down()),
-        ) {
-            (true, false) => {
-                Vec2::new(self.rotation.sin(), -self.rotation.cos()) * Self::SPACE_SHIP_ACCELERATION
-            }
-            (false, true) => {
-                Vec2::new(-self.rotation.sin(), self.rotation.cos()) * Self::SPACE_SHIP_ACCELERATION
-            }
-            _ => Vec2::new(0.0, 0.0),
-        };
-        self.speed *= Self::SPACE_SHIP_DECELERATION;
-
-        self.rotation += match (
-            is_key_down(keybinding.get_left()),
-            is_key_down(keybinding.get_right()),
-        ) {
-            (true, false) => -0.05,
-            (false, true) => 0.05,
-            _ => 0.0,
-        };
-
-        self.position += self.speed;
-        self.position = Spaceship::bound_pos(self.position);
+    pub fn update_position(&mut self, scheme: ControlScheme, keybinding: &Bind) {
+        self.apply_input(scheme, &ShipInput::from_keyboard(keybinding, scheme));
     }
-    /// Met à jour la position du vaisseau spatial en mode moderne.
+    /// Applique des commandes au vaisseau spatial (rotation, accélération et déplacement).
     ///
     /// # Arguments
     ///
-    /// - `keybinding`: Les touches associées au contrôle du vaisseau.
-    pub fn update_position_modern(&mut self, keybinding: &Bind) {
-        self.speed += match (
-            is_key_down(keybinding.get_up()),
-            is_key_down(keybinding.get_down()),
-            is_key_down(keybinding.get_left()),
-            is_key_down(keybinding.get_right()),
-        ) {
-            (true, false, true, false) => Vec2::new(-1.0, -1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (true, false, false, true) => Vec2::new(1.0, -1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (false, true, true, false) => Vec2::new(-1.0, 1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (false, true, false, true) => Vec2::new(1.0, 1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (true, false, false, false) => Vec2::new(0.0, -1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (false, true, false, false) => Vec2::new(0.0, 1.0) * Self::SPACE_SHIP_ACCELERATION,
-            (false, false, true, false) => Vec2::new(-1.0, 0.0) * Self::SPACE_SHIP_ACCELERATION,
-            (false, false, false, true) => Vec2::new(1.0, 0.0) * Self::SPACE_SHIP_ACCELERATION,
-            _ => Vec2::new(0.0, 0.0),
-        };
-        self.speed *= Self::SPACE_SHIP_DECELERATION;
+    /// - `scheme`: Le schéma de contrôle utilisé pour interpréter les commandes.
+    /// - `input`: Les commandes de la frame (clavier ou réseau).
+    pub fn apply_input(&mut self, scheme: ControlScheme, input: &ShipInput) {
+        let (direction, rotation) = scheme.steer(
+            input,
+            self.position,
+            self.rotation,
+            self.definition.turn_rate,
+        );
+        self.speed += direction * self.definition.acceleration;
+        self.speed *= 1.0 - self.definition.drag;
+        self.rotation = rotation;
 
         self.position += self.speed;
         self.position = Spaceship::bound_pos(self.position);
+        self.stats.distance += self.speed.length();
     }
-    /// Dessine le vaisseau spatial en mode classique.
+    /// Remplace l'état de mouvement du vaisseau spatial (état reçu du réseau).
     ///
     /// # Arguments
     ///
-    /// - `texture`: La texture utilisée pour représenter le vaisseau.
-    /// - `time`: Le temps actuel (en secondes).
-    pub fn draw_spaceship_classic(&self, texture: &Texture2D, time: f64) {
-        if time - self.last_collision_time < 2.0 && has_even_decimal_part(time) {
-            return;
-        }
-        if self.shield != 1 {
-            draw_circle(
-                self.get_position().x,
-                self.get_position().y,
-                self.get_size() + 20.0,
-                Color::new(0.0, 1.0, 0.0, 0.5),
-            );
-        }
-        let thickness = 2.0;
-        let rota = self.get_rotation();
-        draw_texture_ex(
-            texture,
-            self.get_position().x - (self.get_size() * thickness / 2.0),
-            self.get_position().y - (self.get_size() * thickness / 2.0),
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2::new(
-                    self.get_size() * thickness,
-                    self.get_size() * thickness,
-                )),
-                rotation: rota,
-                ..Default::default()
-            },
-        );
+    /// - `position`: La nouvelle position.
+    /// - `speed`: La nouvelle vitesse.
+    /// - `rotation`: La nouvelle rotation, en radians.
+    pub fn set_motion(&mut self, position: Vec2, speed: Vec2, rotation: f32) {
+        self.position = position;
+        self.speed = speed;
+        self.rotation = rotation;
     }
-
-    /// Dessine le vaisseau spatial en mode moderne.
+    /// Dessine le vaisseau spatial, orienté selon sa rotation.
     ///
     /// # Arguments
     ///
     /// - `texture`: La texture utilisée pour représenter le vaisseau.
     /// - `time`: Le temps actuel (en secondes).
-    pub fn draw_spaceship_modern(&self, texture: &Texture2D, time: f64) {
-        if time - self.last_collision_time < 2.0 && has_even_decimal_part(time) {
+    pub fn draw_spaceship(&self, texture: &Texture2D, time: f64) {
+        if time - self.last_collision_time < self.grace_period() && has_even_decimal_part(time) {
             return;
         }
-        if self.shield != 1 {
+        // Le cercle du bouclier s'estompe avec l'énergie restante
+        let alpha = match &self.energy_shield {
+            Some(energy_shield) => 0.5 * energy_shield.ratio(time),
+            None if self.shield != 1 => 0.5,
+            None => 0.0,
+        };
+        if alpha > 0.0 {
             draw_circle(
                 self.get_position().x,
                 self.get_position().y,
                 self.get_size() + 20.0,
-                Color::new(0.0, 1.0, 0.0, 0.5),
+                Color::new(0.0, 1.0, 0.0, alpha),
             );
         }
-        // To make the image thicker
         let thickness = 2.0;
-        let rota = self.angle_from_mouse();
+        let rota = self.get_rotation();
         draw_texture_ex(
             texture,
             self.get_position().x - (self.get_size() * thickness / 2.0),
@@ -306,7 +471,7 @@ impl Spaceship {
                     self.get_size() * thickness,
                     self.get_size() * thickness,
                 )),
-                rotation: rota.y.atan2(rota.x) + std::f32::consts::FRAC_PI_2,
+                rotation: rota,
                 ..Default::default()
             },
         );
@@ -319,29 +484,83 @@ impl_stellar_object!(Spaceship);
 mod tests {
     use super::*;
     use crate::asteroid::AsteroidShape;
+    use crate::material::Material;
 
     #[test]
     fn test_spaceship_new() {
-        let spaceship = Spaceship::new();
+        let spaceship = Spaceship::new(&ShipDefinition::default());
         assert_eq!(spaceship.get_position(), Vec2::new(220.0, 330.0));
-        assert_eq!(spaceship.get_size(), Spaceship::SPACE_SHIP_SIZE);
+        assert_eq!(spaceship.get_size(), ShipDefinition::default().size);
         assert_eq!(spaceship.get_rotation(), 0.0);
         assert_eq!(spaceship.shield, 3);
         assert_eq!(spaceship.last_collision_time, -2.0);
         assert_eq!(spaceship.score, 0);
     }
 
+    #[test]
+    fn test_spaceship_uses_definition() {
+        let definition = ShipDefinition {
+            size: 26.0,
+            shield: 5,
+            ..ShipDefinition::default()
+        };
+        let mut spaceship = Spaceship::new(&definition);
+        assert_eq!(spaceship.get_size(), 26.0);
+        assert_eq!(spaceship.shield, 5);
+        spaceship.shield = 1;
+        spaceship.respawn(Vec2::ZERO, 0.0);
+        assert_eq!(spaceship.shield, 5);
+    }
+
+    #[test]
+    fn test_ship_input_bits_round_trip() {
+        let input = ShipInput {
+            up: true,
+            down: false,
+            left: true,
+            right: false,
+            strafe_left: false,
+            strafe_right: true,
+            fire: true,
+            aim: Vec2::ZERO,
+        };
+        assert_eq!(ShipInput::from_bits(input.to_bits()), input);
+    }
+
+    #[test]
+    fn test_apply_input_is_deterministic() {
+        let input = ShipInput {
+            up: true,
+            right: true,
+            ..Default::default()
+        };
+        let mut first = Spaceship::new(&ShipDefinition::default());
+        let mut second = Spaceship::new(&ShipDefinition::default());
+        for _ in 0..10 {
+            first.apply_input(ControlScheme::Tank, &input);
+            second.apply_input(ControlScheme::Tank, &input);
+        }
+        assert_eq!(first.get_position(), second.get_position());
+        assert!(first.get_position() != Vec2::new(220.0, 330.0));
+    }
+
     #[test]
     fn test_spaceship_collision_asteroid() {
         let spaceship_test: Spaceship = Spaceship {
             // Attributs fictifs pour le test
             position: Vec2::new(200.0, 300.0),
             speed: Vec2::new(0.0, 0.0),
-            size: Spaceship::SPACE_SHIP_SIZE,
+            size: ShipDefinition::default().size,
             rotation: 0.0,
             shield: 3,
             last_collision_time: 0.0,
             score: 0,
+            score_engine: ScoreEngine::new(),
+            stats: SessionStats::new(),
+            definition: ShipDefinition::default(),
+            energy_shield: None,
+            ore: 0,
+            upgrades: Upgrades::default(),
         };
 
         let asteroid_test = Asteroid {
@@ -352,6 +571,8 @@ mod tests {
             size: 20.0,
             rotation: 0.0,
             birth_time: 0.0,
+            material: Material::Rock,
+            hit_points: 1,
         };
         assert_eq!(spaceship_test.collision(&asteroid_test), true);
     }
@@ -362,11 +583,17 @@ mod tests {
             // Attributs fictifs pour le test
             position: Vec2::new(200.0, 300.0),
             speed: Vec2::new(0.0, 0.0),
-            size: Spaceship::SPACE_SHIP_SIZE,
+            size: ShipDefinition::default().size,
             rotation: 0.0,
             shield: 3,
             last_collision_time: 0.0,
             score: 0,
+            score_engine: ScoreEngine::new(),
+            stats: SessionStats::new(),
+            definition: ShipDefinition::default(),
+            energy_shield: None,
+            ore: 0,
+            upgrades: Upgrades::default(),
         };
 
         let blackhole_test = BlackHole {
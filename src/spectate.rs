@@ -0,0 +1,377 @@
+//! # Spectate Module
+//!
+//! Ce module diffuse une partie en cours aux spectateurs (TCP) et permet de la regarder
+//! depuis une autre instance du jeu.
+//!
+//! Chaque frame est envoyée sous la forme `[taille u32][type u8][mode u8][données]` :
+//! l'instantané complet du monde (voir `network::WorldView`) pour la première frame,
+//! puis le delta par rapport à la frame précédemment envoyée au spectateur.
+use crate::asteroid::Asteroid;
+use crate::classic::draw_classic;
+use crate::lobby::{enter_address, load_textures, show_message};
+use crate::modern::draw_modern;
+use crate::network::{decode_delta, encode_delta, resolve_address, Reader, WorldSnapshot};
+use crate::shipclass::ShipDefinition;
+use crate::sound::Sound;
+use crate::spaceship::Spaceship;
+use macroquad::prelude::*;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+/// Port utilisé par défaut pour la diffusion aux spectateurs.
+pub const SPECTATOR_PORT: u16 = 7879;
+
+/// Frame contenant l'instantané complet du monde.
+const FRAME_FULL: u8 = 0;
+/// Frame contenant le delta par rapport à la frame précédente.
+const FRAME_DELTA: u8 = 1;
+/// Taille maximale des données en attente d'envoi pour un spectateur
+/// (au-delà, les frames sont sautées jusqu'à ce qu'il rattrape son retard).
+const MAX_BACKLOG: usize = 256 * 1024;
+/// Taille maximale d'une frame acceptée par un spectateur.
+const MAX_FRAME: usize = 16 * 1024 * 1024;
+
+/// Code d'un mode de jeu dans le flux.
+fn mode_to_u8(mode: &str) -> u8 {
+    match mode {
+        "modern" => 1,
+        _ => 0,
+    }
+}
+
+/// Mode de jeu correspondant à un code du flux.
+fn mode_from_u8(code: u8) -> &'static str {
+    match code {
+        1 => "modern",
+        _ => "classic",
+    }
+}
+
+/// Spectateur connecté à la diffusion.
+struct Viewer {
+    stream: TcpStream,
+    outgoing: Vec<u8>,
+    last_sent: Option<Vec<u8>>,
+}
+
+/// Diffusion d'une partie aux spectateurs.
+pub struct SpectatorServer {
+    listener: TcpListener,
+    viewers: Vec<Viewer>,
+}
+
+impl SpectatorServer {
+    /// Ouvre le port de diffusion.
+    ///
+    /// # Arguments
+    /// - `port` Le port TCP (0 pour un port libre quelconque).
+    ///
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            viewers: Vec::new(),
+        })
+    }
+
+    /// Port de diffusion effectif.
+    pub fn port(&self) -> u16 {
+        self.listener.local_addr().map(|a| a.port()).unwrap_or(0)
+    }
+
+    /// Nombre de spectateurs connectés.
+    pub fn viewer_count(&self) -> usize {
+        self.viewers.len()
+    }
+
+    /// Accepte les nouveaux spectateurs.
+    pub fn poll(&mut self) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                let _ = stream.set_nodelay(true);
+                self.viewers.push(Viewer {
+                    stream,
+                    outgoing: Vec::new(),
+                    last_sent: None,
+                });
+            }
+        }
+    }
+
+    /// Envoie l'état du monde de la frame à tous les spectateurs.
+    /// Les spectateurs déconnectés sont retirés.
+    ///
+    /// # Arguments
+    /// - `mode` Le mode de jeu ('classic' ou 'modern').
+    /// - `world` L'instantané encodé (voir `network::WorldView::encode`).
+    ///
+    pub fn broadcast(&mut self, mode: &str, world: &[u8]) {
+        self.viewers.retain_mut(|viewer| {
+            if viewer.outgoing.len() < MAX_BACKLOG {
+                let (kind, payload) = match &viewer.last_sent {
+                    Some(previous) => (FRAME_DELTA, encode_delta(previous, world)),
+                    None => (FRAME_FULL, world.to_vec()),
+                };
+                let length = (payload.len() + 2) as u32;
+                viewer.outgoing.extend_from_slice(&length.to_le_bytes());
+                viewer.outgoing.push(kind);
+                viewer.outgoing.push(mode_to_u8(mode));
+                viewer.outgoing.extend_from_slice(&payload);
+                viewer.last_sent = Some(world.to_vec());
+            }
+            while !viewer.outgoing.is_empty() {
+                match viewer.stream.write(&viewer.outgoing) {
+                    Ok(0) => return false,
+                    Ok(written) => {
+                        viewer.outgoing.drain(..written);
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(_) => return false,
+                }
+            }
+            true
+        });
+    }
+}
+
+/// Connexion d'un spectateur à une partie diffusée.
+pub struct SpectatorClient {
+    stream: TcpStream,
+    incoming: Vec<u8>,
+    last_world: Option<Vec<u8>>,
+    closed: bool,
+}
+
+impl SpectatorClient {
+    /// Se connecte à une partie diffusée.
+    ///
+    /// # Arguments
+    /// - `address` L'adresse de la partie (`ip:port`, ou `ip` pour le port par défaut).
+    ///
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let address = resolve_address(address, SPECTATOR_PORT)?;
+        let stream = TcpStream::connect_timeout(&address, Duration::from_secs(3))?;
+        stream.set_nonblocking(true)?;
+        Ok(Self {
+            stream,
+            incoming: Vec::new(),
+            last_world: None,
+            closed: false,
+        })
+    }
+
+    /// Vérifie si la partie a fermé la connexion.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Lit les frames reçues et renvoie l'état du monde le plus récent.
+    ///
+    /// # Returns
+    /// - `Some((mode, WorldSnapshot))` Le mode de jeu et le dernier état du monde reçu.
+    /// - `None` si aucune frame complète n'a été reçue.
+    ///
+    pub fn poll(&mut self) -> Option<(&'static str, WorldSnapshot)> {
+        let mut buffer = [0; 16 * 1024];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => {
+                    self.closed = true;
+                    break;
+                }
+                Ok(size) => self.incoming.extend_from_slice(&buffer[..size]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+
+        let mut latest = None;
+        while let Some(length) = Reader::new(&self.incoming).u32() {
+            let length = length as usize;
+            if !(2..=MAX_FRAME).contains(&length) {
+                self.closed = true;
+                break;
+            }
+            if self.incoming.len() < 4 + length {
+                break;
+            }
+            let frame: Vec<u8> = self.incoming.drain(..4 + length).skip(4).collect();
+            let world = match (frame[0], &self.last_world) {
+                (FRAME_FULL, _) => Some(frame[2..].to_vec()),
+                (FRAME_DELTA, Some(previous)) => decode_delta(previous, &frame[2..]),
+                _ => None,
+            };
+            match world {
+                Some(world) => {
+                    latest = Some(mode_from_u8(frame[1]));
+                    self.last_world = Some(world);
+                }
+                None => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+        let mode = latest?;
+        WorldSnapshot::decode(self.last_world.as_ref()?).map(|world| (mode, world))
+    }
+}
+
+/// Mode spectateur : regarde une partie diffusée par une autre instance du jeu.
+/// L'affichage utilise les mêmes fonctions que la partie regardée (`draw_classic`, `draw_modern`).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan du menu.
+/// - `asteroid_texture` La texture des astéroids du menu.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseau).
+///
+pub async fn spectate(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+    skins: [&str; 4],
+) {
+    let Some(address) = enter_address(background_texture, asteroid_texture, asteroids, sound).await
+    else {
+        return;
+    };
+    let mut client = match SpectatorClient::connect(&address) {
+        Ok(client) => client,
+        Err(e) => {
+            show_message(&format!("Unable to spectate {} : {}", address, e), sound).await;
+            return;
+        }
+    };
+
+    let textures = load_textures(skins).await;
+    let textures = [&textures[0], &textures[1], &textures[2], &textures[3]];
+    let mut current: Option<(&str, WorldSnapshot)> = None;
+
+    loop {
+        if let Some(received) = client.poll() {
+            current = Some(received);
+        }
+        if client.is_closed() {
+            show_message("The game has ended", sound).await;
+            return;
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+
+        clear_background(BLACK);
+        match &current {
+            Some((mode, world)) => {
+                let no_ship = Spaceship::new(&ShipDefinition::default());
+                let spaceship = world.get_ship(0).unwrap_or(&no_ship);
+                if *mode == "modern" {
+                    draw_modern(
+                        textures,
+                        &world.asteroids,
+                        &world.blackholes,
+                        spaceship,
+                        world.time,
+                        &world.missiles,
+                    );
+                } else {
+                    draw_classic(
+                        textures,
+                        &world.asteroids,
+                        &world.blackholes,
+                        spaceship,
+                        world.time,
+                        &world.missiles,
+                    );
+                }
+                if world.over {
+                    let text = if world.won { "You Won !" } else { "Game Over" };
+                    let dimension = measure_text(text, None, 60, 1.0);
+                    draw_text(
+                        text,
+                        (screen_width() - dimension.width) / 2.0,
+                        screen_height() / 2.0,
+                        60.0,
+                        WHITE,
+                    );
+                }
+            }
+            None => {
+                let text = format!("Waiting for {}...", address);
+                let dimension = measure_text(&text, None, 40, 1.0);
+                draw_text(
+                    &text,
+                    (screen_width() - dimension.width) / 2.0,
+                    screen_height() / 2.0,
+                    40.0,
+                    WHITE,
+                );
+            }
+        }
+        draw_text(
+            "SPECTATING - 'Backspace' to leave",
+            10.0,
+            screen_height() - 20.0,
+            25.0,
+            YELLOW,
+        );
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::WorldView;
+    use std::thread::sleep;
+
+    #[test]
+    fn test_stream_over_loopback() {
+        let mut server = SpectatorServer::bind(0).unwrap();
+        let mut client = SpectatorClient::connect(&format!("127.0.0.1:{}", server.port())).unwrap();
+        for _ in 0..200 {
+            server.poll();
+            if server.viewer_count() == 1 {
+                break;
+            }
+            sleep(Duration::from_millis(5));
+        }
+        assert_eq!(server.viewer_count(), 1);
+
+        let mut ship = Spaceship::new(&ShipDefinition::default());
+        for (time, score) in [(1.0, 10), (2.0, 25)] {
+            ship.score = score;
+            let view = WorldView {
+                time,
+                over: false,
+                won: false,
+                ships: vec![(0, &ship)],
+                asteroids: &[],
+                blackholes: &[],
+                missiles: &[],
+            };
+            server.broadcast("modern", &view.encode());
+        }
+
+        let mut received = None;
+        for _ in 0..200 {
+            if let Some((mode, world)) = client.poll() {
+                received = Some((mode, world.time, world.get_ship(0).unwrap().score));
+                if world.time == 2.0 {
+                    break;
+                }
+            }
+            sleep(Duration::from_millis(5));
+        }
+        assert_eq!(received, Some(("modern", 2.0, 25)));
+        assert!(!client.is_closed());
+    }
+}
@@ -0,0 +1,231 @@
+//! # Station Module
+//!
+//! Ce module gère les structures à protéger des astéroïdes :
+//! - la capsule de cargaison des missions d'escorte, qui dérive à travers l'écran ;
+//! - la station spatiale du mode défense, qui se déplace lentement et vers laquelle
+//!   les astéroïdes sont lancés (voir `Spawner::Toward`).
+//!
+//! Chaque astéroïde qui percute la structure lui retire un point de vie
+//! (l'astéroïde est détruit par le choc, sans se fragmenter).
+//! La partie est perdue lorsque la structure est détruite.
+use crate::asteroid::Asteroid;
+use crate::impl_stellar_object;
+use crate::stellarobject::StellarObject;
+use macroquad::prelude::*;
+
+/// Type de structure à protéger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StationKind {
+    /// Capsule de cargaison (missions d'escorte).
+    CargoPod,
+    /// Station spatiale (mode défense).
+    Outpost,
+}
+
+impl StationKind {
+    /// Taille (rayon) de la structure.
+    fn size(self) -> f32 {
+        match self {
+            StationKind::CargoPod => 25.0,
+            StationKind::Outpost => 60.0,
+        }
+    }
+
+    /// Points de vie de la structure intacte.
+    pub fn max_hit_points(self) -> u32 {
+        match self {
+            StationKind::CargoPod => 5,
+            StationKind::Outpost => 20,
+        }
+    }
+
+    /// Nom affiché de la structure.
+    pub fn name(self) -> &'static str {
+        match self {
+            StationKind::CargoPod => "Cargo pod",
+            StationKind::Outpost => "Station",
+        }
+    }
+}
+
+/// Structure à protéger (capsule de cargaison ou station spatiale).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Station {
+    pub position: Vec2,
+    pub speed: Vec2,
+    pub size: f32,
+    /// Type de structure.
+    kind: StationKind,
+    /// Points de vie restants.
+    hit_points: u32,
+}
+
+impl Station {
+    /// Vitesse de dérive de la station spatiale.
+    const OUTPOST_DRIFT: Vec2 = vec2(0.15, 0.08);
+
+    /// Créer une structure intacte.
+    ///
+    /// # Arguments
+    /// - `kind` Le type de structure.
+    /// - `position` La position de départ.
+    /// - `speed` La vitesse de dérive.
+    ///
+    pub fn new(kind: StationKind, position: Vec2, speed: Vec2) -> Self {
+        Self {
+            position,
+            speed,
+            size: kind.size(),
+            kind,
+            hit_points: kind.max_hit_points(),
+        }
+    }
+
+    /// Créer la station spatiale du mode défense, qui dérive lentement.
+    ///
+    /// # Arguments
+    /// - `position` La position de départ.
+    ///
+    pub fn outpost(position: Vec2) -> Self {
+        Self::new(StationKind::Outpost, position, Self::OUTPOST_DRIFT)
+    }
+
+    /// Getter du type de structure.
+    pub fn get_kind(&self) -> StationKind {
+        self.kind
+    }
+
+    /// Fait dériver la structure (elle réapparaît de l'autre côté de l'écran).
+    pub fn update(&mut self) {
+        self.position = Self::bound_pos(self.position + self.speed);
+    }
+
+    /// Détruit les astéroïdes qui percutent la structure ; chacun lui retire un point de vie.
+    ///
+    /// # Arguments
+    /// - `asteroids` Les astéroïdes.
+    ///
+    /// # Returns
+    /// - `bool` Vrai si la structure a été touchée.
+    ///
+    pub fn hit_by_asteroids(&mut self, asteroids: &mut Vec<Asteroid>) -> bool {
+        let count = asteroids.len();
+        asteroids.retain(|asteroid| {
+            asteroid.get_position().distance(self.position) > asteroid.get_size() + self.size
+        });
+        let hits = (count - asteroids.len()) as u32;
+        self.hit_points = self.hit_points.saturating_sub(hits);
+        hits > 0
+    }
+
+    /// Vérifie si la structure est détruite.
+    pub fn is_destroyed(&self) -> bool {
+        self.hit_points == 0
+    }
+
+    /// Points de vie restants.
+    pub fn get_hit_points(&self) -> u32 {
+        self.hit_points
+    }
+
+    /// Part des points de vie restants (entre 0 et 1).
+    pub fn health_ratio(&self) -> f32 {
+        self.hit_points as f32 / self.kind.max_hit_points() as f32
+    }
+
+    /// Dessine la structure et sa barre de vie.
+    pub fn draw(&self) {
+        let color = Color::new(1.0, 0.8, 0.2, 1.0);
+        match self.kind {
+            StationKind::CargoPod => {
+                draw_rectangle(
+                    self.position.x - self.size,
+                    self.position.y - self.size / 2.0,
+                    self.size * 2.0,
+                    self.size,
+                    color,
+                );
+                draw_rectangle_lines(
+                    self.position.x - self.size,
+                    self.position.y - self.size / 2.0,
+                    self.size * 2.0,
+                    self.size,
+                    2.0,
+                    WHITE,
+                );
+            }
+            StationKind::Outpost => {
+                draw_circle(self.position.x, self.position.y, self.size * 0.45, DARKGRAY);
+                draw_circle_lines(self.position.x, self.position.y, self.size, 6.0, color);
+                draw_circle_lines(
+                    self.position.x,
+                    self.position.y,
+                    self.size * 0.45,
+                    2.0,
+                    WHITE,
+                );
+                for angle in [0.0, 0.5, 1.0, 1.5] {
+                    let direction = Vec2::from_angle(angle * std::f32::consts::PI);
+                    let start = self.position + direction * self.size * 0.45;
+                    let end = self.position + direction * self.size;
+                    draw_line(start.x, start.y, end.x, end.y, 3.0, GRAY);
+                }
+            }
+        }
+        let bar_y = self.position.y - self.size - 10.0;
+        draw_rectangle(
+            self.position.x - self.size,
+            bar_y,
+            self.size * 2.0 * self.health_ratio(),
+            5.0,
+            GREEN,
+        );
+        draw_rectangle_lines(
+            self.position.x - self.size,
+            bar_y,
+            self.size * 2.0,
+            5.0,
+            1.0,
+            WHITE,
+        );
+    }
+}
+
+impl_stellar_object!(Station);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::AsteroidShape;
+
+    #[test]
+    fn test_station_is_worn_down_by_asteroids() {
+        let mut cargo = Station::new(StationKind::CargoPod, vec2(100.0, 100.0), vec2(1.0, 0.0));
+        cargo.update();
+        assert_eq!(cargo.position, vec2(101.0, 100.0));
+
+        let mut asteroids = vec![
+            Asteroid::new(vec2(120.0, 100.0), Vec2::ZERO, AsteroidShape::Small, 20.0),
+            Asteroid::new(vec2(400.0, 400.0), Vec2::ZERO, AsteroidShape::Large, 60.0),
+        ];
+        assert!(cargo.hit_by_asteroids(&mut asteroids));
+        assert_eq!(asteroids.len(), 1);
+        assert!(!cargo.hit_by_asteroids(&mut asteroids));
+        assert_eq!(cargo.health_ratio(), 0.8);
+
+        for _ in 0..StationKind::CargoPod.max_hit_points() {
+            let mut asteroids = vec![Asteroid::new(
+                cargo.position,
+                Vec2::ZERO,
+                AsteroidShape::Small,
+                20.0,
+            )];
+            cargo.hit_by_asteroids(&mut asteroids);
+        }
+        assert!(cargo.is_destroyed());
+
+        let outpost = Station::outpost(vec2(400.0, 300.0));
+        assert_eq!(outpost.get_kind(), StationKind::Outpost);
+        assert_eq!(outpost.get_hit_points(), 20);
+    }
+}
@@ -0,0 +1,311 @@
+//! # Stats Module
+//!
+//! Ce module enregistre les statistiques d'une partie (`SessionStats`) et les cumule
+//! sur toutes les parties jouées (`LifetimeStats`, sauvegardées sur le disque).
+use crate::asteroid::{Asteroid, AsteroidShape};
+use crate::highscore::HighScores;
+use crate::save::{get_or_default, read_key_values, write_key_values};
+use crate::sound::Sound;
+use crate::utils::{background_asteroids, handle_enter_press};
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Statistiques d'une partie.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionStats {
+    /// Nombre de missiles tirés.
+    pub shots_fired: u32,
+    /// Nombre de missiles ayant touché un astéroïd.
+    pub hits: u32,
+    /// Astéroïds détruits, par forme (`Small`, `Medium`, `Large`).
+    pub destroyed: [u32; 3],
+    /// Nombre de coups reçus par le bouclier.
+    pub shield_hits: u32,
+    /// Nombre de trous noirs formés.
+    pub blackholes_formed: u32,
+    /// Distance parcourue par le vaisseau, en pixels.
+    pub distance: f32,
+    /// Temps de survie, en secondes.
+    pub survival_time: f64,
+}
+
+impl SessionStats {
+    /// Créer des statistiques vides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indice d'une forme d'astéroïd dans le tableau `destroyed`.
+    fn shape_index(shape: AsteroidShape) -> usize {
+        match shape {
+            AsteroidShape::Small => 0,
+            AsteroidShape::Medium => 1,
+            AsteroidShape::Large => 2,
+        }
+    }
+
+    /// Enregistre la destruction d'un astéroïd par un missile.
+    ///
+    /// # Arguments
+    /// - `asteroid` L'astéroïd détruit.
+    ///
+    pub fn record_destroyed(&mut self, asteroid: &Asteroid) {
+        self.hits += 1;
+        self.destroyed[Self::shape_index(asteroid.get_shape())] += 1;
+    }
+
+    /// Nombre d'astéroïds détruits d'une forme donnée.
+    pub fn get_destroyed(&self, shape: AsteroidShape) -> u32 {
+        self.destroyed[Self::shape_index(shape)]
+    }
+
+    /// Nombre total d'astéroïds détruits.
+    pub fn total_destroyed(&self) -> u32 {
+        self.destroyed.iter().sum()
+    }
+
+    /// Précision des tirs (entre 0.0 et 1.0).
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            0.0
+        } else {
+            self.hits as f32 / self.shots_fired as f32
+        }
+    }
+
+    /// Lignes de texte décrivant les statistiques (pour l'affichage).
+    pub fn describe(&self) -> Vec<String> {
+        vec![
+            format!("Shots fired : {}", self.shots_fired),
+            format!("Hits : {}", self.hits),
+            format!("Accuracy : {:.0}%", self.accuracy() * 100.0),
+            format!(
+                "Destroyed : {} ({} small / {} medium / {} large)",
+                self.total_destroyed(),
+                self.get_destroyed(AsteroidShape::Small),
+                self.get_destroyed(AsteroidShape::Medium),
+                self.get_destroyed(AsteroidShape::Large)
+            ),
+            format!("Shield hits : {}", self.shield_hits),
+            format!("Black holes formed : {}", self.blackholes_formed),
+            format!("Distance : {:.0} px", self.distance),
+            format!("Survival time : {:.1} s", self.survival_time),
+        ]
+    }
+}
+
+/// Statistiques cumulées sur toutes les parties.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LifetimeStats {
+    /// Nombre de parties jouées.
+    pub games_played: u32,
+    /// Somme des statistiques de toutes les parties.
+    pub totals: SessionStats,
+    /// Plus long temps de survie, en secondes.
+    pub longest_survival: f64,
+}
+
+impl LifetimeStats {
+    /// Nom du fichier de sauvegarde des statistiques.
+    const FILE_NAME: &'static str = "stats.txt";
+
+    /// Charge les statistiques cumulées depuis le disque.
+    pub fn load() -> Self {
+        Self::from_key_values(&read_key_values(Self::FILE_NAME))
+    }
+
+    /// Sauvegarde les statistiques cumulées sur le disque.
+    pub fn save(&self) {
+        write_key_values(Self::FILE_NAME, &self.to_key_values());
+    }
+
+    /// Ajoute les statistiques d'une partie au cumul.
+    ///
+    /// # Arguments
+    /// - `session` Les statistiques de la partie terminée.
+    ///
+    pub fn add_session(&mut self, session: &SessionStats) {
+        self.games_played += 1;
+        self.totals.shots_fired += session.shots_fired;
+        self.totals.hits += session.hits;
+        for (total, destroyed) in self.totals.destroyed.iter_mut().zip(session.destroyed) {
+            *total += destroyed;
+        }
+        self.totals.shield_hits += session.shield_hits;
+        self.totals.blackholes_formed += session.blackholes_formed;
+        self.totals.distance += session.distance;
+        self.totals.survival_time += session.survival_time;
+        self.longest_survival = self.longest_survival.max(session.survival_time);
+    }
+
+    /// Construit les statistiques à partir d'un fichier `clé=valeur`.
+    fn from_key_values(values: &HashMap<String, String>) -> Self {
+        Self {
+            games_played: get_or_default(values, "games_played"),
+            totals: SessionStats {
+                shots_fired: get_or_default(values, "shots_fired"),
+                hits: get_or_default(values, "hits"),
+                destroyed: [
+                    get_or_default(values, "destroyed_small"),
+                    get_or_default(values, "destroyed_medium"),
+                    get_or_default(values, "destroyed_large"),
+                ],
+                shield_hits: get_or_default(values, "shield_hits"),
+                blackholes_formed: get_or_default(values, "blackholes_formed"),
+                distance: get_or_default(values, "distance"),
+                survival_time: get_or_default(values, "survival_time"),
+            },
+            longest_survival: get_or_default(values, "longest_survival"),
+        }
+    }
+
+    /// Convertit les statistiques en couples `clé=valeur`.
+    fn to_key_values(&self) -> Vec<(String, String)> {
+        [
+            ("games_played", self.games_played.to_string()),
+            ("shots_fired", self.totals.shots_fired.to_string()),
+            ("hits", self.totals.hits.to_string()),
+            ("destroyed_small", self.totals.destroyed[0].to_string()),
+            ("destroyed_medium", self.totals.destroyed[1].to_string()),
+            ("destroyed_large", self.totals.destroyed[2].to_string()),
+            ("shield_hits", self.totals.shield_hits.to_string()),
+            (
+                "blackholes_formed",
+                self.totals.blackholes_formed.to_string(),
+            ),
+            ("distance", self.totals.distance.to_string()),
+            ("survival_time", self.totals.survival_time.to_string()),
+            ("longest_survival", self.longest_survival.to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+
+    /// Lignes de texte décrivant les statistiques cumulées (pour l'affichage).
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![format!("Games played : {}", self.games_played)];
+        lines.extend(self.totals.describe());
+        lines.push(format!("Longest survival : {:.1} s", self.longest_survival));
+        lines
+    }
+}
+
+/// Affiche une liste de lignes de statistiques sous un titre.
+///
+/// # Arguments
+/// - `title` Le titre du panneau.
+/// - `lines` Les lignes à afficher.
+/// - `x` La position horizontale du panneau.
+/// - `y` La position verticale du titre.
+///
+pub fn draw_stats_panel(title: &str, lines: &[String], x: f32, y: f32) {
+    draw_text(title, x, y, 30.0, YELLOW);
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(line, x, y + 35.0 + i as f32 * 28.0, 24.0, WHITE);
+    }
+}
+
+/// Menu des statistiques.
+/// Affiche les statistiques cumulées de toutes les parties jouées et le meilleur score de chaque mode.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids.
+/// - `sound` Les sons du jeu.
+///
+pub async fn statistics_menu(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) {
+    let lifetime = LifetimeStats::load();
+    let lines = lifetime.describe();
+    let high_scores = HighScores::load().describe();
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let dimension = measure_text("Statistics", None, 50, 1.0);
+        draw_text(
+            "Statistics",
+            (screen_width() - dimension.width) / 2.0,
+            200.0,
+            50.0,
+            WHITE,
+        );
+        draw_stats_panel("Lifetime", &lines, screen_width() / 2.0 - 500.0, 300.0);
+        draw_stats_panel(
+            "High scores",
+            &high_scores,
+            screen_width() / 2.0 + 50.0,
+            300.0,
+        );
+
+        let back = "Back";
+        let dimension = measure_text(back, None, 40, 1.0);
+        draw_text(
+            back,
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 200.0,
+            40.0,
+            YELLOW,
+        );
+
+        if is_key_pressed(KeyCode::Backspace)
+            || is_key_pressed(KeyCode::Escape)
+            || handle_enter_press(&mut last_enter_press)
+        {
+            sound.play_sound_effect("select_menu");
+            return;
+        }
+        next_frame().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_destroyed_and_accuracy() {
+        let mut stats = SessionStats::new();
+        stats.shots_fired = 4;
+        let asteroid = Asteroid::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            AsteroidShape::Medium,
+            40.0,
+        );
+        stats.record_destroyed(&asteroid);
+        assert_eq!(stats.get_destroyed(AsteroidShape::Medium), 1);
+        assert_eq!(stats.total_destroyed(), 1);
+        assert_eq!(stats.accuracy(), 0.25);
+    }
+
+    #[test]
+    fn test_lifetime_aggregation_and_round_trip() {
+        let session = SessionStats {
+            shots_fired: 10,
+            hits: 6,
+            destroyed: [1, 2, 3],
+            shield_hits: 2,
+            blackholes_formed: 1,
+            distance: 1500.0,
+            survival_time: 42.5,
+        };
+        let mut lifetime = LifetimeStats::default();
+        lifetime.add_session(&session);
+        lifetime.add_session(&session);
+        assert_eq!(lifetime.games_played, 2);
+        assert_eq!(lifetime.totals.destroyed, [2, 4, 6]);
+        assert_eq!(lifetime.longest_survival, 42.5);
+
+        let values: HashMap<String, String> = lifetime.to_key_values().into_iter().collect();
+        assert_eq!(LifetimeStats::from_key_values(&values), lifetime);
+    }
+}
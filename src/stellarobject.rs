@@ -5,8 +5,6 @@ pub trait StellarObject {
     fn get_speed(&self) -> Vec2;
     fn get_size(&self) -> f32;
 
-    fn angle_from_mouse(&self) -> Vec2;
-
     fn set_position(&mut self, position: Vec2);
     fn bound_to(coord: f32, max: f32) -> f32;
     fn bound_pos(pos: Vec2) -> Vec2;
@@ -61,17 +59,6 @@ macro_rules! impl_stellar_object {
                     coord
                 }
             }
-            fn angle_from_mouse(&self) -> Vec2 {
-                let mouse_pos = mouse_position();
-                let mouse_vec = vec2(mouse_pos.0, mouse_pos.1);
-
-                let dx = mouse_vec.x - self.position.x;
-                let dy = mouse_vec.y - self.position.y;
-
-                let res = dy.atan2(dx);
-
-                vec2(res.cos(), res.sin())
-            }
         }
     };
 }
@@ -0,0 +1,358 @@
+//! # Tuning Module
+//!
+//! Ce module regroupe les valeurs d'équilibrage du jeu (tailles et vitesses des astéroïdes,
+//! missiles, trous noirs, vaisseau, score et courbes de difficulté).
+//!
+//! Les valeurs sont lues dans `assets/tuning.txt` (format `clé=valeur`) au démarrage,
+//! puis le fichier est surveillé (`reload_if_changed`, appelé à chaque frame par les boucles de jeu
+//! et de menu) : toute modification est prise en compte pendant la partie.
+//! Les lignes invalides sont signalées (avec leur numéro) et prennent la valeur historique ;
+//! `gui::draw_tuning_errors` affiche ces erreurs à l'écran.
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Chemin du fichier d'équilibrage.
+pub const TUNING_FILE: &str = "assets/tuning.txt";
+
+/// Délai entre deux vérifications du fichier d'équilibrage.
+const CHECK_PERIOD: Duration = Duration::from_millis(500);
+
+/// Valeurs d'équilibrage du jeu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    /// Taille maximale d'un petit astéroïde.
+    pub asteroid_small_size: f32,
+    /// Taille maximale d'un astéroïde moyen.
+    pub asteroid_medium_size: f32,
+    /// Taille maximale d'un grand astéroïde.
+    pub asteroid_large_size: f32,
+    /// Taille minimale d'un astéroïde.
+    pub asteroid_min_size: f32,
+    /// Taille minimale des fragments d'un astéroïde moyen.
+    pub asteroid_fragment_min_size: f32,
+    /// Vitesse de rotation des astéroïdes (radians par frame).
+    pub asteroid_spin: f32,
+    /// Facteur de vitesse des fragments de glace.
+    pub ice_fragment_speed: f32,
+    /// Longueur d'un missile (en nombre de pas de déplacement).
+    pub missile_length: f32,
+    /// Épaisseur d'un missile.
+    pub missile_thickness: f32,
+    /// Écart angulaire (en radians) entre les missiles d'un tir en éventail.
+    pub missile_spread_angle: f32,
+    /// Portée maximale d'un missile qui traverse les bords de l'écran.
+    pub missile_wrap_range: f32,
+    /// Taille d'un trou noir.
+    pub blackhole_size: f32,
+    /// Vitesse de rotation des trous noirs (radians par frame).
+    pub blackhole_spin: f32,
+    /// Durée (en secondes) d'invulnérabilité du vaisseau après une collision.
+    pub spaceship_grace_period: f64,
+    /// Points d'un petit astéroïde.
+    pub score_small: u32,
+    /// Points d'un astéroïde moyen.
+    pub score_medium: u32,
+    /// Points d'un grand astéroïde.
+    pub score_large: u32,
+    /// Durée d'une vague, en secondes.
+    pub wave_duration: f64,
+    /// Fenêtre (en secondes) pendant laquelle une destruction prolonge le combo.
+    pub combo_window: f64,
+    /// Gain de multiplicateur par destruction enchaînée.
+    pub multiplier_step: f32,
+    /// Multiplicateur maximal.
+    pub max_multiplier: f32,
+    /// Perte de multiplicateur par seconde, une fois la fenêtre de combo expirée.
+    pub multiplier_decay: f32,
+    /// Bonus attribué à la fin d'une vague terminée sans dégâts.
+    pub no_damage_bonus: u32,
+    /// Durée (en secondes) en dessous de laquelle nettoyer le terrain rapporte un bonus.
+    pub quick_clear_window: f64,
+    /// Points par seconde restante dans la fenêtre de nettoyage rapide.
+    pub quick_clear_points: f64,
+    /// Bonus maximal de précision (100% de tirs réussis).
+    pub accuracy_bonus_max: f32,
+    /// Nombre minimum de tirs pour obtenir le bonus de précision.
+    pub accuracy_min_shots: u32,
+    /// Durée de vie d'un texte flottant, en secondes.
+    pub popup_duration: f64,
+    /// Délai maximal (en secondes) entre deux apparitions d'astéroïdes (début de partie).
+    pub spawn_interval_max: f64,
+    /// Temps (en secondes) auquel la vitesse des astéroïdes atteint la moitié de son gain.
+    pub speed_ramp_midpoint: f64,
+}
+
+impl Default for Tuning {
+    /// Valeurs historiques du jeu.
+    fn default() -> Self {
+        Self {
+            asteroid_small_size: 30.0,
+            asteroid_medium_size: 50.0,
+            asteroid_large_size: 70.0,
+            asteroid_min_size: 10.0,
+            asteroid_fragment_min_size: 20.0,
+            asteroid_spin: 0.01,
+            ice_fragment_speed: 1.5,
+            missile_length: 5.0,
+            missile_thickness: 2.0,
+            missile_spread_angle: 0.2,
+            missile_wrap_range: 1500.0,
+            blackhole_size: 50.0,
+            blackhole_spin: 0.01,
+            spaceship_grace_period: 2.0,
+            score_small: 100,
+            score_medium: 50,
+            score_large: 25,
+            wave_duration: 15.0,
+            combo_window: 1.5,
+            multiplier_step: 0.25,
+            max_multiplier: 4.0,
+            multiplier_decay: 0.5,
+            no_damage_bonus: 250,
+            quick_clear_window: 20.0,
+            quick_clear_points: 20.0,
+            accuracy_bonus_max: 1000.0,
+            accuracy_min_shots: 10,
+            popup_duration: 1.0,
+            spawn_interval_max: 1.0,
+            speed_ramp_midpoint: 30.0,
+        }
+    }
+}
+
+impl Tuning {
+    /// Lit un fichier d'équilibrage au format `clé=valeur`.
+    /// Les lignes vides et les commentaires (`#`) sont ignorés ; les clés absentes
+    /// et les lignes invalides prennent leur valeur historique (`Tuning::default`).
+    /// Si l'ensemble des valeurs est incohérent, les valeurs de `previous` sont conservées.
+    ///
+    /// # Arguments
+    /// - `content` Le contenu du fichier.
+    /// - `previous` Les valeurs en vigueur avant la lecture (repli si la validation échoue).
+    ///
+    /// # Returns
+    /// - `(Tuning, Vec<String>)` Les valeurs lues et les erreurs rencontrées (avec leur numéro de ligne).
+    ///
+    pub fn parse(content: &str, previous: &Tuning) -> (Self, Vec<String>) {
+        let mut tuning = Tuning::default();
+        let mut errors = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => tuning.set(key.trim(), value.trim()),
+                None => Err(format!("expected 'key=value', found '{}'", line)),
+            };
+            if let Err(e) = result {
+                errors.push(format!("line {}: {}", number + 1, e));
+            }
+        }
+        if let Err(e) = tuning.validate() {
+            errors.push(e);
+            tuning = *previous;
+        }
+        (tuning, errors)
+    }
+
+    /// Modifie une valeur d'équilibrage.
+    ///
+    /// # Arguments
+    /// - `key` Le nom de la valeur.
+    /// - `value` La nouvelle valeur (texte).
+    ///
+    /// # Returns
+    /// - `Err(String)` si la clé est inconnue ou la valeur invalide.
+    ///
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "asteroid_small_size" => self.asteroid_small_size = positive(key, value)?,
+            "asteroid_medium_size" => self.asteroid_medium_size = positive(key, value)?,
+            "asteroid_large_size" => self.asteroid_large_size = positive(key, value)?,
+            "asteroid_min_size" => self.asteroid_min_size = positive(key, value)?,
+            "asteroid_fragment_min_size" => self.asteroid_fragment_min_size = positive(key, value)?,
+            "asteroid_spin" => self.asteroid_spin = positive(key, value)?,
+            "ice_fragment_speed" => self.ice_fragment_speed = positive(key, value)?,
+            "missile_length" => self.missile_length = positive(key, value)?,
+            "missile_thickness" => self.missile_thickness = positive(key, value)?,
+            "missile_spread_angle" => self.missile_spread_angle = positive(key, value)?,
+            "missile_wrap_range" => self.missile_wrap_range = positive(key, value)?,
+            "blackhole_size" => self.blackhole_size = positive(key, value)?,
+            "blackhole_spin" => self.blackhole_spin = positive(key, value)?,
+            "spaceship_grace_period" => self.spaceship_grace_period = positive(key, value)?,
+            "score_small" => self.score_small = positive(key, value)?,
+            "score_medium" => self.score_medium = positive(key, value)?,
+            "score_large" => self.score_large = positive(key, value)?,
+            "wave_duration" => self.wave_duration = positive(key, value)?,
+            "combo_window" => self.combo_window = positive(key, value)?,
+            "multiplier_step" => self.multiplier_step = positive(key, value)?,
+            "max_multiplier" => self.max_multiplier = positive(key, value)?,
+            "multiplier_decay" => self.multiplier_decay = positive(key, value)?,
+            "no_damage_bonus" => self.no_damage_bonus = positive(key, value)?,
+            "quick_clear_window" => self.quick_clear_window = positive(key, value)?,
+            "quick_clear_points" => self.quick_clear_points = positive(key, value)?,
+            "accuracy_bonus_max" => self.accuracy_bonus_max = positive(key, value)?,
+            "accuracy_min_shots" => self.accuracy_min_shots = positive(key, value)?,
+            "popup_duration" => self.popup_duration = positive(key, value)?,
+            "spawn_interval_max" => self.spawn_interval_max = positive(key, value)?,
+            "speed_ramp_midpoint" => self.speed_ramp_midpoint = positive(key, value)?,
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+        Ok(())
+    }
+
+    /// Vérifie la cohérence des valeurs entre elles.
+    ///
+    /// # Returns
+    /// - `Err(String)` Un message décrivant l'incohérence.
+    ///
+    fn validate(&self) -> Result<(), String> {
+        if !(self.asteroid_min_size <= self.asteroid_fragment_min_size
+            && self.asteroid_fragment_min_size <= self.asteroid_small_size
+            && self.asteroid_small_size <= self.asteroid_medium_size
+            && self.asteroid_medium_size <= self.asteroid_large_size)
+        {
+            return Err("asteroid sizes must be ordered: min <= fragment_min <= small <= medium <= large (file ignored)".to_string());
+        }
+        if self.max_multiplier < 1.0 {
+            return Err("'max_multiplier' must be at least 1 (file ignored)".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Lit une valeur strictement positive.
+///
+/// # Arguments
+/// - `key` Le nom de la valeur (pour le message d'erreur).
+/// - `value` La valeur (texte).
+///
+fn positive<T: FromStr + PartialOrd + Default>(key: &str, value: &str) -> Result<T, String> {
+    match value.parse::<T>() {
+        Ok(parsed) if parsed > T::default() => Ok(parsed),
+        Ok(_) => Err(format!("'{}' must be positive", key)),
+        Err(_) => Err(format!("invalid value '{}' for '{}'", value, key)),
+    }
+}
+
+/// État de la surveillance du fichier d'équilibrage.
+#[derive(Default)]
+struct Watcher {
+    /// Vrai si le fichier a déjà été lu.
+    loaded: bool,
+    /// Date de modification du fichier lors de la dernière lecture.
+    modified: Option<SystemTime>,
+    /// Moment de la dernière vérification.
+    last_check: Option<Instant>,
+    /// Erreurs de la dernière lecture.
+    errors: Vec<String>,
+}
+
+thread_local! {
+    /// Valeurs d'équilibrage en vigueur.
+    static CURRENT: Cell<Tuning> = Cell::new(Tuning::default());
+    /// Surveillance du fichier d'équilibrage.
+    static WATCHER: RefCell<Watcher> = RefCell::new(Watcher::default());
+}
+
+/// Valeurs d'équilibrage en vigueur.
+pub fn tuning() -> Tuning {
+    CURRENT.with(Cell::get)
+}
+
+/// Relit le fichier d'équilibrage s'il a été modifié depuis la dernière lecture
+/// (vérification au plus toutes les 500 ms). Un fichier absent donne les valeurs historiques.
+pub fn reload_if_changed() {
+    WATCHER.with(|watcher| {
+        let mut watcher = watcher.borrow_mut();
+        if watcher
+            .last_check
+            .is_some_and(|last| last.elapsed() < CHECK_PERIOD)
+        {
+            return;
+        }
+        watcher.last_check = Some(Instant::now());
+        let modified = fs::metadata(TUNING_FILE)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if watcher.loaded && modified == watcher.modified {
+            return;
+        }
+        watcher.loaded = true;
+        watcher.modified = modified;
+        let (values, errors) = match fs::read_to_string(TUNING_FILE) {
+            Ok(content) => Tuning::parse(&content, &tuning()),
+            Err(_) => (Tuning::default(), Vec::new()),
+        };
+        for error in &errors {
+            eprintln!("{}: {}", TUNING_FILE, error);
+        }
+        CURRENT.with(|current| current.set(values));
+        watcher.errors = errors;
+    });
+}
+
+/// Erreurs de la dernière lecture du fichier d'équilibrage.
+pub fn errors() -> Vec<String> {
+    WATCHER.with(|watcher| watcher.borrow().errors.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reports_line_numbers() {
+        let previous = Tuning::default();
+        let content = "# Astéroïdes\nasteroid_large_size=90\nmissile_length=abc\n\nscore_small=-5\nfoo=1\nno separator";
+        let (tuning, errors) = Tuning::parse(content, &previous);
+        assert_eq!(tuning.asteroid_large_size, 90.0);
+        assert_eq!(tuning.missile_length, previous.missile_length);
+        assert_eq!(tuning.score_small, previous.score_small);
+        assert_eq!(
+            errors,
+            vec![
+                "line 3: invalid value 'abc' for 'missile_length'",
+                "line 5: invalid value '-5' for 'score_small'",
+                "line 6: unknown key 'foo'",
+                "line 7: expected 'key=value', found 'no separator'",
+            ]
+        );
+
+        let (tuning, errors) = Tuning::parse("asteroid_small_size=80", &previous);
+        assert_eq!(tuning, previous);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_resets_removed_keys() {
+        let previous = Tuning {
+            asteroid_large_size: 90.0,
+            score_small: 7,
+            ..Tuning::default()
+        };
+        let (tuning, errors) = Tuning::parse("score_small=7\nmissile_length=abc", &previous);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(tuning.score_small, 7);
+        assert_eq!(
+            tuning.asteroid_large_size,
+            Tuning::default().asteroid_large_size
+        );
+        assert_eq!(tuning.missile_length, Tuning::default().missile_length);
+    }
+
+    #[test]
+    fn test_bundled_tuning_file_matches_defaults() {
+        let content = fs::read_to_string(TUNING_FILE).unwrap();
+        let previous = Tuning {
+            score_small: 1,
+            ..Tuning::default()
+        };
+        let (tuning, errors) = Tuning::parse(&content, &previous);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(tuning, Tuning::default());
+    }
+}
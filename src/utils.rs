@@ -1,8 +1,13 @@
+use crate::achievement::Achievements;
 use crate::asteroid::Asteroid;
+use crate::bind::Input;
+use crate::difficulty::Difficulty;
 use crate::draw_background;
 use crate::sound::Sound;
+use crate::tuning::tuning;
 use macroquad::prelude::*;
 use std::fs;
+use std::path::Path;
 use std::time::Instant;
 
 /// Gestion de la touche 'Entrée'.
@@ -24,6 +29,24 @@ pub fn handle_enter_press(last_press: &mut Instant) -> bool {
     false
 }
 
+/// Calcule la première ligne affichée d'une liste déroulante.
+/// Garde l'option sélectionnée visible sans laisser de lignes vides en fin de liste.
+///
+/// # Arguments
+/// - `selected` L'indice de l'option sélectionnée.
+/// - `first` La première ligne actuellement affichée.
+/// - `visible` Le nombre de lignes affichables (au moins 1).
+/// - `len` Le nombre total d'options.
+///
+/// # Returns
+/// - `usize` La nouvelle première ligne affichée.
+///
+pub fn scroll_offset(selected: usize, first: usize, visible: usize, len: usize) -> usize {
+    first
+        .clamp((selected + 1).saturating_sub(visible), selected)
+        .min(len.saturating_sub(visible))
+}
+
 pub fn get_top_level_directory_names(dir_path: &str) -> Vec<String> {
     let mut directories = Vec::new();
 
@@ -42,13 +65,20 @@ pub fn get_top_level_directory_names(dir_path: &str) -> Vec<String> {
 }
 
 /// Permet de choisir une image dans un dossier.
+/// Les images verrouillées par un succès non débloqué ne sont pas proposées.
 ///
 /// # Arguments
 /// - `folder_path` Le chemin du dossier contenant les images.
 /// - `sound` L'objet Sound qui permettra de jouer les sons associés.
+/// - `achievements` Les succès débloqués par le joueur.
 ///
-pub async fn choose_image_from_folder(folder_path: &str, sound: &Sound) -> Option<String> {
-    let textures = load_textures_from_folder(folder_path).await;
+pub async fn choose_image_from_folder(
+    folder_path: &str,
+    sound: &Sound,
+    achievements: &Achievements,
+) -> Option<String> {
+    let mut textures = load_textures_from_folder(folder_path).await;
+    textures.retain(|(path, _)| !achievements.is_skin_locked(Path::new(path)));
     let mut last_enter_press = Instant::now();
 
     if textures.is_empty() {
@@ -132,18 +162,18 @@ async fn load_textures_from_folder(folder_path: &str) -> Vec<(String, Texture2D)
     textures
 }
 
-pub fn sigmoid_range_secs(time: f64, t0: f64) -> f64 {
-    let l = 1.0;
-    let min_range = 0.4;
-    let k = 0.05;
+pub fn sigmoid_range_secs(time: f64, t0: f64, difficulty: Difficulty) -> f64 {
+    let l = tuning().spawn_interval_max;
+    let min_range = difficulty.min_spawn_interval();
+    let k = difficulty.ramp_rate();
 
     min_range + (l - min_range) / (1.0 + (k * (time - t0)).exp())
 }
 
-pub fn sigmoid_speed(time: f64, mode: &str) -> f64 {
-    let l = if mode == "classic" { 1.0 } else { 2.5 };
-    let k = 0.05;
-    let t0 = 30.0;
+pub fn sigmoid_speed(time: f64, mode: &str, difficulty: Difficulty) -> f64 {
+    let l = difficulty.speed_gain(mode);
+    let k = difficulty.ramp_rate();
+    let t0 = tuning().speed_ramp_midpoint;
 
     1.0 + (l / (1.0 + (-k * (time - t0)).exp()))
 }
@@ -170,17 +200,30 @@ pub async fn background_asteroids(
     }
 }
 
-pub async fn pressed_key(
+/// Attend que le joueur presse une touche ou un bouton de la souris.
+/// 'Entrée' est ignorée (elle sert à valider dans les menus).
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids.
+///
+/// # Returns
+/// - `Input` L'entrée pressée.
+///
+pub async fn pressed_input(
     background_texture: &Texture2D,
     asteroid_texture: &Texture2D,
     asteroids: &mut Vec<Asteroid>,
-) -> KeyCode {
+) -> Input {
+    // La touche ayant ouvert la saisie ne doit pas être prise en compte.
+    next_frame().await;
     loop {
         clear_background(BLACK);
         background_asteroids(background_texture, asteroid_texture, asteroids).await;
 
-        let text = "Press a key";
-        let dimension = measure_text("Press a key", None, 50, 1.0);
+        let text = "Press a key or a mouse button";
+        let dimension = measure_text(text, None, 50, 1.0);
         draw_text(
             text,
             (screen_width() - dimension.width) / 2.0,
@@ -189,10 +232,79 @@ pub async fn pressed_key(
             WHITE,
         );
         if let Some(key) = get_last_key_pressed() {
-            if key != KeyCode::Enter && key != KeyCode::Backspace && key != KeyCode::Space {
-                break key;
+            if key != KeyCode::Enter {
+                break Input::Key(key);
+            }
+        }
+        for button in [MouseButton::Left, MouseButton::Middle, MouseButton::Right] {
+            if is_mouse_button_pressed(button) {
+                return Input::Mouse(button);
+            }
+        }
+        next_frame().await;
+    }
+}
+
+/// Saisie d'un nom (lettres, chiffres, '-' et '_').
+///
+/// # Arguments
+/// - `title` Le titre de l'écran de saisie.
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some(String)` Le nom saisi.
+/// - `None` si le joueur annule ('Échap').
+///
+pub async fn enter_name(
+    title: &str,
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<String> {
+    let mut name = String::new();
+    while get_char_pressed().is_some() {}
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        while let Some(c) = get_char_pressed() {
+            if (c.is_ascii_alphanumeric() || c == '-' || c == '_') && name.len() < 20 {
+                name.push(c);
             }
         }
+        if is_key_pressed(KeyCode::Backspace) {
+            name.pop();
+        }
+
+        let lines = [
+            title.to_string(),
+            format!("{}_", name),
+            "Press 'Enter' to save or 'Esc' to cancel".to_string(),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let font_size = if i == 0 { 50 } else { 40 };
+            let dimension = measure_text(line, None, font_size, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                200.0 + i as f32 * 150.0,
+                font_size as f32,
+                if i == 1 { YELLOW } else { WHITE },
+            );
+        }
+
+        if is_key_pressed(KeyCode::Enter) && !name.is_empty() {
+            sound.play_sound_effect("select_menu");
+            return Some(name);
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
         next_frame().await;
     }
 }
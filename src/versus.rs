@@ -0,0 +1,514 @@
+use super::draw_background;
+use super::draw_time;
+use crate::bind::{Action, Bind};
+use crate::blackhole::BlackHole;
+use crate::control::ControlScheme;
+use crate::coop::{PLAYER_COLORS, PLAYER_COUNT};
+use crate::difficulty::Difficulty;
+use crate::gui::{draw_player_panel, draw_tuning_errors, pause};
+use crate::settings::Settings;
+use crate::shipclass::ShipDefinition;
+use crate::sound::Sound;
+use crate::tuning;
+use crate::utils::{background_asteroids, handle_enter_press};
+use crate::{asteroid::Asteroid, missile::Missile, spaceship::Spaceship};
+use macroquad::prelude::*;
+use std::cmp::Ordering;
+use std::time::Instant;
+
+/// Nombre d'éliminations par défaut pour remporter un match.
+pub const DEFAULT_KILLS_TO_WIN: u32 = 5;
+/// Nombre maximal d'astéroïds présents dans l'arène.
+const MAX_ASTEROIDS: usize = 6;
+/// Nombre maximal de trous noirs présents dans l'arène.
+const MAX_BLACKHOLES: usize = 2;
+/// Délai (en secondes) entre deux apparitions d'astéroïds.
+const ASTEROID_RANGE_SECS: f64 = 2.0;
+
+/// Positions de départ des joueurs (de part et d'autre de l'écran).
+fn spawn_positions() -> [Vec2; PLAYER_COUNT] {
+    [
+        vec2(screen_width() * 0.25, screen_height() / 2.0),
+        vec2(screen_width() * 0.75, screen_height() / 2.0),
+    ]
+}
+
+/// Attribue une élimination en fonction de la victime.
+/// En duel, une élimination par l'environnement (astéroïd, trou noir) profite à l'adversaire.
+///
+/// # Arguments
+/// - `victim` L'indice du joueur éliminé.
+///
+/// # Returns
+/// - `usize` L'indice du joueur crédité de l'élimination.
+///
+pub fn credited_player(victim: usize) -> usize {
+    (victim + 1) % PLAYER_COUNT
+}
+
+/// Vainqueur du match : le joueur qui a atteint le nombre d'éliminations demandé en étant seul en tête.
+/// En cas d'égalité (éliminations simultanées), le match continue jusqu'à ce qu'un joueur mène.
+///
+/// # Arguments
+/// - `kills` Les éliminations de chaque joueur.
+/// - `kills_to_win` Le nombre d'éliminations pour gagner le match.
+///
+/// # Returns
+/// - `Some(usize)` L'indice du vainqueur.
+/// - `None` si le match n'est pas terminé.
+///
+pub fn match_winner(kills: &[u32; PLAYER_COUNT], kills_to_win: u32) -> Option<usize> {
+    let leader = match kills[0].cmp(&kills[1]) {
+        Ordering::Greater => 0,
+        Ordering::Less => 1,
+        Ordering::Equal => return None,
+    };
+    (kills[leader] >= kills_to_win).then_some(leader)
+}
+
+/// Affichage du jeu pour le mode versus.
+///
+/// # Arguments
+/// - `textures` Les textures (astéroïds, trous noirs, arrière-plan).
+/// - `spaceship_textures` Les textures des vaisseaux (une par joueur).
+/// - `asteroids` Les astéroids.
+/// - `blackholes` Les trous noirs.
+/// - `players` Les vaisseaux des joueurs.
+/// - `time` Le temps de jeu.
+/// - `missiles` Les missiles tirés par les joueurs.
+/// - `kills` Les éliminations de chaque joueur.
+/// - `kills_to_win` Le nombre d'éliminations pour gagner le match.
+///
+#[allow(clippy::too_many_arguments)]
+pub fn draw_versus(
+    textures: [&Texture2D; 3],
+    spaceship_textures: [&Texture2D; PLAYER_COUNT],
+    asteroids: &Vec<Asteroid>,
+    blackholes: &Vec<BlackHole>,
+    players: &[Spaceship; PLAYER_COUNT],
+    time: f64,
+    missiles: &Vec<Missile>,
+    kills: &[u32; PLAYER_COUNT],
+    kills_to_win: u32,
+) {
+    draw_background(textures[2]);
+
+    for asteroid in asteroids {
+        asteroid.draw_asteroid(textures[0]);
+    }
+    for blackhole in blackholes {
+        blackhole.draw_blackhole(textures[1]);
+    }
+    for (i, player) in players.iter().enumerate() {
+        player.draw_spaceship(spaceship_textures[i], time);
+    }
+    for missile in missiles {
+        missile.draw_missile_with_color(PLAYER_COLORS[missile.owner]);
+    }
+
+    draw_time(&format!("{:.2}", time));
+    for (i, player) in players.iter().enumerate() {
+        let x = 10.0 + i as f32 * 260.0;
+        draw_player_panel(&format!("P{}", i + 1), player, x, PLAYER_COLORS[i], time);
+        draw_text(
+            &format!("Kills : {} / {}", kills[i], kills_to_win),
+            x + 10.0,
+            180.0,
+            25.0,
+            PLAYER_COLORS[i],
+        );
+    }
+    if kills[0] == kills[1] && kills[0] >= kills_to_win {
+        let text = "Sudden death : next kill wins !";
+        let dimension = measure_text(text, None, 40, 1.0);
+        draw_text(
+            text,
+            (screen_width() - dimension.width) / 2.0,
+            screen_height() - 60.0,
+            40.0,
+            YELLOW,
+        );
+    }
+}
+
+/// Mise à jour du jeu pour le mode versus.
+/// Les vaisseaux peuvent se tirer dessus, les astéroïds et les trous noirs sont des dangers de l'arène.
+///
+/// # Arguments
+/// - `asteroids` Les astéroids.
+/// - `blackholes` Les trous noirs.
+/// - `last_add_asteroid` Le temps de la dernière apparition d'astéroid.
+/// - `players` Les vaisseaux des joueurs.
+/// - `time` Le temps de jeu.
+/// - `last_shoot` Le temps du dernier tir de chaque joueur.
+/// - `missiles` Les missiles tirés par les joueurs.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les touches de chaque joueur.
+///
+/// # Returns
+/// - `Vec<usize>` Les joueurs crédités d'une élimination pendant cette frame.
+///
+#[allow(clippy::too_many_arguments)]
+pub async fn update_versus(
+    asteroids: &mut Vec<Asteroid>,
+    blackholes: &mut Vec<BlackHole>,
+    last_add_asteroid: &mut f64,
+    players: &mut [Spaceship; PLAYER_COUNT],
+    time: f64,
+    last_shoot: &mut [f64; PLAYER_COUNT],
+    missiles: &mut Vec<Missile>,
+    sound: &Sound,
+    bindings: &[Bind; PLAYER_COUNT],
+) -> Vec<usize> {
+    if asteroids.len() < MAX_ASTEROIDS
+        && Asteroid::push_or_dont(asteroids, last_add_asteroid, time, ASTEROID_RANGE_SECS, 0.0)
+    {
+        *last_add_asteroid = time;
+    }
+    for asteroid in &mut *asteroids {
+        asteroid.move_asteroid(true, 1.0);
+    }
+    if blackholes.len() < MAX_BLACKHOLES {
+        Asteroid::what_collide_asteroids(
+            &mut *asteroids,
+            &mut *blackholes,
+            Difficulty::default().blackhole_chance(),
+        );
+    }
+    for blackhole in &mut *blackholes {
+        blackhole.update_rotation();
+    }
+
+    let mut credited = Vec::new();
+    for (i, player) in players.iter_mut().enumerate() {
+        player.update_position(ControlScheme::Tank, &bindings[i]);
+
+        let hazard_kill = player.asteroid_impact(&mut *asteroids, time, sound).await
+            || player.spaceship_blackhole_collision(&mut *blackholes).await;
+        if hazard_kill {
+            player.shield = 0;
+            credited.push(credited_player(i));
+            continue;
+        }
+
+        if Missile::do_i_shoot(&bindings[i], player, last_shoot[i], time) {
+            missiles.extend(Missile::fire(player, 5.0, i));
+            last_shoot[i] = time;
+            sound.play_sound_effect("shoot");
+        }
+    }
+
+    for missile in &mut *missiles {
+        missile.move_missile();
+    }
+    Missile::clear_missiles(&mut *missiles);
+    Missile::what_collide_missile(&mut *missiles, &mut *asteroids, players, sound, time);
+    for (killer, _victim) in Missile::what_collide_spaceship(&mut *missiles, players, sound, time) {
+        credited.push(killer);
+    }
+    credited
+}
+
+/// Joue un match du mode versus, jusqu'à ce qu'un joueur atteigne le nombre d'éliminations demandé
+/// en étant seul en tête (voir `match_winner`).
+/// Après chaque élimination, l'arène est réinitialisée et les vaisseaux réapparaissent.
+///
+/// # Returns
+/// - `Some((winner, kills))` Le vainqueur et les éliminations de chaque joueur à la fin du match.
+/// - `None` si le match a été quitté.
+///
+async fn play_match(
+    textures: [&Texture2D; 3],
+    spaceship_textures: [&Texture2D; PLAYER_COUNT],
+    kills_to_win: u32,
+    sound: &mut Sound,
+    bindings: &mut [Bind; PLAYER_COUNT],
+) -> Option<(usize, [u32; PLAYER_COUNT])> {
+    let start_time = get_time();
+    let mut asteroids: Vec<Asteroid> = Vec::new();
+    let mut blackholes: Vec<BlackHole> = Vec::new();
+    let mut missiles: Vec<Missile> = Vec::new();
+    let mut last_add_asteroid: f64 = 0.0;
+    let mut total_paused_time: f64 = 0.0;
+    let mut last_shoot = [0.0; PLAYER_COUNT];
+    let mut kills = [0; PLAYER_COUNT];
+    let settings = Settings::load();
+    let mut players = [
+        settings.new_spaceship(&settings.ship_definition()),
+        settings.new_spaceship(&ShipDefinition::default()),
+    ];
+    for (player, position) in players.iter_mut().zip(spawn_positions()) {
+        player.respawn(position, 0.0);
+    }
+
+    loop {
+        let time = get_time() - start_time - total_paused_time;
+        // Prise en compte des modifications du fichier d'équilibrage
+        tuning::reload_if_changed();
+
+        total_paused_time += pause(textures[2], textures[0], sound, bindings).await;
+
+        if bindings[0].is_down(Action::Quit) {
+            return None;
+        }
+
+        let credited = update_versus(
+            &mut asteroids,
+            &mut blackholes,
+            &mut last_add_asteroid,
+            &mut players,
+            time,
+            &mut last_shoot,
+            &mut missiles,
+            sound,
+            bindings,
+        )
+        .await;
+
+        if !credited.is_empty() {
+            sound.play_sound_effect("boom");
+            for player in credited {
+                kills[player] += 1;
+            }
+            if let Some(winner) = match_winner(&kills, kills_to_win) {
+                return Some((winner, kills));
+            }
+            // Nouvelle manche : l'arène est réinitialisée.
+            asteroids.clear();
+            blackholes.clear();
+            missiles.clear();
+            last_add_asteroid = time;
+            for (player, position) in players.iter_mut().zip(spawn_positions()) {
+                player.respawn(position, time);
+            }
+        }
+
+        draw_versus(
+            textures,
+            spaceship_textures,
+            &asteroids,
+            &blackholes,
+            &players,
+            time,
+            &missiles,
+            &kills,
+            kills_to_win,
+        );
+        draw_tuning_errors();
+        next_frame().await;
+    }
+}
+
+/// Choix du nombre d'éliminations nécessaires pour remporter un match.
+///
+/// # Arguments
+/// - `background_texture` La texture de l'arrière-plan.
+/// - `asteroid_texture` La texture des astéroids.
+/// - `asteroids` Les astéroids du menu.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `Some(kills)` Le nombre d'éliminations choisi.
+/// - `None` si le joueur revient au menu.
+///
+pub async fn choose_kills_to_win(
+    background_texture: &Texture2D,
+    asteroid_texture: &Texture2D,
+    asteroids: &mut Vec<Asteroid>,
+    sound: &Sound,
+) -> Option<u32> {
+    let mut kills_to_win = DEFAULT_KILLS_TO_WIN;
+    let mut last_enter_press = Instant::now();
+    loop {
+        clear_background(BLACK);
+        background_asteroids(background_texture, asteroid_texture, asteroids).await;
+
+        let lines = [
+            "Versus".to_string(),
+            format!("< First to {} kills >", kills_to_win),
+            "Press 'Enter' to start".to_string(),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let font_size = if i == 0 { 50 } else { 40 };
+            let dimension = measure_text(line, None, font_size, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                200.0 + i as f32 * 150.0,
+                font_size as f32,
+                if i == 1 { YELLOW } else { WHITE },
+            );
+        }
+
+        if is_key_pressed(KeyCode::Left) && kills_to_win > 1 {
+            kills_to_win -= 1;
+            sound.play_sound_effect("select_menu");
+        }
+        if is_key_pressed(KeyCode::Right) && kills_to_win < 20 {
+            kills_to_win += 1;
+            sound.play_sound_effect("select_menu");
+        }
+        if handle_enter_press(&mut last_enter_press) {
+            sound.play_sound_effect("select_menu");
+            return Some(kills_to_win);
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            sound.play_sound_effect("select_menu");
+            return None;
+        }
+        next_frame().await;
+    }
+}
+
+/// Tableau des scores affiché entre deux matchs.
+///
+/// # Arguments
+/// - `match_number` Le numéro du match terminé.
+/// - `winner` Le vainqueur du match.
+/// - `kills` Les éliminations de chaque joueur lors du match.
+/// - `match_wins` Les matchs gagnés par chaque joueur.
+/// - `sound` Les sons du jeu.
+///
+/// # Returns
+/// - `true` pour jouer un nouveau match, `false` pour revenir au menu.
+///
+async fn versus_scoreboard(
+    match_number: u32,
+    winner: usize,
+    kills: &[u32; PLAYER_COUNT],
+    match_wins: &[u32; PLAYER_COUNT],
+    sound: &Sound,
+) -> bool {
+    sound.play_sound_effect("win");
+    loop {
+        clear_background(BLACK);
+        let lines = [
+            format!("Match {} : Player {} wins !", match_number, winner + 1),
+            format!("Kills : {} - {}", kills[0], kills[1]),
+            format!("Matches won : {} - {}", match_wins[0], match_wins[1]),
+            "Press 'Enter' for the next match or 'Backspace' to leave".to_string(),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let font_size = if i == 0 { 60 } else { 40 };
+            let dimension = measure_text(line, None, font_size, 1.0);
+            draw_text(
+                line,
+                (screen_width() - dimension.width) / 2.0,
+                200.0 + i as f32 * 100.0,
+                font_size as f32,
+                if i == 0 { PLAYER_COLORS[winner] } else { WHITE },
+            );
+        }
+
+        if is_key_pressed(KeyCode::Enter) {
+            return true;
+        }
+        if is_key_pressed(KeyCode::Backspace) || is_key_pressed(KeyCode::Escape) {
+            return false;
+        }
+        next_frame().await;
+    }
+}
+
+/// Boucle principale du mode versus (deux joueurs s'affrontent sur un même clavier).
+/// Les matchs s'enchaînent, séparés par un tableau des scores.
+///
+/// # Arguments
+/// - `skins` Chemins des skins (astéroïdes, trous noirs, arrière-plan, vaisseau du premier joueur).
+/// - `player_two_skin` Chemin du skin du vaisseau du second joueur.
+/// - `kills_to_win` Le nombre d'éliminations pour remporter un match.
+/// - `sound` Les sons du jeu.
+/// - `bindings` Les Binds des deux joueurs.
+///
+pub async fn play_versus(
+    skins: [&str; 4],
+    player_two_skin: &str,
+    kills_to_win: u32,
+    sound: &mut Sound,
+    bindings: &mut [Bind; PLAYER_COUNT],
+) {
+    let asteroid_texture = load_texture(skins[0]).await.unwrap();
+    asteroid_texture.set_filter(FilterMode::Linear);
+    let blackhole_texture = load_texture(skins[1]).await.unwrap();
+    blackhole_texture.set_filter(FilterMode::Linear);
+    let background_texture = load_texture(skins[2]).await.unwrap();
+    background_texture.set_filter(FilterMode::Nearest);
+    let player_one_texture = load_texture(skins[3]).await.unwrap();
+    player_one_texture.set_filter(FilterMode::Linear);
+    let player_two_texture = load_texture(player_two_skin).await.unwrap();
+    player_two_texture.set_filter(FilterMode::Linear);
+
+    let mut match_wins = [0; PLAYER_COUNT];
+    let mut match_number = 1;
+    loop {
+        let (winner, kills) = match play_match(
+            [&asteroid_texture, &blackhole_texture, &background_texture],
+            [&player_one_texture, &player_two_texture],
+            kills_to_win,
+            sound,
+            bindings,
+        )
+        .await
+        {
+            Some(result) => result,
+            None => return,
+        };
+        match_wins[winner] += 1;
+
+        if !versus_scoreboard(match_number, winner, &kills, &match_wins, sound).await {
+            return;
+        }
+        match_number += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asteroid::AsteroidShape;
+
+    #[test]
+    fn test_hazard_kill_credits_opponent() {
+        assert_eq!(credited_player(0), 1);
+        assert_eq!(credited_player(1), 0);
+    }
+
+    #[test]
+    fn test_match_winner_needs_a_lead() {
+        assert_eq!(match_winner(&[4, 2], 5), None);
+        assert_eq!(match_winner(&[5, 2], 5), Some(0));
+        assert_eq!(match_winner(&[5, 5], 5), None);
+        assert_eq!(match_winner(&[5, 6], 5), Some(1));
+    }
+
+    #[test]
+    fn test_missile_credits_owner() {
+        let mut players = [
+            Spaceship::new(&ShipDefinition::default()),
+            Spaceship::new(&ShipDefinition::default()),
+        ];
+        let mut missiles = vec![Missile::new(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(1.0, 1.0),
+            5.0,
+            2.0,
+            1,
+        )];
+        let mut asteroids = vec![Asteroid::new(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(0.0, 0.0),
+            AsteroidShape::Small,
+            20.0,
+        )];
+        Missile::what_collide_missile(
+            &mut missiles,
+            &mut asteroids,
+            &mut players,
+            &Sound::new(),
+            1.0,
+        );
+        assert_eq!(players[0].score, 0);
+        assert_eq!(players[1].score, 100);
+        assert!(missiles.is_empty());
+    }
+}